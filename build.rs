@@ -194,19 +194,47 @@ fn binding_picosat(version: &str) {
     }
     build.build("external/satgalaxy_picosat.h");
 }
+/// Link against a system-installed `satgalaxy_<name>` library instead of compiling
+/// the bundled sources.
+///
+/// `SATGALAXY_<NAME>_LIB_DIR` takes precedence over pkg-config. Returns `false` if the
+/// library could not be found, so the caller can fall back to the vendored build.
+fn link_system(name: &str) -> bool {
+    let lib = format!("satgalaxy_{}", name);
+    let env_key = format!("SATGALAXY_{}_LIB_DIR", name.to_uppercase());
+    println!("cargo:rerun-if-env-changed={}", env_key);
+    if let Ok(dir) = env::var(&env_key) {
+        if Path::new(&dir).is_dir() {
+            println!("cargo:rustc-link-search=native={}", dir);
+            println!("cargo:rustc-link-lib={}", lib);
+            return true;
+        }
+        println!("cargo:warning={} is not a directory", env_key);
+    }
+    match pkg_config::Config::new().probe(&lib) {
+        Ok(_) => true,
+        Err(err) => {
+            println!(
+                "cargo:warning=system {} not found ({}), building the bundled sources",
+                lib, err
+            );
+            false
+        }
+    }
+}
 fn main() {
     println!("cargo:rerun-if-changed=build.rs");
-    if cfg!(feature = "cadical") {
+    if cfg!(feature = "cadical") && !(cfg!(feature = "system-cadical") && link_system("cadical")) {
         binding_cadical("2.1.3");
     }
-    if cfg!(feature = "glucose") {
+    if cfg!(feature = "glucose") && !(cfg!(feature = "system-glucose") && link_system("glucose")) {
         binding_glucose("4.2.1");
     }
-    if cfg!(feature = "minisat") {
+    if cfg!(feature = "minisat") && !(cfg!(feature = "system-minisat") && link_system("minisat")) {
         binding_minisat();
     }
 
-    if cfg!(feature = "picosat") {
+    if cfg!(feature = "picosat") && !(cfg!(feature = "system-picosat") && link_system("picosat")) {
         binding_picosat("960");
     }
     let target = env::var("TARGET").unwrap();
@@ -0,0 +1,56 @@
+//! Compares the fast DIMACS lexer with the pest grammar, and with the parallel lexer
+//! when the `parallel` feature is enabled.
+//!
+//! ```sh
+//! cargo run --release --features parallel --example dimacs_engines -- [num_clauses]
+//! ```
+use std::{fmt::Write, time::Instant};
+
+use satgalaxy::parser::{parse_dimacs_cnf, ParseEngine, ParserOptions};
+
+fn main() {
+    let num_clauses: usize = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(1_000_000);
+    let num_vars = (num_clauses / 4).max(3) as u64;
+
+    // Deterministic random 3-SAT instance (xorshift).
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut input = String::new();
+    writeln!(input, "p cnf {} {}", num_vars, num_clauses).unwrap();
+    for _ in 0..num_clauses {
+        for _ in 0..3 {
+            let var = (next() % num_vars + 1) as i64;
+            let lit = if next() % 2 == 0 { var } else { -var };
+            write!(input, "{} ", lit).unwrap();
+        }
+        writeln!(input, "0").unwrap();
+    }
+
+    let engines = [
+        ParseEngine::Grammar,
+        ParseEngine::Auto,
+        #[cfg(feature = "parallel")]
+        ParseEngine::Parallel,
+    ];
+    let mut results = vec![];
+    for engine in engines {
+        let options = ParserOptions {
+            engine,
+            ..ParserOptions::default()
+        };
+        let start = Instant::now();
+        let mut cnf = Vec::new();
+        parse_dimacs_cnf(&input, options, &mut cnf).unwrap();
+        println!("{:?}: {:?}", engine, start.elapsed());
+        results.push(cnf);
+    }
+    assert!(results.iter().all(|cnf| *cnf == results[0]));
+}
@@ -0,0 +1,60 @@
+//! Compares the whole-input DIMACS parser with the streaming one.
+//!
+//! ```sh
+//! cargo run --release --example dimacs_streaming -- [num_clauses]
+//! ```
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    time::Instant,
+};
+
+use satgalaxy::parser::{read_dimacs_from_file, read_dimacs_streaming, ParseMode};
+
+fn main() {
+    let num_clauses: usize = std::env::args()
+        .nth(1)
+        .and_then(|arg| arg.parse().ok())
+        .unwrap_or(1_000_000);
+    let num_vars = (num_clauses / 4).max(3) as u64;
+    let path = std::env::temp_dir().join("satgalaxy_dimacs_streaming.cnf");
+
+    // Deterministic random 3-SAT instance (xorshift).
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut out = BufWriter::new(File::create(&path).unwrap());
+    writeln!(out, "p cnf {} {}", num_vars, num_clauses).unwrap();
+    for _ in 0..num_clauses {
+        for _ in 0..3 {
+            let var = (next() % num_vars + 1) as i64;
+            let lit = if next() % 2 == 0 { var } else { -var };
+            write!(out, "{} ", lit).unwrap();
+        }
+        writeln!(out, "0").unwrap();
+    }
+    out.flush().unwrap();
+    drop(out);
+
+    let start = Instant::now();
+    let mut whole = Vec::new();
+    read_dimacs_from_file(&path, ParseMode::Inferred, &mut whole).unwrap();
+    println!("read_dimacs_from_file: {:?}", start.elapsed());
+
+    let start = Instant::now();
+    let mut streamed = Vec::new();
+    read_dimacs_streaming(
+        File::open(&path).unwrap(),
+        ParseMode::Inferred,
+        &mut streamed,
+    )
+    .unwrap();
+    println!("read_dimacs_streaming: {:?}", start.elapsed());
+
+    assert_eq!(whole, streamed);
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,190 @@
+//! `satgalaxy-cli`: solve a DIMACS CNF file with any enabled backend and print the
+//! result in SAT competition output format.
+//!
+//! ```sh
+//! cargo run --release --features cli --bin satgalaxy-cli -- --solver picosat \
+//!     --timeout 60 --stats --proof proof.rup.gz formula.cnf.xz
+//! ```
+//!
+//! The exit code is 10 for SATISFIABLE, 20 for UNSATISFIABLE, 0 for UNKNOWN and 1 on
+//! errors, following the competition rules.
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use clap::Parser;
+use satgalaxy::{
+    errors::{ParserError, SolverError},
+    output::write_status,
+    parser::{read_dimacs_from_reader, ParserOptions},
+    signal,
+    solver::{Backend, RawStatus, SatSolver, SatStatus},
+};
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Solve a DIMACS CNF file with a satgalaxy backend")]
+struct Args {
+    /// The DIMACS CNF file, optionally gzip, xz, zstd or bzip2 compressed. Reads
+    /// standard input if omitted or `-`.
+    input: Option<PathBuf>,
+    /// The backend: cadical, glucose, minisat or picosat. Defaults to picosat with
+    /// `--proof`, and to the first enabled one, in that order, otherwise.
+    #[arg(long)]
+    solver: Option<Backend>,
+    /// Give up and report UNKNOWN after this many seconds. Ignored, with a warning, by
+    /// minisat and glucose, which cannot be stopped.
+    #[arg(long, value_name = "SECONDS", value_parser = parse_seconds)]
+    timeout: Option<Duration>,
+    /// Write a RUP proof to this file if the formula is unsatisfiable. Only picosat
+    /// produces proofs; a compressed extension compresses the proof.
+    #[arg(long, value_name = "FILE")]
+    proof: Option<PathBuf>,
+    /// Print timings and search statistics as `c` lines.
+    #[arg(long)]
+    stats: bool,
+}
+
+fn parse_seconds(arg: &str) -> Result<Duration, String> {
+    let seconds: f64 = arg.parse().map_err(|err| format!("{err}"))?;
+    Duration::try_from_secs_f64(seconds).map_err(|err| err.to_string())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(SatStatus::Satisfiable(_)) => ExitCode::from(10),
+        Ok(SatStatus::Unsatisfiable) => ExitCode::from(20),
+        Ok(SatStatus::Unknown) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("c error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<SatStatus, ParserError> {
+    let backend = match args.solver {
+        Some(backend) => backend,
+        None if args.proof.is_some() => Backend::PicoSAT,
+        None => Backend::ALL
+            .into_iter()
+            .find(|backend| backend.is_enabled())
+            .ok_or(SolverError::UnsupportedOperation(
+                "solving without a backend",
+            ))?,
+    };
+    let status = match &args.proof {
+        Some(proof) => solve_with_proof(args, backend, proof)?,
+        None => solve(args, &mut backend.build()?)?,
+    };
+    let mut out = BufWriter::new(io::stdout().lock());
+    write_status(&mut out, &status)?;
+    out.flush()?;
+    Ok(status)
+}
+
+/// Read the input into `solver` and solve it, printing statistics if asked to.
+///
+/// A satisfiable status carries the full model, one literal per variable.
+fn solve<S: SatSolver>(args: &Args, solver: &mut S) -> Result<SatStatus, ParserError> {
+    let start = Instant::now();
+    match args.input.as_deref() {
+        Some(path) if path.as_os_str() != "-" => {
+            read_dimacs_from_reader(File::open(path)?, ParserOptions::default(), solver)?
+        }
+        _ => read_dimacs_from_reader(io::stdin().lock(), ParserOptions::default(), solver)?,
+    }
+    let parsed = Instant::now();
+    // Minisat and Glucose cannot be stopped, so they are simply not registered, and
+    // the handler is only installed once a solver listens to it: until then, Ctrl-C
+    // and SIGTERM kill the process as usual.
+    let registration = signal::register(solver).ok();
+    if registration.is_some() {
+        signal::install_handler()?;
+    }
+    let status = match args
+        .timeout
+        .map(|timeout| solver.solve_with_timeout(timeout))
+    {
+        Some(Err(SolverError::UnsupportedOperation(_))) => {
+            println!("c warning: the backend does not support --timeout, solving without it");
+            solver.solve_sat()?
+        }
+        Some(status) => status?,
+        None => solver.solve_sat()?,
+    };
+    drop(registration);
+    let solved = Instant::now();
+    if signal::interrupted() {
+        println!("c interrupted");
+    }
+    if args.stats {
+        print_stats(solver, parsed - start, solved - parsed)?;
+    }
+    Ok(match status {
+        RawStatus::Satisfiable => SatStatus::Satisfiable(solver.full_model()?),
+        RawStatus::Unsatisfiable => SatStatus::Unsatisfiable,
+        RawStatus::Unknown => SatStatus::Unknown,
+    })
+}
+
+#[cfg(feature = "picosat")]
+fn solve_with_proof(
+    args: &Args,
+    backend: Backend,
+    proof: &std::path::Path,
+) -> Result<SatStatus, ParserError> {
+    use satgalaxy::solver::picosat::{PicoSATSolver, TraceFormat};
+
+    if backend != Backend::PicoSAT {
+        let err = SolverError::UnsupportedOperation("proofs from backends other than picosat");
+        return Err(err.into());
+    }
+    let mut solver = PicoSATSolver::new();
+    if !solver.enable_trace_generation()? {
+        let err = SolverError::UnsupportedOperation("proofs without PicoSAT trace support");
+        return Err(err.into());
+    }
+    let status = solve(args, &mut solver)?;
+    if status == SatStatus::Unsatisfiable {
+        solver.write_trace(TraceFormat::Rup, proof)?;
+    }
+    Ok(status)
+}
+
+#[cfg(not(feature = "picosat"))]
+fn solve_with_proof(
+    _args: &Args,
+    _backend: Backend,
+    _proof: &std::path::Path,
+) -> Result<SatStatus, ParserError> {
+    Err(SolverError::UnsupportedOperation("proofs without the picosat feature").into())
+}
+
+fn print_stats<S: SatSolver>(
+    solver: &mut S,
+    parse_time: Duration,
+    solve_time: Duration,
+) -> Result<(), SolverError> {
+    let stats = solver.statistics()?;
+    println!("c parse time: {:.3}s", parse_time.as_secs_f64());
+    println!("c solve time: {:.3}s", solve_time.as_secs_f64());
+    for (name, value) in [
+        ("conflicts", stats.conflicts),
+        ("decisions", stats.decisions),
+        ("propagations", stats.propagations),
+        ("restarts", stats.restarts),
+        ("learned clauses", stats.learned_clauses),
+        ("clauses", stats.clauses),
+        ("memory bytes", stats.memory_bytes),
+    ] {
+        if let Some(value) = value {
+            println!("c {name}: {value}");
+        }
+    }
+    Ok(())
+}
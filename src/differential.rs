@@ -0,0 +1,126 @@
+//! Differential testing: every enabled backend must agree on the same formula.
+//!
+//! This module is enabled when the `testing` feature is activated.
+//!
+//! # Overview
+//! [`check`] solves a [`Problem`] with each enabled backend, requires them to agree on
+//! SAT/UNSAT and verifies every returned model against the clauses. [`fuzz`] runs the
+//! check on random 3-SAT instances at the satisfiability threshold, where both answers
+//! are common. Besides the crate's own tests, this lets downstream users sanity-check
+//! custom solver configurations.
+//!
+//! # Usage
+//! ```toml
+//! [dependencies]
+//! satgalaxy = { version = "x.y.z", features = ["testing"] }
+//! ```
+use thiserror::Error;
+
+use crate::errors::SolverError;
+use crate::generator::{Generator, RandomKSat};
+use crate::parser::Problem;
+use crate::solver::{Backend, SatStatus};
+use crate::verify::{verify_model, UnsatisfiedClause};
+
+/// A failed differential check.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum Disagreement {
+    #[error("{sat} reports SAT but {unsat} reports UNSAT")]
+    Status { sat: Backend, unsat: Backend },
+    #[error("{backend} returned a wrong model: {clause}")]
+    WrongModel {
+        backend: Backend,
+        clause: UnsatisfiedClause,
+    },
+    #[error("{backend} failed: {error}")]
+    Solver {
+        backend: Backend,
+        error: SolverError,
+    },
+}
+
+/// Solve `problem` with every enabled backend and check that they agree.
+///
+/// Returns `Some(true)` if all answered SAT, `Some(false)` if all answered UNSAT, and
+/// `None` if no backend gave a definite answer.
+/// # Example
+/// ```rust
+/// use satgalaxy::differential::check;
+/// use satgalaxy::parser::{AsDimacs, Problem};
+/// let mut problem = Problem::new();
+/// problem.push_clause(vec![1, 2]).unwrap();
+/// problem.push_clause(vec![-1]).unwrap();
+/// assert_ne!(check(&problem).unwrap(), Some(false));
+/// ```
+pub fn check(problem: &Problem) -> Result<Option<bool>, Disagreement> {
+    let backends: Vec<Backend> = Backend::ALL
+        .into_iter()
+        .filter(|b| b.is_enabled())
+        .collect();
+    check_backends(problem, &backends)
+}
+
+/// Like [`check`], restricted to `backends`.
+pub fn check_backends(
+    problem: &Problem,
+    backends: &[Backend],
+) -> Result<Option<bool>, Disagreement> {
+    let mut sat = None;
+    let mut unsat = None;
+    for &backend in backends {
+        let solve = || -> Result<SatStatus, SolverError> {
+            let mut solver = backend.build()?;
+            problem.load_into(&mut solver)?;
+            solver.solve_model()
+        };
+        match solve() {
+            Ok(SatStatus::Satisfiable(model)) => {
+                verify_model(&problem.clauses, &model)
+                    .map_err(|clause| Disagreement::WrongModel { backend, clause })?;
+                sat = sat.or(Some(backend));
+            }
+            Ok(SatStatus::Unsatisfiable) => unsat = unsat.or(Some(backend)),
+            Ok(SatStatus::Unknown) => {}
+            Err(error) => return Err(Disagreement::Solver { backend, error }),
+        }
+        if let (Some(sat), Some(unsat)) = (sat, unsat) {
+            return Err(Disagreement::Status { sat, unsat });
+        }
+    }
+    Ok(match (sat, unsat) {
+        (Some(_), _) => Some(true),
+        (_, Some(_)) => Some(false),
+        _ => None,
+    })
+}
+
+/// Run [`check`] on `rounds` random 3-SAT instances over `num_vars` variables (at
+/// least 3).
+///
+/// Round `i` uses `RandomKSat::with_ratio(3, num_vars, 4.26, seed + i)`, which is
+/// returned with the disagreement so the failing formula can be regenerated.
+pub fn fuzz(rounds: u64, num_vars: usize, seed: u64) -> Result<(), (RandomKSat, Disagreement)> {
+    for round in 0..rounds {
+        let instance = RandomKSat::with_ratio(3, num_vars.max(3), 4.26, seed.wrapping_add(round));
+        let problem = instance
+            .to_problem()
+            .expect("generated clauses are valid DIMACS");
+        check(&problem).map_err(|disagreement| (instance, disagreement))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::Pigeonhole;
+
+    #[test]
+    fn backends_agree() {
+        fuzz(20, 25, 1).unwrap();
+        let php = Pigeonhole::new(4).to_problem().unwrap();
+        let any_enabled = Backend::ALL.into_iter().any(|b| b.is_enabled());
+        assert_eq!(check(&php).unwrap(), any_enabled.then_some(false));
+        assert!(check_backends(&php, &[]).unwrap().is_none());
+    }
+}
@@ -0,0 +1,240 @@
+use crate::errors::ParserError;
+use crate::parser::AsDimacs;
+
+use super::VarManager;
+
+/// How a cardinality constraint is turned into clauses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CardEncoding {
+    /// One clause per set of `k + 1` literals, without auxiliary variables. The number of
+    /// clauses is binomial in `k`, so this only suits small `k` (pairwise for `k = 1`).
+    Pairwise,
+    /// Sinz's sequential counter: `O(n * k)` clauses and auxiliary variables.
+    SequentialCounter,
+    /// A totalizer tree of unary counters, each capped at `k + 1`.
+    #[default]
+    Totalizer,
+    /// The commander encoding: groups of `k + 2` literals are summarized by `k` ordered
+    /// commander variables, recursively, with pairwise constraints inside each group.
+    Commander,
+}
+
+/// Require that at most `k` of `lits` are true.
+/// # Example
+/// ```rust
+/// use satgalaxy::encoder::{cardinality::{at_most_k, CardEncoding}, VarManager};
+/// let mut vars = VarManager::new(4);
+/// let mut cnf: Vec<Vec<i32>> = Vec::new();
+/// at_most_k(&[1, 2, 3, 4], 2, CardEncoding::SequentialCounter, &mut vars, &mut cnf).unwrap();
+/// assert!(vars.max_var() > 4);
+/// ```
+pub fn at_most_k<D: AsDimacs>(
+    lits: &[i32],
+    k: usize,
+    encoding: CardEncoding,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    if k >= lits.len() {
+        return Ok(());
+    }
+    if k == 0 {
+        for &lit in lits {
+            dim.push_clause(vec![-lit])?;
+        }
+        return Ok(());
+    }
+    match encoding {
+        CardEncoding::Pairwise => pairwise(lits, k, dim),
+        CardEncoding::SequentialCounter => sequential_counter(lits, k, vars, dim),
+        CardEncoding::Totalizer => {
+            let outputs = totalizer(lits, k + 1, vars, dim)?;
+            dim.push_clause(vec![-outputs[k]])
+        }
+        CardEncoding::Commander => commander(lits, k, vars, dim),
+    }
+}
+
+/// Require that at least `k` of `lits` are true.
+///
+/// Encoded as at most `lits.len() - k` of the negated literals; if `k` exceeds the
+/// number of literals, the empty clause is emitted.
+pub fn at_least_k<D: AsDimacs>(
+    lits: &[i32],
+    k: usize,
+    encoding: CardEncoding,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    if k > lits.len() {
+        return dim.push_clause(vec![]);
+    }
+    let negated: Vec<i32> = lits.iter().map(|lit| -lit).collect();
+    at_most_k(&negated, lits.len() - k, encoding, vars, dim)
+}
+
+/// Every combination of `k + 1` literals has one false.
+fn pairwise<D: AsDimacs>(lits: &[i32], k: usize, dim: &mut D) -> Result<(), ParserError> {
+    let mut picked: Vec<usize> = (0..=k).collect();
+    loop {
+        dim.push_clause(picked.iter().map(|&i| -lits[i]).collect())?;
+        // Advance to the next combination in lexicographic order.
+        let Some(pos) = (0..=k)
+            .rev()
+            .find(|&pos| picked[pos] < lits.len() - (k + 1) + pos)
+        else {
+            return Ok(());
+        };
+        picked[pos] += 1;
+        for next in pos + 1..=k {
+            picked[next] = picked[next - 1] + 1;
+        }
+    }
+}
+
+fn sequential_counter<D: AsDimacs>(
+    lits: &[i32],
+    k: usize,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let n = lits.len();
+    // s[i][j]: at least j + 1 of lits[..=i] are true.
+    let s: Vec<Vec<i32>> = (0..n - 1)
+        .map(|_| (0..k).map(|_| vars.fresh()).collect())
+        .collect();
+    dim.push_clause(vec![-lits[0], s[0][0]])?;
+    for &aux in &s[0][1..] {
+        dim.push_clause(vec![-aux])?;
+    }
+    for i in 1..n - 1 {
+        dim.push_clause(vec![-lits[i], s[i][0]])?;
+        dim.push_clause(vec![-s[i - 1][0], s[i][0]])?;
+        for j in 1..k {
+            dim.push_clause(vec![-lits[i], -s[i - 1][j - 1], s[i][j]])?;
+            dim.push_clause(vec![-s[i - 1][j], s[i][j]])?;
+        }
+        dim.push_clause(vec![-lits[i], -s[i - 1][k - 1]])?;
+    }
+    dim.push_clause(vec![-lits[n - 1], -s[n - 2][k - 1]])
+}
+
+/// Build a totalizer over `lits` and return its unary outputs, capped at `cap`:
+/// `outputs[j]` is implied by at least `j + 1` of `lits` being true.
+fn totalizer<D: AsDimacs>(
+    lits: &[i32],
+    cap: usize,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<Vec<i32>, ParserError> {
+    if lits.len() == 1 {
+        return Ok(lits.to_vec());
+    }
+    let (left, right) = lits.split_at(lits.len() / 2);
+    let left = totalizer(left, cap, vars, dim)?;
+    let right = totalizer(right, cap, vars, dim)?;
+    let outputs: Vec<i32> = (0..(left.len() + right.len()).min(cap))
+        .map(|_| vars.fresh())
+        .collect();
+    for a in 0..=left.len() {
+        for b in 0..=right.len() {
+            if a + b == 0 || a + b > outputs.len() {
+                continue;
+            }
+            let mut clause = vec![outputs[a + b - 1]];
+            if a > 0 {
+                clause.push(-left[a - 1]);
+            }
+            if b > 0 {
+                clause.push(-right[b - 1]);
+            }
+            dim.push_clause(clause)?;
+        }
+    }
+    Ok(outputs)
+}
+
+fn commander<D: AsDimacs>(
+    lits: &[i32],
+    k: usize,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let group_size = k + 2;
+    if lits.len() <= group_size {
+        return pairwise(lits, k, dim);
+    }
+    let mut commanders = Vec::new();
+    for group in lits.chunks(group_size) {
+        if group.len() <= k {
+            // A group that can never exceed `k` stands for itself.
+            commanders.extend_from_slice(group);
+            continue;
+        }
+        let local: Vec<i32> = (0..k).map(|_| vars.fresh()).collect();
+        // Exactly k of the group and the negated commanders are true, so the number of
+        // true commanders equals the number of true group literals (up to k).
+        let mut members = group.to_vec();
+        members.extend(local.iter().map(|c| -c));
+        pairwise(&members, k, dim)?;
+        let negated: Vec<i32> = members.iter().map(|lit| -lit).collect();
+        pairwise(&negated, members.len() - k, dim)?;
+        // Commanders are set in order, which breaks the symmetry between them.
+        for pair in local.windows(2) {
+            dim.push_clause(vec![-pair[1], pair[0]])?;
+        }
+        commanders.extend(local);
+    }
+    commander(&commanders, k, vars, dim)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::extends;
+    use super::*;
+
+    const ENCODINGS: [CardEncoding; 4] = [
+        CardEncoding::Pairwise,
+        CardEncoding::SequentialCounter,
+        CardEncoding::Totalizer,
+        CardEncoding::Commander,
+    ];
+
+    fn check(n: i32, at_least: bool) {
+        // Mixed polarities make sure literals, not variables, are counted.
+        let lits: Vec<i32> = (1..=n).map(|v| if v % 3 == 0 { -v } else { v }).collect();
+        for k in 0..=n as usize + 1 {
+            for encoding in ENCODINGS {
+                let mut vars = VarManager::new(n);
+                let mut cnf: Vec<Vec<i32>> = Vec::new();
+                if at_least {
+                    at_least_k(&lits, k, encoding, &mut vars, &mut cnf).unwrap();
+                } else {
+                    at_most_k(&lits, k, encoding, &mut vars, &mut cnf).unwrap();
+                }
+                for mask in 0..1 << n {
+                    let fixed: Vec<i32> = (1..=n)
+                        .map(|v| if mask >> (v - 1) & 1 == 1 { v } else { -v })
+                        .collect();
+                    let count = lits.iter().filter(|lit| fixed.contains(lit)).count();
+                    let expected = if at_least { count >= k } else { count <= k };
+                    assert_eq!(
+                        extends(&cnf, &fixed),
+                        expected,
+                        "{encoding:?} k={k} {fixed:?}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn at_most_k_encodings() {
+        check(6, false);
+    }
+
+    #[test]
+    fn at_least_k_encodings() {
+        check(5, true);
+    }
+}
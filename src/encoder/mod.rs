@@ -0,0 +1,298 @@
+//! The `encoder` module builds CNF from higher-level constraints.
+//!
+//! Encodings emit their clauses into any [`AsDimacs`] sink, such as a solver, a
+//! [`Problem`](crate::parser::Problem) or a `Vec<Vec<i32>>`, and take their auxiliary
+//! variables from a [`VarManager`].
+//!
+//! # Usage
+//! The `encoder` module needs the `parser` feature, which provides `AsDimacs`:
+//! ```toml
+//! [dependencies]
+//! satgalaxy = { version = "x.y.z", features = ["parser"] }
+//! ```
+use std::ops::Not;
+
+use crate::errors::ParserError;
+use crate::parser::AsDimacs;
+
+pub mod cardinality;
+pub mod pb;
+pub mod xor;
+
+/// Hands out fresh variables above every variable already in use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct VarManager {
+    max_var: i32,
+}
+
+impl VarManager {
+    /// Create a manager whose first fresh variable is `max_var + 1`.
+    pub fn new(max_var: i32) -> Self {
+        Self { max_var }
+    }
+
+    /// Allocate a new variable.
+    pub fn fresh(&mut self) -> i32 {
+        self.max_var += 1;
+        self.max_var
+    }
+
+    /// Mark the variable of `lit` as used, so it is never handed out.
+    pub fn reserve(&mut self, lit: i32) {
+        self.max_var = self.max_var.max(lit.abs());
+    }
+
+    /// The largest variable used so far.
+    pub fn max_var(&self) -> i32 {
+        self.max_var
+    }
+}
+
+/// A boolean expression over DIMACS variables.
+/// # Example
+/// ```rust
+/// use satgalaxy::encoder::{assert_expr, Expr, VarManager};
+/// let expr = Expr::iff(Expr::var(1), Expr::and([Expr::var(2), !Expr::var(3)]));
+/// let mut vars = VarManager::new(expr.max_var());
+/// let mut cnf: Vec<Vec<i32>> = Vec::new();
+/// assert_expr(&expr, &mut vars, &mut cnf).unwrap();
+/// assert!(vars.max_var() > 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Expr {
+    /// A literal; negative values are negated variables.
+    Var(i32),
+    Not(Box<Expr>),
+    /// True when every operand is; the empty conjunction is true.
+    And(Vec<Expr>),
+    /// True when some operand is; the empty disjunction is false.
+    Or(Vec<Expr>),
+    Xor(Box<Expr>, Box<Expr>),
+    Implies(Box<Expr>, Box<Expr>),
+    Iff(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn var(lit: i32) -> Self {
+        Expr::Var(lit)
+    }
+    pub fn and(operands: impl IntoIterator<Item = Expr>) -> Self {
+        Expr::And(operands.into_iter().collect())
+    }
+    pub fn or(operands: impl IntoIterator<Item = Expr>) -> Self {
+        Expr::Or(operands.into_iter().collect())
+    }
+    pub fn xor(a: Expr, b: Expr) -> Self {
+        Expr::Xor(Box::new(a), Box::new(b))
+    }
+    pub fn implies(a: Expr, b: Expr) -> Self {
+        Expr::Implies(Box::new(a), Box::new(b))
+    }
+    pub fn iff(a: Expr, b: Expr) -> Self {
+        Expr::Iff(Box::new(a), Box::new(b))
+    }
+
+    /// The largest variable in the expression, or 0 if it has none.
+    pub fn max_var(&self) -> i32 {
+        match self {
+            Expr::Var(lit) => lit.abs(),
+            Expr::Not(e) => e.max_var(),
+            Expr::And(es) | Expr::Or(es) => es.iter().map(Expr::max_var).max().unwrap_or(0),
+            Expr::Xor(a, b) | Expr::Implies(a, b) | Expr::Iff(a, b) => a.max_var().max(b.max_var()),
+        }
+    }
+
+    /// Evaluate the expression, where `value(var)` is the value of variable `var`.
+    pub fn eval(&self, value: &impl Fn(i32) -> bool) -> bool {
+        match self {
+            Expr::Var(lit) => value(lit.abs()) == (*lit > 0),
+            Expr::Not(e) => !e.eval(value),
+            Expr::And(es) => es.iter().all(|e| e.eval(value)),
+            Expr::Or(es) => es.iter().any(|e| e.eval(value)),
+            Expr::Xor(a, b) => a.eval(value) != b.eval(value),
+            Expr::Implies(a, b) => !a.eval(value) || b.eval(value),
+            Expr::Iff(a, b) => a.eval(value) == b.eval(value),
+        }
+    }
+}
+
+impl Not for Expr {
+    type Output = Expr;
+    fn not(self) -> Expr {
+        match self {
+            Expr::Var(lit) => Expr::Var(-lit),
+            Expr::Not(e) => *e,
+            e => Expr::Not(Box::new(e)),
+        }
+    }
+}
+
+/// Tseitin-encode `expr` into `dim` and return the literal equivalent to it.
+///
+/// Every compound subexpression gets a fresh variable from `vars` that is constrained
+/// to equal it, so the clauses are satisfiable for every assignment of the original
+/// variables. `vars` must not hand out any variable of `expr`; create it with
+/// `VarManager::new(expr.max_var())` or `reserve` them.
+pub fn tseitin<D: AsDimacs>(
+    expr: &Expr,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<i32, ParserError> {
+    match expr {
+        Expr::Var(lit) => {
+            if *lit == 0 || *lit == i32::MIN {
+                return Err(ParserError::InvalidLiteral(*lit));
+            }
+            Ok(*lit)
+        }
+        Expr::Not(e) => Ok(-tseitin(e, vars, dim)?),
+        Expr::And(es) => {
+            let lits = es
+                .iter()
+                .map(|e| tseitin(e, vars, dim))
+                .collect::<Result<Vec<_>, _>>()?;
+            encode_and(&lits, vars, dim)
+        }
+        Expr::Or(es) => {
+            // a | b == !(!a & !b)
+            let lits = es
+                .iter()
+                .map(|e| tseitin(e, vars, dim).map(|lit| -lit))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(-encode_and(&lits, vars, dim)?)
+        }
+        Expr::Xor(a, b) => {
+            let (a, b) = (tseitin(a, vars, dim)?, tseitin(b, vars, dim)?);
+            encode_xor(a, b, vars, dim)
+        }
+        Expr::Implies(a, b) => {
+            let (a, b) = (tseitin(a, vars, dim)?, tseitin(b, vars, dim)?);
+            Ok(-encode_and(&[a, -b], vars, dim)?)
+        }
+        Expr::Iff(a, b) => {
+            let (a, b) = (tseitin(a, vars, dim)?, tseitin(b, vars, dim)?);
+            Ok(-encode_xor(a, b, vars, dim)?)
+        }
+    }
+}
+
+/// Encode `expr` into `dim` and require it to hold.
+pub fn assert_expr<D: AsDimacs>(
+    expr: &Expr,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let root = tseitin(expr, vars, dim)?;
+    dim.push_clause(vec![root])
+}
+
+fn encode_and<D: AsDimacs>(
+    lits: &[i32],
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<i32, ParserError> {
+    if let [lit] = lits {
+        return Ok(*lit);
+    }
+    let x = vars.fresh();
+    for &lit in lits {
+        dim.push_clause(vec![-x, lit])?;
+    }
+    let mut clause = vec![x];
+    clause.extend(lits.iter().map(|lit| -lit));
+    dim.push_clause(clause)?;
+    Ok(x)
+}
+
+fn encode_xor<D: AsDimacs>(
+    a: i32,
+    b: i32,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<i32, ParserError> {
+    let x = vars.fresh();
+    dim.push_clause(vec![-x, a, b])?;
+    dim.push_clause(vec![-x, -a, -b])?;
+    dim.push_clause(vec![x, -a, b])?;
+    dim.push_clause(vec![x, a, -b])?;
+    Ok(x)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Whether `clauses` have a model extending the literals in `fixed` (a small DPLL).
+    pub(crate) fn extends(clauses: &[Vec<i32>], fixed: &[i32]) -> bool {
+        let mut assignment = fixed.to_vec();
+        loop {
+            let mut unit = None;
+            let mut branch = None;
+            for clause in clauses {
+                if clause.iter().any(|lit| assignment.contains(lit)) {
+                    continue;
+                }
+                let open: Vec<i32> = clause
+                    .iter()
+                    .copied()
+                    .filter(|lit| !assignment.contains(&-lit))
+                    .collect();
+                match open[..] {
+                    [] => return false,
+                    [lit] => unit = Some(lit),
+                    [lit, ..] => branch = branch.or(Some(lit)),
+                }
+                if unit.is_some() {
+                    break;
+                }
+            }
+            match (unit, branch) {
+                (Some(lit), _) => assignment.push(lit),
+                (None, Some(lit)) => {
+                    assignment.push(lit);
+                    if extends(clauses, &assignment) {
+                        return true;
+                    }
+                    assignment.pop();
+                    assignment.push(-lit);
+                }
+                (None, None) => return true,
+            }
+        }
+    }
+
+    #[test]
+    fn tseitin_is_equisatisfiable() {
+        let (a, b, c) = (Expr::var(1), Expr::var(2), Expr::var(3));
+        let exprs = [
+            Expr::and([a.clone(), !b.clone(), c.clone()]),
+            Expr::or([Expr::xor(a.clone(), b.clone()), !c.clone()]),
+            Expr::implies(
+                Expr::iff(a.clone(), c.clone()),
+                Expr::and([b.clone(), a.clone()]),
+            ),
+            !Expr::or([Expr::and([]), a.clone()]),
+            Expr::or([]),
+            Expr::iff(Expr::var(-1), Expr::xor(b, Expr::implies(c, a))),
+        ];
+        for expr in exprs {
+            let mut vars = VarManager::new(3);
+            let mut cnf: Vec<Vec<i32>> = Vec::new();
+            assert_expr(&expr, &mut vars, &mut cnf).unwrap();
+            for mask in 0..8 {
+                let fixed: Vec<i32> = (1..=3)
+                    .map(|v| if mask >> (v - 1) & 1 == 1 { v } else { -v })
+                    .collect();
+                let expected = expr.eval(&|var| fixed.contains(&var));
+                assert_eq!(extends(&cnf, &fixed), expected, "{expr:?} {fixed:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_zero() {
+        let mut cnf: Vec<Vec<i32>> = Vec::new();
+        let expr = Expr::and([Expr::var(1), Expr::var(0)]);
+        assert!(tseitin(&expr, &mut VarManager::new(1), &mut cnf).is_err());
+    }
+}
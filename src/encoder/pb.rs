@@ -0,0 +1,289 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::errors::ParserError;
+use crate::parser::AsDimacs;
+
+use super::VarManager;
+
+/// How a pseudo-Boolean constraint is turned into clauses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PbEncoding {
+    /// A reduced decision diagram over the terms (Eén and Sörensson). Compact when the
+    /// weights share structure, but may grow with the number of distinct partial sums.
+    #[default]
+    Bdd,
+    /// A binary adder network (Warners) whose output is compared with the bound;
+    /// the size is polynomial in the number of terms and the bits of the weights.
+    Adder,
+}
+
+/// Require `Σ weight * lit ≤ bound` over `terms` of `(weight, lit)`, where a true literal
+/// counts as 1 and a false one as 0.
+/// # Example
+/// ```rust
+/// use satgalaxy::encoder::{pb::{pb_at_most, PbEncoding}, VarManager};
+/// let mut vars = VarManager::new(3);
+/// let mut cnf: Vec<Vec<i32>> = Vec::new();
+/// pb_at_most(&[(3, 1), (2, 2), (2, -3)], 4, PbEncoding::Adder, &mut vars, &mut cnf).unwrap();
+/// ```
+pub fn pb_at_most<D: AsDimacs>(
+    terms: &[(i64, i32)],
+    bound: i64,
+    encoding: PbEncoding,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    // Make every weight positive: w * l == w + |w| * !l for w < 0.
+    let mut bound = bound;
+    let mut normalized = Vec::with_capacity(terms.len());
+    for &(weight, lit) in terms {
+        if lit == 0 || lit == i32::MIN {
+            return Err(ParserError::InvalidLiteral(lit));
+        }
+        match weight {
+            0 => {}
+            w if w < 0 => {
+                bound -= w;
+                normalized.push((-w, -lit));
+            }
+            w => normalized.push((w, lit)),
+        }
+    }
+    if bound < 0 {
+        return dim.push_clause(vec![]);
+    }
+    // A term that alone exceeds the bound must be false.
+    let mut remaining = Vec::with_capacity(normalized.len());
+    for (weight, lit) in normalized {
+        if weight > bound {
+            dim.push_clause(vec![-lit])?;
+        } else {
+            remaining.push((weight, lit));
+        }
+    }
+    if remaining.iter().map(|(w, _)| w).sum::<i64>() <= bound {
+        return Ok(());
+    }
+    match encoding {
+        PbEncoding::Bdd => {
+            remaining.sort_by_key(|&(weight, _)| Reverse(weight));
+            let mut bdd = Bdd {
+                terms: &remaining,
+                suffix: suffix_sums(&remaining),
+                bound,
+                memo: HashMap::new(),
+            };
+            match bdd.node(0, 0, vars, dim)? {
+                Node::True => Ok(()),
+                Node::False => dim.push_clause(vec![]),
+                Node::Lit(root) => dim.push_clause(vec![root]),
+            }
+        }
+        PbEncoding::Adder => adder(&remaining, bound, vars, dim),
+    }
+}
+
+/// Require `Σ weight * lit ≥ bound`, encoded as `Σ -weight * lit ≤ -bound`.
+pub fn pb_at_least<D: AsDimacs>(
+    terms: &[(i64, i32)],
+    bound: i64,
+    encoding: PbEncoding,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let negated: Vec<(i64, i32)> = terms.iter().map(|&(w, lit)| (-w, lit)).collect();
+    pb_at_most(&negated, -bound, encoding, vars, dim)
+}
+
+fn suffix_sums(terms: &[(i64, i32)]) -> Vec<i64> {
+    let mut suffix = vec![0; terms.len() + 1];
+    for i in (0..terms.len()).rev() {
+        suffix[i] = suffix[i + 1] + terms[i].0;
+    }
+    suffix
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Node {
+    True,
+    False,
+    Lit(i32),
+}
+
+struct Bdd<'a> {
+    terms: &'a [(i64, i32)],
+    /// `suffix[i]` is the total weight of `terms[i..]`.
+    suffix: Vec<i64>,
+    bound: i64,
+    memo: HashMap<(usize, i64), Node>,
+}
+
+impl Bdd<'_> {
+    /// The node stating that `terms[i..]` fit in what `sum` leaves of the bound.
+    fn node<D: AsDimacs>(
+        &mut self,
+        i: usize,
+        sum: i64,
+        vars: &mut VarManager,
+        dim: &mut D,
+    ) -> Result<Node, ParserError> {
+        if sum > self.bound {
+            return Ok(Node::False);
+        }
+        if sum + self.suffix[i] <= self.bound {
+            return Ok(Node::True);
+        }
+        if let Some(&node) = self.memo.get(&(i, sum)) {
+            return Ok(node);
+        }
+        let (weight, lit) = self.terms[i];
+        let high = self.node(i + 1, sum + weight, vars, dim)?;
+        let low = self.node(i + 1, sum, vars, dim)?;
+        let x = vars.fresh();
+        match high {
+            Node::True => {}
+            Node::False => dim.push_clause(vec![-x, -lit])?,
+            Node::Lit(h) => dim.push_clause(vec![-x, -lit, h])?,
+        }
+        match low {
+            Node::True => {}
+            Node::False => dim.push_clause(vec![-x])?,
+            Node::Lit(l) => dim.push_clause(vec![-x, l])?,
+        }
+        self.memo.insert((i, sum), Node::Lit(x));
+        Ok(Node::Lit(x))
+    }
+}
+
+/// Sum the terms with a network of full and half adders and forbid outputs above `bound`.
+fn adder<D: AsDimacs>(
+    terms: &[(i64, i32)],
+    bound: i64,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let mut buckets: Vec<Vec<i32>> = Vec::new();
+    for &(weight, lit) in terms {
+        for bit in 0..64 - weight.leading_zeros() as usize {
+            if weight >> bit & 1 == 1 {
+                if buckets.len() <= bit {
+                    buckets.resize(bit + 1, vec![]);
+                }
+                buckets[bit].push(lit);
+            }
+        }
+    }
+    // outputs[bit] is the literal of that bit of the sum, `None` if it is always 0.
+    let mut outputs: Vec<Option<i32>> = Vec::new();
+    let mut bit = 0;
+    while bit < buckets.len() {
+        while buckets[bit].len() >= 2 {
+            let a = buckets[bit].pop().unwrap();
+            let b = buckets[bit].pop().unwrap();
+            let c = buckets[bit].pop();
+            let (sum, carry) = full_adder(a, b, c, vars, dim)?;
+            buckets[bit].push(sum);
+            if buckets.len() <= bit + 1 {
+                buckets.push(vec![]);
+            }
+            buckets[bit + 1].push(carry);
+        }
+        outputs.push(buckets[bit].pop());
+        bit += 1;
+    }
+    // The sum exceeds the bound iff at the highest differing bit it has 1 and the bound 0.
+    let bound_bit = |bit: usize| bit < 63 && bound >> bit & 1 == 1;
+    'bits: for (i, output) in outputs.iter().enumerate() {
+        let Some(o) = *output else { continue };
+        if bound_bit(i) {
+            continue;
+        }
+        let mut clause = vec![-o];
+        for (j, higher) in outputs.iter().enumerate().skip(i + 1) {
+            match (*higher, bound_bit(j)) {
+                (Some(h), false) => clause.push(h),
+                (Some(h), true) => clause.push(-h),
+                (None, false) => {}
+                // The sum has 0 where the bound has 1, so it is below the bound.
+                (None, true) => continue 'bits,
+            }
+        }
+        dim.push_clause(clause)?;
+    }
+    Ok(())
+}
+
+/// Encode `a + b (+ c)` and return the literals of the sum and carry bits.
+fn full_adder<D: AsDimacs>(
+    a: i32,
+    b: i32,
+    c: Option<i32>,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(i32, i32), ParserError> {
+    let (sum, carry) = (vars.fresh(), vars.fresh());
+    let inputs: Vec<i32> = [Some(a), Some(b), c].into_iter().flatten().collect();
+    // Clauses from the truth table over every assignment of the inputs.
+    for mask in 0..1u32 << inputs.len() {
+        let ones = mask.count_ones();
+        let blocked: Vec<i32> = inputs
+            .iter()
+            .enumerate()
+            .map(|(i, &lit)| if mask >> i & 1 == 1 { -lit } else { lit })
+            .collect();
+        let mut clause = blocked.clone();
+        clause.push(if ones % 2 == 1 { sum } else { -sum });
+        dim.push_clause(clause)?;
+        let mut clause = blocked;
+        clause.push(if ones >= 2 { carry } else { -carry });
+        dim.push_clause(clause)?;
+    }
+    Ok((sum, carry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::extends;
+    use super::*;
+
+    #[test]
+    fn encodings_match_brute_force() {
+        let constraints: [(&[(i64, i32)], i64); 5] = [
+            (&[(3, 1), (2, 2), (2, 3), (1, 4), (5, 5)], 6),
+            (&[(1, 1), (1, 2), (1, 3), (1, 4)], 2),
+            (&[(4, 1), (-3, 2), (2, -3), (7, 4), (-1, -5)], 3),
+            (&[(6, 1), (6, 2), (6, 3)], 11),
+            (&[(2, 1), (3, -2)], -1),
+        ];
+        for (terms, bound) in constraints {
+            for encoding in [PbEncoding::Bdd, PbEncoding::Adder] {
+                for at_least in [false, true] {
+                    let mut vars = VarManager::new(5);
+                    let mut cnf: Vec<Vec<i32>> = Vec::new();
+                    if at_least {
+                        pb_at_least(terms, bound, encoding, &mut vars, &mut cnf).unwrap();
+                    } else {
+                        pb_at_most(terms, bound, encoding, &mut vars, &mut cnf).unwrap();
+                    }
+                    for mask in 0..32 {
+                        let fixed: Vec<i32> = (1..=5)
+                            .map(|v| if mask >> (v - 1) & 1 == 1 { v } else { -v })
+                            .collect();
+                        let sum: i64 = terms
+                            .iter()
+                            .filter(|(_, lit)| fixed.contains(lit))
+                            .map(|(w, _)| w)
+                            .sum();
+                        let expected = if at_least { sum >= bound } else { sum <= bound };
+                        assert_eq!(
+                            extends(&cnf, &fixed),
+                            expected,
+                            "{encoding:?} {terms:?} {bound} {fixed:?}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use crate::errors::ParserError;
+use crate::parser::AsDimacs;
+
+use super::VarManager;
+
+/// XORs longer than this are cut into chunks linked by auxiliary variables.
+const CUT: usize = 4;
+
+/// The constraint `v1 ⊕ v2 ⊕ ... ⊕ vn = rhs` over distinct variables.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XorClause {
+    /// The variables, in ascending order.
+    pub vars: Vec<i32>,
+    pub rhs: bool,
+}
+
+impl XorClause {
+    /// The XOR clause `l1 ⊕ l2 ⊕ ... ⊕ ln = true` of CryptoMiniSat's `x` lines.
+    ///
+    /// Each negated literal flips the right-hand side, and a variable occurring twice
+    /// cancels out.
+    pub fn from_lits(lits: &[i32]) -> Result<Self, ParserError> {
+        let mut rhs = true;
+        let mut vars = Vec::with_capacity(lits.len());
+        for &lit in lits {
+            if lit == 0 || lit == i32::MIN {
+                return Err(ParserError::InvalidLiteral(lit));
+            }
+            rhs ^= lit < 0;
+            vars.push(lit.abs());
+        }
+        vars.sort_unstable();
+        let mut reduced: Vec<i32> = Vec::with_capacity(vars.len());
+        for var in vars {
+            if reduced.last() == Some(&var) {
+                reduced.pop();
+            } else {
+                reduced.push(var);
+            }
+        }
+        Ok(Self { vars: reduced, rhs })
+    }
+
+    /// Evaluate the constraint, where `value(var)` is the value of variable `var`.
+    pub fn eval(&self, value: &impl Fn(i32) -> bool) -> bool {
+        (self.vars.iter().filter(|&&var| value(var)).count() % 2 == 1) == self.rhs
+    }
+}
+
+/// Bring `xors` to reduced row echelon form by Gauss-Jordan elimination over GF(2).
+///
+/// The result has the same solutions, no more constraints than `xors`, and every
+/// constraint has a pivot variable that occurs in no other one; units and equivalences
+/// implied by the system show up as constraints over one or two variables. Returns
+/// `None` if the system is inconsistent.
+/// # Example
+/// ```rust
+/// use satgalaxy::encoder::xor::{gauss_eliminate, XorClause};
+/// let xors = [
+///     XorClause::from_lits(&[1, 2, 3]).unwrap(),
+///     XorClause::from_lits(&[1, 2]).unwrap(),
+/// ];
+/// let reduced = gauss_eliminate(&xors).unwrap();
+/// assert!(reduced.contains(&XorClause { vars: vec![3], rhs: false }));
+/// ```
+pub fn gauss_eliminate(xors: &[XorClause]) -> Option<Vec<XorClause>> {
+    let mut columns: Vec<i32> = xors
+        .iter()
+        .flat_map(|xor| xor.vars.iter().copied())
+        .collect();
+    columns.sort_unstable();
+    columns.dedup();
+    let words = columns.len() / 64 + 1;
+    let bit = |bits: &[u64], col: usize| bits[col / 64] >> (col % 64) & 1 == 1;
+    let mut rows: Vec<(Vec<u64>, bool)> = xors
+        .iter()
+        .map(|xor| {
+            let mut bits = vec![0u64; words];
+            for var in &xor.vars {
+                let col = columns
+                    .binary_search(var)
+                    .expect("every variable has a column");
+                bits[col / 64] ^= 1 << (col % 64);
+            }
+            (bits, xor.rhs)
+        })
+        .collect();
+    let mut rank = 0;
+    for col in 0..columns.len() {
+        let Some(pivot) = (rank..rows.len()).find(|&row| bit(&rows[row].0, col)) else {
+            continue;
+        };
+        rows.swap(rank, pivot);
+        let (pivot_bits, pivot_rhs) = rows[rank].clone();
+        for (i, (bits, rhs)) in rows.iter_mut().enumerate() {
+            if i != rank && bit(bits, col) {
+                for (word, pivot_word) in bits.iter_mut().zip(&pivot_bits) {
+                    *word ^= pivot_word;
+                }
+                *rhs ^= pivot_rhs;
+            }
+        }
+        rank += 1;
+    }
+    // The rows below the rank are `0 = rhs`.
+    if rows[rank..].iter().any(|&(_, rhs)| rhs) {
+        return None;
+    }
+    let reduced = rows[..rank]
+        .iter()
+        .map(|(bits, rhs)| XorClause {
+            vars: (0..columns.len())
+                .filter(|&col| bit(bits, col))
+                .map(|col| columns[col])
+                .collect(),
+            rhs: *rhs,
+        })
+        .collect();
+    Some(reduced)
+}
+
+/// Encode one XOR clause to CNF.
+///
+/// Up to [`CUT`] variables are encoded directly, with one clause per forbidden
+/// assignment; longer XORs are split into chunks whose parities are chained through
+/// auxiliary variables, so the size is linear in the number of variables.
+pub fn encode_xor_clause<D: AsDimacs>(
+    xor: &XorClause,
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let mut rest: &[i32] = &xor.vars;
+    let mut carry = None;
+    loop {
+        let mut chunk: Vec<i32> = carry.into_iter().collect();
+        if chunk.len() + rest.len() <= CUT {
+            chunk.extend_from_slice(rest);
+            return encode_direct(&chunk, xor.rhs, dim);
+        }
+        let take = CUT - 1 - chunk.len();
+        chunk.extend_from_slice(&rest[..take]);
+        rest = &rest[take..];
+        // `parity` is the XOR of the chunk.
+        let parity = vars.fresh();
+        chunk.push(parity);
+        encode_direct(&chunk, false, dim)?;
+        carry = Some(parity);
+    }
+}
+
+/// Forbid every assignment of `xor_vars` whose parity differs from `rhs`.
+fn encode_direct<D: AsDimacs>(xor_vars: &[i32], rhs: bool, dim: &mut D) -> Result<(), ParserError> {
+    // The clause with negations `signs` excludes the assignment setting exactly the
+    // negated variables to true, whose parity is that of the number of negations.
+    for signs in 0u32..1 << xor_vars.len() {
+        if (signs.count_ones() % 2 == 1) == rhs {
+            continue;
+        }
+        let clause = xor_vars
+            .iter()
+            .enumerate()
+            .map(|(i, &var)| if signs >> i & 1 == 1 { -var } else { var })
+            .collect();
+        dim.push_clause(clause)?;
+    }
+    Ok(())
+}
+
+/// Simplify `xors` with [`gauss_eliminate`] and encode the result to CNF.
+///
+/// An inconsistent system is encoded as the empty clause.
+pub fn encode_xors<D: AsDimacs>(
+    xors: &[XorClause],
+    vars: &mut VarManager,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let Some(reduced) = gauss_eliminate(xors) else {
+        return dim.push_clause(vec![]);
+    };
+    for xor in &reduced {
+        encode_xor_clause(xor, vars, dim)?;
+    }
+    Ok(())
+}
+
+/// An `AsDimacs` sink that forwards clauses to `inner` and collects XOR clauses, so
+/// CryptoMiniSat-style inputs with `x` lines can be parsed into any sink.
+///
+/// The XORs reach `inner` when [`finish`](XorCollector::finish) encodes them, after the
+/// whole input is read.
+/// # Example
+/// ```rust
+/// use satgalaxy::encoder::xor::XorCollector;
+/// use satgalaxy::parser::{parse_dimacs_cnf, ParseMode, Problem};
+/// let mut collector = XorCollector::new(Problem::new());
+/// parse_dimacs_cnf("p cnf 3 2\n1 2 0\nx1 -2 3 0\n", ParseMode::Inferred, &mut collector).unwrap();
+/// assert_eq!(collector.xors().len(), 1);
+/// let (problem, _vars) = collector.finish().unwrap();
+/// assert_eq!(problem.clauses[0], vec![1, 2]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct XorCollector<D> {
+    inner: D,
+    xors: Vec<XorClause>,
+    vars: VarManager,
+}
+
+impl<D: AsDimacs> XorCollector<D> {
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner,
+            xors: vec![],
+            vars: VarManager::default(),
+        }
+    }
+
+    /// The XOR clauses collected so far.
+    pub fn xors(&self) -> &[XorClause] {
+        &self.xors
+    }
+
+    /// Encode the collected XORs into the inner sink with [`encode_xors`] and return it.
+    ///
+    /// Auxiliary variables are numbered after every variable of the input; the returned
+    /// manager hands out the next free ones.
+    pub fn finish(mut self) -> Result<(D, VarManager), ParserError> {
+        encode_xors(&self.xors, &mut self.vars, &mut self.inner)?;
+        Ok((self.inner, self.vars))
+    }
+}
+
+impl<D: AsDimacs> AsDimacs for XorCollector<D> {
+    fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
+        for &lit in &clause {
+            self.vars.reserve(lit);
+        }
+        self.inner.push_clause(clause)
+    }
+    fn push_xor(&mut self, lits: Vec<i32>) -> Result<(), ParserError> {
+        let xor = XorClause::from_lits(&lits)?;
+        for &lit in &lits {
+            self.vars.reserve(lit);
+        }
+        self.xors.push(xor);
+        Ok(())
+    }
+    fn add_comment(&mut self, comment: String) {
+        self.inner.add_comment(comment);
+    }
+    fn set_header(&mut self, num_vars: usize, num_clauses: usize) {
+        self.vars.reserve(num_vars as i32);
+        self.inner.set_header(num_vars, num_clauses);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::tests::extends;
+
+    #[test]
+    fn eliminate_and_encode() {
+        let xors: Vec<XorClause> = [vec![1, 2, 3, 4, 5, 6], vec![-1, 2], vec![2, 3, 4, 5, 6]]
+            .iter()
+            .map(|lits| XorClause::from_lits(lits).unwrap())
+            .collect();
+        // The first and last rows sum to x1 = false, so x2 = false by the second.
+        let reduced = gauss_eliminate(&xors).unwrap();
+        assert!(reduced.contains(&XorClause {
+            vars: vec![1],
+            rhs: false
+        }));
+        assert!(reduced.contains(&XorClause {
+            vars: vec![2],
+            rhs: false
+        }));
+
+        let mut vars = VarManager::new(6);
+        let mut system: Vec<Vec<i32>> = vec![];
+        encode_xors(&xors, &mut vars, &mut system).unwrap();
+        // The first XOR alone is long enough to be cut.
+        let mut chained: Vec<Vec<i32>> = vec![];
+        encode_xor_clause(&xors[0], &mut vars, &mut chained).unwrap();
+        for assignment in 0u32..1 << 6 {
+            let value = |var: i32| assignment >> (var - 1) & 1 == 1;
+            let fixed: Vec<i32> = (1..=6)
+                .map(|var| if value(var) { var } else { -var })
+                .collect();
+            let expected = xors.iter().all(|xor| xor.eval(&value));
+            assert_eq!(extends(&system, &fixed), expected, "{fixed:?}");
+            assert_eq!(extends(&chained, &fixed), xors[0].eval(&value), "{fixed:?}");
+        }
+
+        let inconsistent = [
+            XorClause::from_lits(&[1, 2]).unwrap(),
+            XorClause::from_lits(&[1, -2]).unwrap(),
+        ];
+        assert_eq!(gauss_eliminate(&inconsistent), None);
+        assert_eq!(
+            XorClause::from_lits(&[3, -1, 3]).unwrap(),
+            XorClause {
+                vars: vec![1],
+                rhs: false
+            }
+        );
+    }
+
+    #[test]
+    fn parse_x_lines() {
+        use crate::parser::{parse_dimacs_cnf, read_dimacs_streaming, ParseMode};
+        let input = "p cnf 3 3\n1 2 0\nx1 -2 3 0\nx 2 3 0\n";
+        let mut parsed = XorCollector::new(Vec::<Vec<i32>>::new());
+        parse_dimacs_cnf(input, ParseMode::Inferred, &mut parsed).unwrap();
+        let mut streamed = XorCollector::new(Vec::<Vec<i32>>::new());
+        read_dimacs_streaming(input.as_bytes(), ParseMode::Strict, &mut streamed).unwrap();
+        assert_eq!(parsed.xors(), streamed.xors());
+        assert_eq!(
+            parsed.xors(),
+            [
+                XorClause {
+                    vars: vec![1, 2, 3],
+                    rhs: false
+                },
+                XorClause {
+                    vars: vec![2, 3],
+                    rhs: true
+                },
+            ]
+        );
+        // Plain sinks reject XOR clauses.
+        assert!(parse_dimacs_cnf(input, ParseMode::Inferred, &mut Vec::<Vec<i32>>::new()).is_err());
+    }
+}
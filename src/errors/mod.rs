@@ -1,21 +1,59 @@
 #[cfg(feature = "parser")]
 mod parser;
-use std::error::Error;
+use thiserror::Error;
 
 #[cfg(feature = "parser")]
-pub use parser::ParserError;
+pub use parser::{ParserError, Position};
 
-#[derive(Debug)]
-pub struct  SolverError(pub &'static str);
-
-impl std::fmt::Display for SolverError   {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SolverError {
+    /// An error reported by the C solver, with its error code.
+    #[error("{message} (error code {code})")]
+    Ffi { code: i32, message: String },
+    /// A literal that is 0, `i32::MIN` or otherwise rejected by the solver.
+    #[error("Invalid literal: {0}")]
+    InvalidLiteral(i32),
+    /// An argument other than a literal was rejected.
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(&'static str),
+    /// The call is not valid in the solver's current state, e.g. asking for a model
+    /// after an UNSAT result.
+    #[error("{0}")]
+    InvalidState(&'static str),
+    /// The backend does not implement the operation.
+    #[error("Solver does not support {0}")]
+    UnsupportedOperation(&'static str),
+    /// The solver stopped without an answer, because of a limit, a timeout or a
+    /// termination callback.
+    #[error("Solver was terminated before reaching an answer")]
+    Terminated,
+    /// Reading or writing a file failed.
+    #[error("{0}")]
+    Io(String),
+    /// Loading a solver from a shared library failed.
+    #[error("{0}")]
+    Library(String),
 }
 
-impl Error for SolverError {
-    fn description(&self) -> &str {
-        self.0
+impl SolverError {
+    /// Build an `Ffi` error from a code and the message the C library returned for it.
+    ///
+    /// # Safety
+    /// `message` must be null or point to a NUL-terminated string.
+    #[cfg(any(
+        feature = "cadical",
+        feature = "glucose",
+        feature = "minisat",
+        feature = "picosat"
+    ))]
+    pub(crate) unsafe fn ffi(code: i32, message: *const std::ffi::c_char) -> Self {
+        let message = if message.is_null() {
+            String::from("unknown error")
+        } else {
+            std::ffi::CStr::from_ptr(message)
+                .to_string_lossy()
+                .into_owned()
+        };
+        SolverError::Ffi { code, message }
     }
 }
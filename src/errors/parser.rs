@@ -1,6 +1,10 @@
+use pest::error::{InputLocation, LineColLocation};
 use thiserror::Error;
 
-use crate::{errors::SolverError, parser::Rule};
+use crate::{
+    errors::SolverError,
+    parser::{OpbRule, Rule},
+};
 
 #[derive(Error, Debug)]
 pub enum ParserError {
@@ -8,13 +12,139 @@ pub enum ParserError {
     FileReadError(#[from] std::io::Error),
 
     #[error("Failed to parse CNF: {0}")]
-    CnfParseError(#[from] pest::error::Error<Rule>),
+    CnfParseError(Box<pest::error::Error<Rule>>),
+    #[error("Failed to parse OPB: {0}")]
+    OpbParseError(Box<pest::error::Error<OpbRule>>),
     #[error("Number of variables ({0}) exceeds expected maximum ({1})")]
     TooManyVariables(i32, i32),
     #[error("Number of clauses ({0}) exceeds expected maximum ({1})")]
     TooManyClauses(i32, i32),
+    #[error("Missing `p cnf` header in strict mode")]
+    MissingHeader,
+    #[error("Invalid literal: {0}")]
+    InvalidLiteral(i32),
+    #[error("Invalid DIMACS at line {0}: {1}")]
+    InvalidLine(usize, &'static str),
     #[error("Failed to parse int: {0}")]
     ParseIntError(#[from] std::num::ParseIntError),
     #[error("{0}")]
-    SolverError(#[from] SolverError)
+    SolverError(#[from] SolverError),
+    /// `source` occurred at `position` of the input.
+    #[error("{source} (line {}, column {})", position.line, position.column)]
+    Located {
+        position: Position,
+        source: Box<ParserError>,
+    },
+}
+
+// The pest errors are boxed to keep `ParserError` small, so they get their own `From`.
+impl From<pest::error::Error<Rule>> for ParserError {
+    fn from(error: pest::error::Error<Rule>) -> Self {
+        ParserError::CnfParseError(Box::new(error))
+    }
+}
+
+impl From<pest::error::Error<OpbRule>> for ParserError {
+    fn from(error: pest::error::Error<OpbRule>) -> Self {
+        ParserError::OpbParseError(Box::new(error))
+    }
+}
+
+/// Where in the input a [`ParserError`] occurred.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Position {
+    /// The byte offset from the start of the (decompressed) input.
+    pub offset: usize,
+    /// The line, starting at 1.
+    pub line: usize,
+    /// The column in characters, starting at 1.
+    pub column: usize,
+    /// The index of the clause being read, if the error belongs to one.
+    pub clause: Option<usize>,
+}
+
+impl ParserError {
+    /// Attach `position`, unless the error already has one.
+    pub(crate) fn at(self, position: Position) -> Self {
+        match self {
+            ParserError::Located { .. } => self,
+            source => ParserError::Located {
+                position,
+                source: Box::new(source),
+            },
+        }
+    }
+
+    /// The error without its position.
+    pub fn into_inner(self) -> ParserError {
+        match self {
+            ParserError::Located { source, .. } => *source,
+            error => error,
+        }
+    }
+
+    /// Where the error occurred, if known.
+    ///
+    /// Grammar errors report the position pest found; their clause is unknown.
+    pub fn position(&self) -> Option<Position> {
+        let (offset, (line, column)) = match self {
+            ParserError::Located { position, .. } => return Some(*position),
+            ParserError::CnfParseError(error) => pest_position(&error.location, &error.line_col),
+            ParserError::OpbParseError(error) => pest_position(&error.location, &error.line_col),
+            _ => return None,
+        };
+        Some(Position {
+            offset,
+            line,
+            column,
+            clause: None,
+        })
+    }
+
+    /// Show the lines of `input` around the error, with a caret under its column.
+    ///
+    /// `input` must be the text that was parsed. Returns `None` if the error has no
+    /// position.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::parser::{read_dimacs_streaming, ParseMode};
+    /// let input = "p cnf 2 2\n1 -2 0\n2 x 0\n";
+    /// let error = read_dimacs_streaming(input.as_bytes(), ParseMode::Strict, &mut Vec::new())
+    ///     .unwrap_err();
+    /// assert_eq!(
+    ///     error.render_snippet(input).unwrap(),
+    ///     "line 3, column 3, clause 1:\n  2 | 1 -2 0\n  3 | 2 x 0\n    |   ^\n"
+    /// );
+    /// ```
+    pub fn render_snippet(&self, input: &str) -> Option<String> {
+        let position = self.position()?;
+        let lines: Vec<&str> = input.lines().collect();
+        let index = position.line.checked_sub(1).filter(|&i| i < lines.len())?;
+        let first = index.saturating_sub(1);
+        let last = (index + 1).min(lines.len() - 1);
+        let width = (last + 1).to_string().len();
+        let mut snippet = format!("line {}, column {}", position.line, position.column);
+        if let Some(clause) = position.clause {
+            snippet.push_str(&format!(", clause {clause}"));
+        }
+        snippet.push_str(":\n");
+        for (i, line) in lines.iter().enumerate().take(last + 1).skip(first) {
+            snippet.push_str(&format!("  {:>width$} | {line}\n", i + 1));
+            if i == index {
+                let caret = " ".repeat(position.column.saturating_sub(1));
+                snippet.push_str(&format!("  {:>width$} | {caret}^\n", ""));
+            }
+        }
+        Some(snippet)
+    }
+}
+
+fn pest_position(location: &InputLocation, line_col: &LineColLocation) -> (usize, (usize, usize)) {
+    let offset = match *location {
+        InputLocation::Pos(offset) | InputLocation::Span((offset, _)) => offset,
+    };
+    let line_col = match *line_col {
+        LineColLocation::Pos(line_col) | LineColLocation::Span(line_col, _) => line_col,
+    };
+    (offset, line_col)
 }
@@ -0,0 +1,247 @@
+//! The `generator` module produces benchmark and fuzzing formulas.
+//!
+//! Every generator implements [`Generator`], which writes its clauses into any
+//! [`AsDimacs`] sink (a solver, a [`Problem`] or a `Vec<Vec<i32>>`). Random instances
+//! take a seed, so a failing fuzz case can be reproduced from its seed alone.
+//!
+//! # Usage
+//! The `generator` module needs the `parser` feature, which provides `AsDimacs`:
+//! ```toml
+//! [dependencies]
+//! satgalaxy = { version = "x.y.z", features = ["parser"] }
+//! ```
+use crate::errors::{ParserError, SolverError};
+use crate::parser::{AsDimacs, Problem};
+pub use crate::rng::Rng;
+
+/// A family of formulas that can be written into an [`AsDimacs`] sink.
+pub trait Generator {
+    /// Write the header, a describing comment and every clause into `dim`.
+    fn write_to<D: AsDimacs>(&self, dim: &mut D) -> Result<(), ParserError>;
+
+    /// Collect the formula into a [`Problem`].
+    fn to_problem(&self) -> Result<Problem, ParserError> {
+        let mut problem = Problem::with_declared_vars();
+        self.write_to(&mut problem)?;
+        Ok(problem)
+    }
+}
+
+/// Uniform random k-SAT: each clause has `k` distinct variables with random signs.
+/// # Example
+/// ```rust
+/// use satgalaxy::generator::{Generator, RandomKSat};
+/// // Hard 3-SAT instances sit around a clause/variable ratio of 4.26.
+/// let problem = RandomKSat::with_ratio(3, 50, 4.26, 7).to_problem().unwrap();
+/// assert_eq!(problem.num_vars, 50);
+/// assert_eq!(problem.num_clauses, 213);
+/// assert!(problem.clauses.iter().all(|clause| clause.len() == 3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RandomKSat {
+    pub k: usize,
+    pub num_vars: usize,
+    pub num_clauses: usize,
+    pub seed: u64,
+}
+
+impl RandomKSat {
+    pub fn new(k: usize, num_vars: usize, num_clauses: usize, seed: u64) -> Self {
+        Self {
+            k,
+            num_vars,
+            num_clauses,
+            seed,
+        }
+    }
+
+    /// An instance with `round(ratio * num_vars)` clauses.
+    pub fn with_ratio(k: usize, num_vars: usize, ratio: f64, seed: u64) -> Self {
+        Self::new(
+            k,
+            num_vars,
+            (ratio * num_vars as f64).round() as usize,
+            seed,
+        )
+    }
+}
+
+impl Generator for RandomKSat {
+    fn write_to<D: AsDimacs>(&self, dim: &mut D) -> Result<(), ParserError> {
+        if self.k > self.num_vars {
+            let err = SolverError::InvalidArgument("clause width exceeds the number of variables");
+            return Err(err.into());
+        }
+        dim.set_header(self.num_vars, self.num_clauses);
+        dim.add_comment(format!(
+            "random {}-SAT, {} variables, {} clauses, seed {}",
+            self.k, self.num_vars, self.num_clauses, self.seed
+        ));
+        let mut rng = Rng::new(self.seed);
+        for _ in 0..self.num_clauses {
+            let mut clause: Vec<i32> = Vec::with_capacity(self.k);
+            while clause.len() < self.k {
+                let var = rng.below(self.num_vars as u64) as i32 + 1;
+                if clause.iter().any(|lit| lit.abs() == var) {
+                    continue;
+                }
+                clause.push(if rng.chance(0.5) { var } else { -var });
+            }
+            dim.push_clause(clause)?;
+        }
+        Ok(())
+    }
+}
+
+/// The pigeonhole principle: `holes + 1` pigeons do not fit into `holes` holes.
+///
+/// Always unsatisfiable, and exponentially hard for resolution-based solvers. Variable
+/// `pigeon * holes + hole + 1` puts `pigeon` into `hole`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pigeonhole {
+    pub holes: usize,
+}
+
+impl Pigeonhole {
+    pub fn new(holes: usize) -> Self {
+        Self { holes }
+    }
+}
+
+impl Generator for Pigeonhole {
+    fn write_to<D: AsDimacs>(&self, dim: &mut D) -> Result<(), ParserError> {
+        let holes = self.holes;
+        let pigeons = holes + 1;
+        let var = |pigeon: usize, hole: usize| (pigeon * holes + hole + 1) as i32;
+        let num_clauses = pigeons + holes * pigeons * (pigeons - 1) / 2;
+        dim.set_header(pigeons * holes, num_clauses);
+        dim.add_comment(format!("pigeonhole, {pigeons} pigeons, {holes} holes"));
+        for pigeon in 0..pigeons {
+            dim.push_clause((0..holes).map(|hole| var(pigeon, hole)).collect())?;
+        }
+        for hole in 0..holes {
+            for a in 0..pigeons {
+                for b in a + 1..pigeons {
+                    dim.push_clause(vec![-var(a, hole), -var(b, hole)])?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Graph coloring: give each vertex one of `colors` colors, with adjacent vertices
+/// colored differently.
+///
+/// Vertices are numbered from 0, and variable `vertex * colors + color + 1` gives
+/// `vertex` the color `color`.
+/// # Example
+/// ```rust
+/// use satgalaxy::generator::{Generator, GraphColoring};
+/// let triangle = vec![(0, 1), (1, 2), (0, 2)];
+/// let problem = GraphColoring::new(3, triangle, 3).to_problem().unwrap();
+/// assert_eq!(problem.num_vars, 9);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphColoring {
+    pub vertices: usize,
+    pub edges: Vec<(usize, usize)>,
+    pub colors: usize,
+}
+
+impl GraphColoring {
+    pub fn new(vertices: usize, edges: Vec<(usize, usize)>, colors: usize) -> Self {
+        Self {
+            vertices,
+            edges,
+            colors,
+        }
+    }
+
+    /// Coloring of an Erdős–Rényi graph, where each edge exists with probability `p`.
+    pub fn random(vertices: usize, p: f64, colors: usize, seed: u64) -> Self {
+        let mut rng = Rng::new(seed);
+        let mut edges = vec![];
+        for a in 0..vertices {
+            for b in a + 1..vertices {
+                if rng.chance(p) {
+                    edges.push((a, b));
+                }
+            }
+        }
+        Self::new(vertices, edges, colors)
+    }
+}
+
+impl Generator for GraphColoring {
+    fn write_to<D: AsDimacs>(&self, dim: &mut D) -> Result<(), ParserError> {
+        if self.edges.iter().any(|&(a, b)| a.max(b) >= self.vertices) {
+            return Err(SolverError::InvalidArgument("edge endpoint is not a vertex").into());
+        }
+        let colors = self.colors;
+        let var = |vertex: usize, color: usize| (vertex * colors + color + 1) as i32;
+        let num_clauses =
+            self.vertices * (1 + colors * colors.saturating_sub(1) / 2) + self.edges.len() * colors;
+        dim.set_header(self.vertices * colors, num_clauses);
+        dim.add_comment(format!(
+            "{colors}-coloring, {} vertices, {} edges",
+            self.vertices,
+            self.edges.len()
+        ));
+        for vertex in 0..self.vertices {
+            dim.push_clause((0..colors).map(|color| var(vertex, color)).collect())?;
+            for a in 0..colors {
+                for b in a + 1..colors {
+                    dim.push_clause(vec![-var(vertex, a), -var(vertex, b)])?;
+                }
+            }
+        }
+        for &(a, b) in &self.edges {
+            for color in 0..colors {
+                dim.push_clause(vec![-var(a, color), -var(b, color)])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::tests::extends;
+
+    #[test]
+    fn random_ksat() {
+        let a = RandomKSat::new(3, 20, 50, 42).to_problem().unwrap();
+        let b = RandomKSat::new(3, 20, 50, 42).to_problem().unwrap();
+        let c = RandomKSat::new(3, 20, 50, 43).to_problem().unwrap();
+        assert_eq!(a.clauses, b.clauses);
+        assert_ne!(a.clauses, c.clauses);
+        for clause in &a.clauses {
+            let mut vars: Vec<i32> = clause.iter().map(|lit| lit.abs()).collect();
+            vars.sort();
+            vars.dedup();
+            assert_eq!(vars.len(), 3);
+            assert!(vars.iter().all(|&var| (1..=20).contains(&var)));
+        }
+        assert!(RandomKSat::new(4, 3, 1, 0).to_problem().is_err());
+    }
+
+    #[test]
+    fn structured() {
+        let php = Pigeonhole::new(3).to_problem().unwrap();
+        assert_eq!(php.num_vars, 12);
+        assert_eq!(php.num_clauses, 4 + 3 * 6);
+        assert!(!extends(&php.clauses, &[]));
+
+        let triangle = vec![(0, 1), (1, 2), (0, 2)];
+        let mut cnf: Vec<Vec<i32>> = Vec::new();
+        GraphColoring::new(3, triangle.clone(), 2)
+            .write_to(&mut cnf)
+            .unwrap();
+        assert!(!extends(&cnf, &[]));
+        let coloring = GraphColoring::new(3, triangle, 3).to_problem().unwrap();
+        assert!(extends(&coloring.clauses, &[]));
+        assert!(GraphColoring::new(2, vec![(0, 2)], 2).to_problem().is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! The standard IPASIR C interface, backed by any enabled solver.
+//!
+//! This module is enabled when the `ipasir-export` feature is activated. Build the
+//! crate as a shared library with
+//! ```sh
+//! cargo rustc --release --lib --crate-type cdylib --features ipasir-export
+//! ```
+//! and link IPASIR applications against it. `ipasir_init` creates a solver of the
+//! backend named by the `SATGALAXY_IPASIR_BACKEND` environment variable (e.g.
+//! `cadical`), or of the first enabled backend in `Backend::ALL` order;
+//! `satgalaxy_ipasir_init_backend` picks it explicitly.
+#![allow(clippy::missing_safety_doc)]
+
+use std::collections::HashSet;
+use std::ffi::{c_char, c_int, c_void, CStr};
+use std::ptr;
+
+use crate::errors::SolverError;
+use crate::solver::{Backend, RawStatus, SatSolver};
+
+const SIGNATURE: &CStr = match CStr::from_bytes_with_nul(
+    concat!("satgalaxy-", env!("CARGO_PKG_VERSION"), "\0").as_bytes(),
+) {
+    Ok(signature) => signature,
+    Err(_) => panic!("signature contains a NUL byte"),
+};
+
+/// The solver behind an IPASIR handle.
+struct IpasirState {
+    solver: Box<dyn SatSolver + Send>,
+    clause: Vec<i32>,
+    assumptions: Vec<i32>,
+    /// Variables true in the last model.
+    model: HashSet<i32>,
+    /// Failed assumptions of the last UNSAT solve.
+    failed: HashSet<i32>,
+    /// The first clause the backend rejected. The formula is incomplete from then on,
+    /// so every later solve answers UNKNOWN.
+    error: Option<SolverError>,
+}
+
+/// The `data` pointer of an IPASIR terminate callback, handed to the solver's callback.
+struct CallbackData(*mut c_void);
+// SAFETY: the pointer is only handed back to the caller's callback, never read here.
+// IPASIR callers may call the solver from any thread, so the callback must accept its
+// `data` from whichever thread solves, as with any other IPASIR solver.
+unsafe impl Send for CallbackData {}
+
+fn init(backend: Backend) -> *mut c_void {
+    match backend.build() {
+        Ok(solver) => Box::into_raw(Box::new(IpasirState {
+            solver,
+            clause: vec![],
+            assumptions: vec![],
+            model: HashSet::new(),
+            failed: HashSet::new(),
+            error: None,
+        })) as *mut c_void,
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+unsafe fn state<'a>(solver: *mut c_void) -> &'a mut IpasirState {
+    &mut *(solver as *mut IpasirState)
+}
+
+#[no_mangle]
+pub extern "C" fn ipasir_signature() -> *const c_char {
+    SIGNATURE.as_ptr()
+}
+
+#[no_mangle]
+pub extern "C" fn ipasir_init() -> *mut c_void {
+    let named = std::env::var("SATGALAXY_IPASIR_BACKEND")
+        .ok()
+        .and_then(|name| name.parse::<Backend>().ok());
+    match named.or_else(|| Backend::ALL.into_iter().find(|b| b.is_enabled())) {
+        Some(backend) => init(backend),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Like `ipasir_init`, but with the backend named by `name` (e.g. `"picosat"`).
+///
+/// Returns null if the name is unknown or the backend is not enabled.
+#[no_mangle]
+pub unsafe extern "C" fn satgalaxy_ipasir_init_backend(name: *const c_char) -> *mut c_void {
+    match CStr::from_ptr(name)
+        .to_str()
+        .ok()
+        .and_then(|name| name.parse().ok())
+    {
+        Some(backend) => init(backend),
+        None => ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_release(solver: *mut c_void) {
+    if !solver.is_null() {
+        drop(Box::from_raw(solver as *mut IpasirState));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_add(solver: *mut c_void, lit_or_zero: i32) {
+    let state = state(solver);
+    if lit_or_zero != 0 {
+        state.clause.push(lit_or_zero);
+        return;
+    }
+    let clause = std::mem::take(&mut state.clause);
+    // IPASIR has no error channel, so a rejected clause is reported by `ipasir_solve`.
+    if let Err(err) = state.solver.push_clause(&clause) {
+        state.error.get_or_insert(err);
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_assume(solver: *mut c_void, lit: i32) {
+    state(solver).assumptions.push(lit);
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_solve(solver: *mut c_void) -> c_int {
+    let state = state(solver);
+    let assumptions = std::mem::take(&mut state.assumptions);
+    state.model.clear();
+    state.failed.clear();
+    if state.error.is_some() {
+        return 0;
+    }
+    let status = if assumptions.is_empty() {
+        state.solver.solve_sat()
+    } else {
+        state.solver.solve_with_assumptions(&assumptions)
+    };
+    match status {
+        Ok(RawStatus::Satisfiable) => {
+            state.model = state
+                .solver
+                .model()
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            10
+        }
+        Ok(RawStatus::Unsatisfiable) => {
+            state.failed = state
+                .solver
+                .failed_assumptions()
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            20
+        }
+        _ => 0,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_val(solver: *mut c_void, lit: i32) -> i32 {
+    let var = lit.abs();
+    if state(solver).model.contains(&var) {
+        var
+    } else {
+        -var
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_failed(solver: *mut c_void, lit: i32) -> c_int {
+    state(solver).failed.contains(&lit) as c_int
+}
+
+/// Only honoured by backends that support `SatSolver::set_terminate`.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_terminate(
+    solver: *mut c_void,
+    data: *mut c_void,
+    terminate: Option<unsafe extern "C" fn(data: *mut c_void) -> c_int>,
+) {
+    let state = state(solver);
+    let callback: Box<dyn FnMut() -> bool + Send> = match terminate {
+        Some(terminate) => {
+            let data = CallbackData(data);
+            Box::new(move || {
+                let data = &data;
+                unsafe { terminate(data.0) != 0 }
+            })
+        }
+        None => Box::new(|| false),
+    };
+    let _ = state.solver.set_terminate(callback);
+}
+
+/// Learned clauses are not exported by any backend, so the callback is never called.
+#[no_mangle]
+pub unsafe extern "C" fn ipasir_set_learn(
+    _solver: *mut c_void,
+    _data: *mut c_void,
+    _max_length: c_int,
+    _learn: Option<unsafe extern "C" fn(data: *mut c_void, clause: *mut i32)>,
+) {
+}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        unsafe {
+            let solver = satgalaxy_ipasir_init_backend(c"cadical".as_ptr());
+            assert!(!solver.is_null());
+            for lit in [1, 2, 0, -1, 0] {
+                ipasir_add(solver, lit);
+            }
+            assert_eq!(ipasir_solve(solver), 10);
+            assert_eq!(ipasir_val(solver, 1), -1);
+            assert_eq!(ipasir_val(solver, -2), 2);
+            ipasir_assume(solver, -2);
+            assert_eq!(ipasir_solve(solver), 20);
+            assert_eq!(ipasir_failed(solver, -2), 1);
+            ipasir_release(solver);
+            let signature = CStr::from_ptr(ipasir_signature());
+            assert!(signature.to_str().unwrap().starts_with("satgalaxy-"));
+        }
+    }
+
+    #[test]
+    fn rejected_clause() {
+        unsafe {
+            let solver = satgalaxy_ipasir_init_backend(c"cadical".as_ptr());
+            for lit in [1, 0, -1, i32::MIN, 0] {
+                ipasir_add(solver, lit);
+            }
+            assert_eq!(ipasir_solve(solver), 0);
+            ipasir_add(solver, 2);
+            ipasir_add(solver, 0);
+            assert_eq!(ipasir_solve(solver), 0);
+            ipasir_release(solver);
+        }
+    }
+}
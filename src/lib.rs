@@ -1,6 +1,30 @@
 #![doc = include_str!("../README.md")]
+#[cfg(feature = "testing")]
+pub mod differential;
+#[cfg(feature = "parser")]
+pub mod encoder;
 pub mod errors;
 #[cfg(feature = "parser")]
+pub mod generator;
+#[cfg(feature = "ipasir-export")]
+mod ipasir_export;
+pub mod output;
+#[cfg(feature = "parser")]
 pub mod parser;
+pub mod prelude;
+#[cfg(all(
+    feature = "parser",
+    any(
+        feature = "cadical",
+        feature = "glucose",
+        feature = "minisat",
+        feature = "picosat"
+    )
+))]
+pub mod preprocess;
+mod rng;
+#[cfg(feature = "signal")]
+pub mod signal;
 pub mod solver;
+pub mod verify;
 pub use solver::*;
@@ -0,0 +1,211 @@
+//! Reading and writing solver results in SAT competition output format.
+//!
+//! A result is an `s` line (`s SATISFIABLE`, `s UNSATISFIABLE` or `s UNKNOWN`) and,
+//! for a model, `v` lines listing its literals and ending with `0`. Lines starting
+//! with `c` are comments. `write_status` and `read_status` round-trip a `SatStatus`,
+//! so results can be exchanged with solvers outside this crate.
+//! # Example
+//! ```rust
+//! use satgalaxy::output::{parse_status, write_status};
+//! use satgalaxy::solver::SatStatus;
+//! let status = SatStatus::Satisfiable(vec![1, -2, 3]);
+//! let mut out = Vec::new();
+//! write_status(&mut out, &status).unwrap();
+//! assert_eq!(out, b"s SATISFIABLE\nv 1 -2 3 0\n");
+//! assert_eq!(parse_status("c solved\ns SATISFIABLE\nv 1 -2\nv 3 0\n").unwrap(), status);
+//! ```
+use std::io::{self, BufRead, Write};
+
+use thiserror::Error;
+
+use crate::solver::SatStatus;
+
+/// The longest `v` line written by [`write_status`], in bytes.
+pub const LINE_WIDTH: usize = 78;
+
+/// Why solver output could not be read by [`read_status`].
+#[derive(Error, Debug)]
+pub enum OutputError {
+    #[error("Failed to read solver output: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid solver output at line {0}: {1}")]
+    InvalidLine(usize, &'static str),
+    #[error("Missing `s` line in solver output")]
+    MissingStatus,
+}
+
+/// Write `status` as an `s` line and, for a model, `v` lines of at most
+/// [`LINE_WIDTH`] bytes.
+///
+/// The literals of the model are written as given. The competition expects every
+/// variable to be listed, so pass a model from `SatSolver::full_model` rather than
+/// `SatSolver::model`, which lists only the true variables.
+pub fn write_status<W: Write>(out: &mut W, status: &SatStatus) -> io::Result<()> {
+    write_status_with_width(out, status, LINE_WIDTH)
+}
+
+/// Like [`write_status`], wrapping `v` lines at `width` bytes instead.
+///
+/// A line holds at least one literal, however small `width` is.
+pub fn write_status_with_width<W: Write>(
+    out: &mut W,
+    status: &SatStatus,
+    width: usize,
+) -> io::Result<()> {
+    let model = match status {
+        SatStatus::Satisfiable(model) => model,
+        SatStatus::Unsatisfiable => return writeln!(out, "s UNSATISFIABLE"),
+        SatStatus::Unknown => return writeln!(out, "s UNKNOWN"),
+    };
+    writeln!(out, "s SATISFIABLE")?;
+    let mut line = String::from("v");
+    for lit in model.iter().chain([&0]) {
+        let lit = lit.to_string();
+        if line.len() > 1 && line.len() + 1 + lit.len() > width {
+            writeln!(out, "{line}")?;
+            line.truncate(1);
+        }
+        line.push(' ');
+        line.push_str(&lit);
+    }
+    writeln!(out, "{line}")
+}
+
+/// Parse solver output from a string, see [`read_status`].
+pub fn parse_status(input: &str) -> Result<SatStatus, OutputError> {
+    read_status(input.as_bytes())
+}
+
+/// Read solver output in competition format.
+///
+/// Comment and blank lines are skipped. There must be exactly one `s` line;
+/// `s INDETERMINATE` is read as `SatStatus::Unknown`. The `v` lines may come before
+/// or after it, but only a satisfiable result may have them, and they must end with
+/// `0`. The model is returned with its literals in the order they were listed.
+pub fn read_status<R: BufRead>(reader: R) -> Result<SatStatus, OutputError> {
+    let mut status = None;
+    let mut model = Vec::new();
+    // Whether the `v` lines were terminated by `0`.
+    let mut terminated = false;
+    let mut line_no = 0;
+    for line in reader.lines() {
+        let line = line?;
+        line_no += 1;
+        let mut chars = line.trim().chars();
+        let kind = chars.next();
+        let rest = chars.as_str();
+        if matches!(kind, Some('s' | 'v'))
+            && !rest.is_empty()
+            && !rest.starts_with(char::is_whitespace)
+        {
+            return Err(OutputError::InvalidLine(
+                line_no,
+                "expected `c`, `s` or `v`",
+            ));
+        }
+        match kind {
+            None | Some('c') => {}
+            Some('s') => {
+                if status.is_some() {
+                    return Err(OutputError::InvalidLine(line_no, "more than one `s` line"));
+                }
+                status = Some(match rest.trim() {
+                    "SATISFIABLE" => SatStatus::Satisfiable(Vec::new()),
+                    "UNSATISFIABLE" => SatStatus::Unsatisfiable,
+                    "UNKNOWN" | "INDETERMINATE" => SatStatus::Unknown,
+                    _ => return Err(OutputError::InvalidLine(line_no, "unknown status")),
+                });
+            }
+            Some('v') => {
+                for token in rest.split_whitespace() {
+                    let lit: i32 = token
+                        .parse()
+                        .map_err(|_| OutputError::InvalidLine(line_no, "invalid literal"))?;
+                    if terminated {
+                        return Err(OutputError::InvalidLine(line_no, "literal after `0`"));
+                    }
+                    match lit {
+                        0 => terminated = true,
+                        i32::MIN => {
+                            return Err(OutputError::InvalidLine(line_no, "invalid literal"))
+                        }
+                        lit => model.push(lit),
+                    }
+                }
+            }
+            _ => {
+                return Err(OutputError::InvalidLine(
+                    line_no,
+                    "expected `c`, `s` or `v`",
+                ))
+            }
+        }
+    }
+    match status {
+        None => Err(OutputError::MissingStatus),
+        Some(SatStatus::Satisfiable(_)) if !terminated => Err(OutputError::InvalidLine(
+            line_no,
+            "model is not terminated by 0",
+        )),
+        Some(SatStatus::Satisfiable(_)) => Ok(SatStatus::Satisfiable(model)),
+        Some(_) if terminated || !model.is_empty() => Err(OutputError::InvalidLine(
+            line_no,
+            "`v` lines without a satisfiable status",
+        )),
+        Some(status) => Ok(status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let model: Vec<i32> = (1..=40)
+            .map(|var| if var % 3 == 0 { -var } else { var })
+            .collect();
+        for status in [
+            SatStatus::Satisfiable(model),
+            SatStatus::Satisfiable(Vec::new()),
+            SatStatus::Unsatisfiable,
+            SatStatus::Unknown,
+        ] {
+            for width in [0, 10, LINE_WIDTH] {
+                let mut out = Vec::new();
+                write_status_with_width(&mut out, &status, width).unwrap();
+                let text = String::from_utf8(out).unwrap();
+                let mut v_lines = text.lines().filter(|line| line.starts_with('v'));
+                assert!(v_lines.all(|line| line.len() <= width.max("v -39".len())));
+                assert_eq!(parse_status(&text).unwrap(), status);
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_output() {
+        assert!(matches!(
+            parse_status("c nothing\n"),
+            Err(OutputError::MissingStatus)
+        ));
+        for (input, line) in [
+            ("s SATISFIABLE\nv 1 -2\n", 2),
+            ("s SATISFIABLE\nv 1 0 2\n", 2),
+            ("s UNSATISFIABLE\nv 0\n", 2),
+            ("s SAT\n", 1),
+            ("s UNKNOWN\ns UNKNOWN\n", 2),
+            ("s SATISFIABLE\nv 1 x 0\n", 2),
+            ("c ok\nsolution 1\n", 2),
+            ("s SATISFIABLE\n\u{e9} 1 0\n", 2),
+        ] {
+            match parse_status(input) {
+                Err(OutputError::InvalidLine(at, _)) => assert_eq!(at, line, "{input:?}"),
+                other => panic!("{input:?}: {other:?}"),
+            }
+        }
+        assert_eq!(
+            parse_status("v 2 0\r\ns  SATISFIABLE \r\n").unwrap(),
+            SatStatus::Satisfiable(vec![2])
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use std::{fs::File, io::Read, path::Path};
+
+use pest::Parser;
+
+use crate::errors::ParserError;
+use crate::solver::{RawStatus, SatSolver};
+
+use super::{DIMACSParser, Rule, SmartReader};
+
+/// Runs an incremental CNF (`p inccnf`) string on `solver`.
+///
+/// Clauses are added to `solver` as they are read, and every `a <lits> 0` line solves
+/// under the listed assumptions, the cube, over the clauses read so far. Returns the
+/// status of each cube, in input order.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::parse_inccnf;
+/// use satgalaxy::solver::{CaDiCaLSolver, RawStatus};
+/// let input = "p inccnf\n1 2 0\na -1 0\n-2 0\na -1 0\na 0\n";
+/// let mut solver = CaDiCaLSolver::new();
+/// assert_eq!(
+///     parse_inccnf(input, &mut solver).unwrap(),
+///     vec![RawStatus::Satisfiable, RawStatus::Unsatisfiable, RawStatus::Satisfiable]
+/// );
+/// ```
+pub fn parse_inccnf<S: SatSolver + ?Sized>(
+    input: &str,
+    solver: &mut S,
+) -> Result<Vec<RawStatus>, ParserError> {
+    let mut statuses = vec![];
+    let pairs = DIMACSParser::parse(Rule::inc_file, input)?;
+    for pair in pairs.flat_map(|pair| pair.into_inner()) {
+        let rule = pair.as_rule();
+        if rule != Rule::clause && rule != Rule::cube {
+            continue;
+        }
+        let mut lits = Vec::new();
+        for lit_pair in pair.into_inner().filter(|pair| pair.as_rule() == Rule::lit) {
+            let lit = lit_pair.as_str().parse::<i32>()?;
+            if lit == i32::MIN {
+                return Err(ParserError::InvalidLiteral(lit));
+            }
+            lits.push(lit);
+        }
+        if rule == Rule::clause {
+            solver.push_clause(&lits)?;
+        } else {
+            statuses.push(solver.solve_with_assumptions(&lits)?);
+        }
+    }
+    Ok(statuses)
+}
+
+/// Reads an incremental CNF file from a given path and runs it, see [`parse_inccnf`].
+pub fn read_inccnf_from_file<P: AsRef<Path>, S: SatSolver + ?Sized>(
+    path: P,
+    solver: &mut S,
+) -> Result<Vec<RawStatus>, ParserError> {
+    read_inccnf_from_reader(File::open(path)?, solver)
+}
+
+/// Reads an incremental CNF file from a given reader and runs it, see [`parse_inccnf`].
+///
+/// Compressed input is detected automatically when the `compression` feature is enabled.
+pub fn read_inccnf_from_reader<R: Read, S: SatSolver + ?Sized>(
+    reader: R,
+    solver: &mut S,
+) -> Result<Vec<RawStatus>, ParserError> {
+    let mut reader = SmartReader::new(reader)?;
+    let mut buf = String::new();
+    reader.read_to_string(&mut buf)?;
+    parse_inccnf(&buf, solver)
+}
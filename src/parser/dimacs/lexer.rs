@@ -0,0 +1,469 @@
+use std::ops::Range;
+
+use crate::{
+    errors::{ParserError, Position},
+    parser::AsDimacs,
+};
+
+use super::{deliver_comment, Validator};
+
+/// An item of the input, in input order. Clause literals are kept in [`Chunk::lits`].
+enum Event {
+    Header(i32, i32),
+    /// The comment text after the `c`.
+    Comment(Range<usize>),
+    /// A clause, an `x` line or a last clause without its `0`, each with the byte offset
+    /// and line (counted within the chunk) of its first token.
+    Clause(usize, usize),
+    Xor(usize, usize),
+    Unterminated(usize, usize),
+}
+
+/// A lexed DIMACS input, in one or more chunks of whole lines.
+pub(super) struct Lexed {
+    chunks: Vec<Chunk>,
+}
+
+/// A lexed run of lines: no tree, just the events and one flat literal buffer in which
+/// every clause is terminated by 0.
+struct Chunk {
+    events: Vec<Event>,
+    lits: Vec<i32>,
+    /// The number of lines started, counting a last line without a line break.
+    lines: usize,
+}
+
+struct Lexer<'a> {
+    input: &'a [u8],
+    i: usize,
+    line: usize,
+    lits: Vec<i32>,
+    events: Vec<Event>,
+}
+
+impl Lexer<'_> {
+    fn skip_blanks(&mut self) {
+        while matches!(self.input.get(self.i), Some(b' ' | b'\t')) {
+            self.i += 1;
+        }
+    }
+
+    /// Consume a line break, returning `None` if there is something else.
+    fn newline(&mut self) -> Option<()> {
+        match self.input[self.i..] {
+            [b'\n', ..] => self.i += 1,
+            [b'\r', b'\n', ..] => self.i += 2,
+            _ => return None,
+        }
+        self.line += 1;
+        Some(())
+    }
+
+    fn at_line_end(&self) -> bool {
+        matches!(self.input.get(self.i), None | Some(b'\n' | b'\r'))
+    }
+
+    /// An unsigned decimal with no leading zeros that fits an `i32`.
+    fn digits(&mut self) -> Option<i32> {
+        let start = self.i;
+        let mut value: i32 = 0;
+        while let Some(&b) = self.input.get(self.i) {
+            if !b.is_ascii_digit() {
+                break;
+            }
+            value = value.checked_mul(10)?.checked_add((b - b'0') as i32)?;
+            self.i += 1;
+        }
+        let len = self.i - start;
+        (len > 0 && (len == 1 || self.input[start] != b'0')).then_some(value)
+    }
+
+    /// The next token of a clause: a literal, or 0 for the terminator.
+    fn token(&mut self) -> Option<i32> {
+        let negative = self.input[self.i] == b'-';
+        if negative {
+            self.i += 1;
+        }
+        let value = self.digits()?;
+        // `1-2` and the like are left to the grammar, as are `-0` and `i32::MIN`.
+        let separated = matches!(
+            self.input.get(self.i),
+            None | Some(b' ' | b'\t' | b'\n' | b'\r')
+        );
+        match (separated, negative, value) {
+            (false, _, _) | (true, true, 0) => None,
+            (true, true, value) => Some(-value),
+            (true, false, value) => Some(value),
+        }
+    }
+
+    /// Read the tokens up to the end of the line, returning the start of a clause left
+    /// open.
+    fn clauses(&mut self) -> Option<Option<(usize, usize)>> {
+        let mut open = None;
+        loop {
+            self.skip_blanks();
+            if self.at_line_end() {
+                return Some(open);
+            }
+            let start = (self.i, self.line);
+            let lit = self.token()?;
+            self.lits.push(lit);
+            let (offset, line) = *open.get_or_insert(start);
+            if lit == 0 {
+                self.events.push(Event::Clause(offset, line));
+                open = None;
+            }
+        }
+    }
+
+    fn lex(mut self) -> Option<Chunk> {
+        let mut seen_header = false;
+        let mut seen_clause = false;
+        loop {
+            self.skip_blanks();
+            let Some(&first) = self.input.get(self.i) else {
+                break;
+            };
+            match first {
+                b'\n' | b'\r' => self.newline()?,
+                b'c' => {
+                    let start = self.i + 1;
+                    while !self.at_line_end() {
+                        self.i += 1;
+                    }
+                    self.events.push(Event::Comment(start..self.i));
+                }
+                b'p' if !seen_header && !seen_clause => {
+                    if !self.input[self.i..].starts_with(b"p cnf") {
+                        return None;
+                    }
+                    self.i += 5;
+                    self.skip_blanks();
+                    let variables = self.digits()?;
+                    self.skip_blanks();
+                    let clauses = self.digits()?;
+                    self.skip_blanks();
+                    self.events.push(Event::Header(variables, clauses));
+                    seen_header = true;
+                    self.newline()?;
+                }
+                b'x' => {
+                    let start = (self.i, self.line);
+                    self.i += 1;
+                    let len = self.lits.len();
+                    loop {
+                        self.skip_blanks();
+                        if self.at_line_end() {
+                            return None;
+                        }
+                        let lit = self.token()?;
+                        self.lits.push(lit);
+                        if lit == 0 {
+                            break;
+                        }
+                    }
+                    if self.lits.len() - len < 2 {
+                        return None;
+                    }
+                    self.events.push(Event::Xor(start.0, start.1));
+                    seen_clause = true;
+                    // Clauses may follow on the same line.
+                    if let Some(open) = self.clauses()? {
+                        return self.unterminated(open);
+                    }
+                }
+                _ => {
+                    seen_clause = true;
+                    if let Some(open) = self.clauses()? {
+                        return self.unterminated(open);
+                    }
+                }
+            }
+        }
+        Some(self.finish())
+    }
+
+    fn finish(self) -> Chunk {
+        Chunk {
+            events: self.events,
+            lits: self.lits,
+            lines: self.line,
+        }
+    }
+
+    /// Finish after a line that leaves the clause at `start` open, which is only valid
+    /// at the end of the input.
+    fn unterminated(mut self, (offset, line): (usize, usize)) -> Option<Chunk> {
+        if !self.input[self.i..].iter().all(u8::is_ascii_whitespace) {
+            return None;
+        }
+        self.lits.push(0);
+        self.events.push(Event::Unterminated(offset, line));
+        Some(self.finish())
+    }
+}
+
+/// Lex `input`, or return `None` if it contains anything beyond plain clauses, `x`
+/// lines, whole-line comments and the header, or anything malformed; those are left to
+/// the grammar, which also produces the errors.
+pub(super) fn lex(input: &str) -> Option<Lexed> {
+    let chunk = lex_chunk(input.as_bytes(), 0)?;
+    Some(Lexed {
+        chunks: vec![chunk],
+    })
+}
+
+/// Lex the lines of `input` from byte `start` on; offsets stay relative to `input`.
+fn lex_chunk(input: &[u8], start: usize) -> Option<Chunk> {
+    Lexer {
+        input,
+        i: start,
+        line: 1,
+        lits: Vec::new(),
+        events: Vec::new(),
+    }
+    .lex()
+}
+
+/// The smallest chunk worth a task of its own.
+#[cfg(feature = "parallel")]
+const MIN_CHUNK: usize = 1 << 20;
+
+/// [`lex`] on the rayon thread pool, splitting `input` into chunks at line breaks.
+///
+/// A lexable clause never spans a line break, so the chunks can be lexed apart; the
+/// rules that span chunks (one header, before any clause, and an unterminated clause
+/// only at the end) are checked once they are done.
+#[cfg(feature = "parallel")]
+pub(super) fn lex_parallel(input: &str) -> Option<Lexed> {
+    let num_chunks = rayon::current_num_threads().min(input.len() / MIN_CHUNK);
+    lex_chunks(input, num_chunks)
+}
+
+/// Lex `input` in about `num_chunks` chunks in parallel.
+#[cfg(feature = "parallel")]
+fn lex_chunks(input: &str, num_chunks: usize) -> Option<Lexed> {
+    use rayon::prelude::*;
+
+    let input = input.as_bytes();
+    let mut bounds = vec![0];
+    for k in 1..num_chunks {
+        let target = (input.len() * k / num_chunks).max(bounds[bounds.len() - 1]);
+        match input[target..].iter().position(|&b| b == b'\n') {
+            Some(i) => bounds.push(target + i + 1),
+            None => break,
+        }
+    }
+    bounds.push(input.len());
+    bounds.dedup();
+    let chunks = bounds
+        .par_windows(2)
+        .map(|bound| lex_chunk(&input[..bound[1]], bound[0]))
+        .collect::<Option<Vec<Chunk>>>()?;
+
+    let mut seen_header = false;
+    let mut seen_clause = false;
+    for (k, chunk) in chunks.iter().enumerate() {
+        if k + 1 < chunks.len() && matches!(chunk.events.last(), Some(Event::Unterminated(..))) {
+            return None;
+        }
+        for event in &chunk.events {
+            match event {
+                Event::Header(..) if seen_header || seen_clause => return None,
+                Event::Header(..) => seen_header = true,
+                Event::Comment(..) => {}
+                _ => seen_clause = true,
+            }
+        }
+    }
+    Some(Lexed { chunks })
+}
+
+/// Feed a lexed input to `dim`, with the checks and error positions of the grammar
+/// path.
+pub(super) fn replay<D: AsDimacs>(
+    input: &str,
+    lexed: Lexed,
+    validator: &mut Validator,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    // The number of lines before the current chunk.
+    let mut lines_before = 0;
+    for chunk in lexed.chunks {
+        let lines = chunk.lines;
+        replay_chunk(input, chunk, lines_before, validator, dim)?;
+        lines_before += lines - 1;
+    }
+    Ok(())
+}
+
+fn replay_chunk<D: AsDimacs>(
+    input: &str,
+    chunk: Chunk,
+    lines_before: usize,
+    validator: &mut Validator,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let mut lits = chunk.lits.into_iter();
+    for event in chunk.events {
+        let (offset, line) = match event {
+            Event::Header(variables, clauses) => {
+                validator.header = Some((variables, clauses));
+                dim.set_header(variables as usize, clauses as usize);
+                continue;
+            }
+            Event::Comment(range) => {
+                deliver_comment(&input[range], dim);
+                continue;
+            }
+            Event::Clause(offset, line)
+            | Event::Xor(offset, line)
+            | Event::Unterminated(offset, line) => (offset, lines_before + line),
+        };
+        let clause: Vec<i32> = lits.by_ref().take_while(|&lit| lit != 0).collect();
+        let clause_index = validator.num_clauses as usize;
+        // Columns are only computed for errors.
+        let at = || {
+            let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+            Position {
+                offset,
+                line,
+                column: input[line_start..offset].chars().count() + 1,
+                clause: Some(clause_index),
+            }
+        };
+        if matches!(event, Event::Unterminated(..)) && !validator.options.allow_missing_terminator {
+            let error = ParserError::InvalidLine(line, "clause is not terminated by 0");
+            return Err(error.at(at()));
+        }
+        validator
+            .check_clause(&clause, line)
+            .map_err(|err| err.at(at()))?;
+        if matches!(event, Event::Xor(..)) {
+            dim.push_xor(clause).map_err(|err| err.at(at()))?;
+        } else {
+            dim.push_clause(clause).map_err(|err| err.at(at()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_dimacs_cnf, ParseEngine, ParseMode, ParserOptions, Problem};
+
+    /// Keeps the clauses and XORs apart.
+    #[derive(Default)]
+    struct Sink(Problem, Vec<Vec<i32>>);
+
+    impl AsDimacs for Sink {
+        fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
+            self.0.push_clause(clause)
+        }
+        fn push_xor(&mut self, lits: Vec<i32>) -> Result<(), ParserError> {
+            self.1.push(lits);
+            Ok(())
+        }
+        fn add_comment(&mut self, comment: String) {
+            self.0.add_comment(comment);
+        }
+        fn set_header(&mut self, num_vars: usize, num_clauses: usize) {
+            self.0.set_header(num_vars, num_clauses);
+        }
+        fn add_independent_support(&mut self, vars: Vec<i32>) {
+            self.0.add_independent_support(vars);
+        }
+    }
+
+    type Outcome = Result<(Problem, Vec<Vec<i32>>), String>;
+
+    fn outcome(result: Result<(), ParserError>, sink: Sink) -> Outcome {
+        match result {
+            Ok(()) => Ok((sink.0, sink.1)),
+            Err(err) => Err(format!("{err} {:?}", err.position())),
+        }
+    }
+
+    fn parse(input: &str, mode: ParseMode, engine: ParseEngine) -> Outcome {
+        let options = ParserOptions {
+            mode,
+            engine,
+            ..ParserOptions::default()
+        };
+        let mut sink = Sink::default();
+        let result = parse_dimacs_cnf(input, options, &mut sink);
+        outcome(result, sink)
+    }
+
+    #[test]
+    fn matches_grammar() {
+        let lexed = [
+            "",
+            "c only a comment",
+            "c t mc\np cnf 4 3\nc ind 1 2 0\n1 -3 0\n\n  2 3 -4 0 -1 0\r\n",
+            "p cnf 3 2\nx 1 -2 0 3 0\nc end\n",
+            "1 2 0\n3",
+            "1 2 0\n3 4\n\n",
+            "p cnf 2 1\n1 0\n2 0\n",
+            "p cnf 2 2\n1 3 0\n",
+            "1 0\n0\n",
+        ];
+        let fallback = [
+            "1 2 c inline\n3 0\n",
+            "1-2 0\n",
+            "1 2\n3 0\n",
+            "1 0\np cnf 1 1\n",
+            "p cnf 1 1",
+            "1 -2147483648 0\n",
+            "1 99999999999 0\n",
+            "1 -0 0\n",
+            "p cnf 1 1\nx 0\n",
+            "1 a 0\n",
+            "1 2\nc trailing\n",
+        ];
+        for input in lexed.iter().chain(&fallback) {
+            assert_eq!(lex(input).is_some(), lexed.contains(input), "{input:?}");
+            for mode in [ParseMode::Strict, ParseMode::Inferred] {
+                assert_eq!(
+                    parse(input, mode, ParseEngine::Auto),
+                    parse(input, mode, ParseEngine::Grammar),
+                    "{input:?} in {mode:?}"
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn chunks_match_grammar() {
+        let inputs = [
+            "c a\np cnf 5 4\n1 -2 0\nc b\n3 4 0\n\n5 0\nx 1 2 0\n",
+            "c ind 1 0\n1 0\n2 0\n3 0\n4 5\n",
+            "1 0\n2 0\n3 0\np cnf 3 3\n",
+            "p cnf 3 3\n1 0\n2 0\np cnf 3 3\n",
+            "1 0\n2\n3 0\n",
+            "1 0\n2 0\n0\n",
+        ];
+        for input in inputs {
+            for num_chunks in 1..=6 {
+                let chunked = lex_chunks(input, num_chunks);
+                assert_eq!(chunked.is_some(), lex(input).is_some(), "{input:?}");
+                let Some(_) = chunked else { continue };
+                for mode in [ParseMode::Strict, ParseMode::Inferred] {
+                    let mut validator = Validator::new(mode.into());
+                    let mut sink = Sink::default();
+                    let lexed = lex_chunks(input, num_chunks).unwrap();
+                    let result = replay(input, lexed, &mut validator, &mut sink)
+                        .and_then(|()| validator.finish());
+                    assert_eq!(
+                        outcome(result, sink),
+                        parse(input, mode, ParseEngine::Grammar),
+                        "{input:?} in {num_chunks} chunks, {mode:?}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,291 @@
+use std::{fs::File, mem, path::Path};
+
+use memmap2::Mmap;
+
+use crate::{
+    errors::{ParserError, Position},
+    parser::{AsDimacs, ParseMode},
+};
+
+use super::{
+    deliver_comment,
+    stream::{push_unterminated, LineBuffer},
+    ParserOptions, Validator,
+};
+
+/// Magic bytes of the formats `SmartReader` decompresses.
+#[cfg(feature = "compression")]
+const COMPRESSED_MAGIC: [&[u8]; 4] = [
+    &[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00],
+    &[0x1F, 0x8B],
+    &[0x28, 0xB5, 0x2F, 0xFD],
+    b"BZh",
+];
+
+/// Memory-maps the DIMACS CNF file at `path` and parses it with [`parse_dimacs_bytes`].
+///
+/// The file is never copied into a `String`, so peak memory stays near the size of the
+/// parsed clauses rather than twice the file. Compressed files cannot be mapped
+/// usefully; with the `compression` feature enabled they are decompressed by
+/// [`read_dimacs_streaming`](crate::parser::read_dimacs_streaming) instead.
+///
+/// The file must not be modified while it is parsed.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::{read_dimacs_mmap, ParseMode};
+/// let path = std::env::temp_dir().join("satgalaxy_read_dimacs_mmap.cnf");
+/// std::fs::write(&path, "p cnf 3 2\n1 -3 0\n2 3 0\n").unwrap();
+/// let mut cnf = Vec::new();
+/// read_dimacs_mmap(&path, ParseMode::Strict, &mut cnf).unwrap();
+/// assert_eq!(cnf, vec![vec![1, -3], vec![2, 3]]);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub fn read_dimacs_mmap<P: AsRef<Path>, D: AsDimacs>(
+    path: P,
+    options: impl Into<ParserOptions>,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let file = File::open(path)?;
+    // SAFETY: the mapping is only read, and the caller must not modify the file
+    // while it is mapped, as documented.
+    let map = unsafe { Mmap::map(&file)? };
+    #[cfg(feature = "compression")]
+    if COMPRESSED_MAGIC.iter().any(|magic| map.starts_with(magic)) {
+        return super::read_dimacs_streaming(&map[..], options, dim);
+    }
+    parse_dimacs_bytes(&map, options, dim)
+}
+
+/// Parses DIMACS CNF from raw bytes with a byte-level scanner.
+///
+/// Accepts the same input as
+/// [`read_dimacs_streaming`](crate::parser::read_dimacs_streaming), with the same errors
+/// and positions, but needs no UTF-8 validation: only comments are decoded, lossily.
+pub fn parse_dimacs_bytes<D: AsDimacs>(
+    input: &[u8],
+    options: impl Into<ParserOptions>,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let mut validator = Validator::new(options.into());
+    let mut line_no = 0;
+    // The byte offset of the current line in the input.
+    let mut offset = 0;
+    let mut clause = Vec::new();
+    for line in input.split_inclusive(|&b| b == b'\n') {
+        line_no += 1;
+        let mut column = 0;
+        let header = trim(line).first() == Some(&b'p');
+        let locate = |err: ParserError, column: usize, validator: &Validator| {
+            err.at(Position {
+                offset: offset + column,
+                line: line_no,
+                // Characters are counted by their first byte.
+                column: line[..column].iter().filter(|&&b| b & 0xC0 != 0x80).count() + 1,
+                clause: (!header).then_some(validator.num_clauses as usize),
+            })
+        };
+        if validator.options.mode == ParseMode::Lenient {
+            let mut buffer = LineBuffer::new(&validator, &clause);
+            let result = scan_line(
+                line,
+                line_no,
+                &mut column,
+                &mut validator,
+                &mut clause,
+                &mut buffer,
+            )
+            .map_err(|err| locate(err, column, &validator));
+            buffer
+                .finish(result, &mut validator, &mut clause, dim)
+                .map_err(|err| locate(err, column, &validator))?;
+        } else {
+            scan_line(line, line_no, &mut column, &mut validator, &mut clause, dim)
+                .map_err(|err| locate(err, column, &validator))?;
+        }
+        offset += line.len();
+    }
+    let at = Position {
+        offset,
+        line: line_no,
+        column: 1,
+        clause: Some(validator.num_clauses as usize),
+    };
+    push_unterminated(&mut validator, clause, at, dim)?;
+    validator.finish()
+}
+
+fn trim(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(start, |i| i + 1);
+    &bytes[start..end]
+}
+
+fn tokens(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    bytes
+        .split(|b| b.is_ascii_whitespace())
+        .filter(|token| !token.is_empty())
+}
+
+/// Parse a decimal `i32`, falling back to `str::parse` for its error.
+fn parse_int(token: &[u8], line_no: usize) -> Result<i32, ParserError> {
+    let (negative, digits) = match token {
+        [b'-', digits @ ..] => (true, digits),
+        digits => (false, digits),
+    };
+    if !digits.is_empty() && digits.len() <= 10 && digits.iter().all(u8::is_ascii_digit) {
+        let value = digits
+            .iter()
+            .fold(0i64, |value, &b| value * 10 + (b - b'0') as i64);
+        if let Ok(value) = i32::try_from(if negative { -value } else { value }) {
+            return Ok(value);
+        }
+    }
+    let token = std::str::from_utf8(token)
+        .map_err(|_| ParserError::InvalidLine(line_no, "invalid UTF-8 in a number"))?;
+    Ok(token.parse::<i32>()?)
+}
+
+/// Read one line into `dim`, leaving an unterminated clause in `clause`; the byte
+/// counterpart of `stream_line`.
+///
+/// On error, `column` is the byte offset in `line` of the token that failed.
+fn scan_line<D: AsDimacs>(
+    line: &[u8],
+    line_no: usize,
+    column: &mut usize,
+    validator: &mut Validator,
+    clause: &mut Vec<i32>,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let offset_of = |token: &[u8]| token.as_ptr() as usize - line.as_ptr() as usize;
+    let trimmed = trim(line);
+    *column = offset_of(trimmed);
+    match trimmed.first() {
+        Some(b'c') => {
+            deliver_comment(&String::from_utf8_lossy(&trimmed[1..]), dim);
+            return Ok(());
+        }
+        Some(b'p') => {
+            if validator.header.is_some() || validator.num_clauses > 0 || !clause.is_empty() {
+                return Err(ParserError::InvalidLine(
+                    line_no,
+                    "unexpected `p cnf` header",
+                ));
+            }
+            let mut fields = tokens(&trimmed[1..]);
+            if fields.next() != Some(b"cnf") {
+                return Err(ParserError::InvalidLine(line_no, "expected `p cnf`"));
+            }
+            let (Some(variables), Some(clauses), None) =
+                (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(ParserError::InvalidLine(
+                    line_no,
+                    "malformed `p cnf` header",
+                ));
+            };
+            *column = offset_of(variables);
+            let variables = parse_int(variables, line_no)?;
+            *column = offset_of(clauses);
+            let clauses = parse_int(clauses, line_no)?;
+            dim.set_header(variables as usize, clauses as usize);
+            validator.header = Some((variables, clauses));
+            return Ok(());
+        }
+        Some(b'x') => {
+            if !clause.is_empty() {
+                return Err(ParserError::InvalidLine(
+                    line_no,
+                    "unterminated clause before XOR",
+                ));
+            }
+            let mut lits = Vec::new();
+            let mut fields = tokens(&trimmed[1..]);
+            loop {
+                let Some(token) = fields.next() else {
+                    return Err(ParserError::InvalidLine(
+                        line_no,
+                        "XOR clause is not terminated by 0",
+                    ));
+                };
+                *column = offset_of(token);
+                match parse_int(token, line_no)? {
+                    0 => break,
+                    i32::MIN => return Err(ParserError::InvalidLiteral(i32::MIN)),
+                    lit => lits.push(lit),
+                }
+            }
+            if lits.is_empty() || fields.next().is_some() {
+                *column = offset_of(trimmed);
+                return Err(ParserError::InvalidLine(line_no, "malformed XOR clause"));
+            }
+            validator.check_clause(&lits, line_no)?;
+            return dim.push_xor(lits);
+        }
+        _ => {}
+    }
+    for token in tokens(trimmed) {
+        *column = offset_of(token);
+        let lit = parse_int(token, line_no)?;
+        if lit != 0 {
+            if lit == i32::MIN {
+                return Err(ParserError::InvalidLiteral(lit));
+            }
+            clause.push(lit);
+            // Checked as the clause grows, so an endless clause cannot exhaust memory.
+            validator.check_len(clause.len(), line_no)?;
+            continue;
+        }
+        validator.check_clause(clause, line_no)?;
+        dim.push_clause(mem::take(clause))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{read_dimacs_streaming, ParseMode, Problem};
+
+    #[test]
+    fn bytes_match_streaming() {
+        let input = "c caf\u{e9}\np cnf 5 2\n1 -3 0\n2 3\n-4 0\nc ind 1 2 0\n";
+        let mut scanned = Problem::new();
+        let mut streamed = Problem::new();
+        parse_dimacs_bytes(input.as_bytes(), ParseMode::Strict, &mut scanned).unwrap();
+        read_dimacs_streaming(input.as_bytes(), ParseMode::Strict, &mut streamed).unwrap();
+        assert_eq!(scanned, streamed);
+        assert_eq!(scanned.clauses, vec![vec![1, -3], vec![2, 3, -4]]);
+        assert_eq!(scanned.comments, vec!["caf\u{e9}", "ind 1 2 0"]);
+        assert_eq!(scanned.independent_support, Some(vec![1, 2]));
+
+        for bad in [
+            "1 2147483648 0\n",
+            "1 -2147483648 0\n",
+            "c \u{e9}\n\u{e9} 1 a 0\n",
+            "1 2",
+        ] {
+            let scanned = parse_dimacs_bytes(bad.as_bytes(), false, &mut vec![]);
+            let streamed = read_dimacs_streaming(bad.as_bytes(), false, &mut vec![]);
+            assert_eq!(
+                scanned.unwrap_err().position(),
+                streamed.unwrap_err().position()
+            );
+        }
+        let lenient = "1 2 0\n3 a 0\n-1 0\n4";
+        let mut scanned = Problem::new();
+        let mut streamed = Problem::new();
+        parse_dimacs_bytes(lenient.as_bytes(), ParseMode::Lenient, &mut scanned).unwrap();
+        read_dimacs_streaming(lenient.as_bytes(), ParseMode::Lenient, &mut streamed).unwrap();
+        assert_eq!(scanned, streamed);
+        assert_eq!(scanned.clauses, vec![vec![1, 2], vec![-1]]);
+        assert_eq!(parse_int(b"-2147483648", 1).unwrap(), i32::MIN);
+        assert_eq!(parse_int(b"007", 1).unwrap(), 7);
+    }
+}
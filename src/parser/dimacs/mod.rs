@@ -1,22 +1,209 @@
-use crate::{errors::ParserError, parser::AsDimacs};
+mod inccnf;
+mod lexer;
+#[cfg(feature = "mmap")]
+mod mmap;
+mod stream;
+mod writer;
+pub use inccnf::{parse_inccnf, read_inccnf_from_file, read_inccnf_from_reader};
+#[cfg(feature = "mmap")]
+pub use mmap::{parse_dimacs_bytes, read_dimacs_mmap};
+pub use stream::read_dimacs_streaming;
+pub use writer::{write_comment, write_dimacs, write_dimacs_to_file, SmartWriter};
+
+use crate::{
+    errors::{ParserError, Position},
+    parser::{AsDimacs, ParseMode},
+};
+#[cfg(feature = "compression")]
+use bzip2::read::MultiBzDecoder;
 #[cfg(feature = "compression")]
 use flate2::read::GzDecoder;
 #[cfg(feature = "compression")]
 use std::io::Cursor;
 use std::{
-    cmp::max,
     fs::File,
     io::{self, BufReader, Read},
     path::Path,
 };
 #[cfg(feature = "compression")]
 use xz2::read::XzDecoder;
+#[cfg(feature = "compression")]
+use zstd::stream::read::Decoder as ZstdDecoder;
 
-use pest::Parser;
+use pest::{iterators::Pair, Parser};
 #[derive(pest_derive::Parser)]
 #[grammar = "../pest/dimacs.pest"]
 struct DIMACSParser;
 
+/// Limits and leniency switches for the DIMACS parsers, on top of a [`ParseMode`].
+///
+/// A `ParseMode`, or a `bool` where `true` means strict, converts into the default
+/// options of that mode, so the parser functions accept any of the three. The limits
+/// bound what a hostile input can make the parser allocate or feed to a solver.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::{parse_dimacs_cnf, ParserOptions};
+/// let options = ParserOptions {
+///     max_clause_len: Some(2),
+///     allow_missing_terminator: true,
+///     ..ParserOptions::default()
+/// };
+/// let mut cnf = Vec::new();
+/// parse_dimacs_cnf("1 -2 0\n3", options, &mut cnf).unwrap();
+/// assert_eq!(cnf, vec![vec![1, -2], vec![3]]);
+/// assert!(parse_dimacs_cnf("1 2 3 0\n", options, &mut cnf).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ParserOptions {
+    pub mode: ParseMode,
+    /// How [`parse_dimacs_cnf`] reads its input; the streaming parser ignores it.
+    pub engine: ParseEngine,
+    /// Reject clauses with more literals than this.
+    pub max_clause_len: Option<usize>,
+    /// Reject variables above this, whatever the header declares.
+    pub max_vars: Option<usize>,
+    /// Accept the empty clause, a lone `0`.
+    pub allow_empty_clauses: bool,
+    /// Accept a last clause that runs into the end of the input without its `0`.
+    pub allow_missing_terminator: bool,
+}
+
+/// The implementation behind [`parse_dimacs_cnf`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseEngine {
+    /// A hand-written lexer that scans the bytes into a flat literal buffer, with no
+    /// parse tree. Anything it does not handle (inline comments, malformed tokens, and
+    /// every error in the input) goes to the grammar instead, so the results and errors
+    /// are those of `Grammar`.
+    #[default]
+    Auto,
+    /// Always the pest grammar.
+    Grammar,
+    /// `Auto`, with the input split at line breaks and the chunks lexed on the rayon
+    /// thread pool. The clauses still reach the sink in input order. Inputs under a few
+    /// megabytes are lexed in one piece.
+    #[cfg(feature = "parallel")]
+    Parallel,
+}
+
+impl From<ParseMode> for ParserOptions {
+    fn from(mode: ParseMode) -> Self {
+        Self {
+            mode,
+            ..Self::default()
+        }
+    }
+}
+
+impl From<bool> for ParserOptions {
+    fn from(strict: bool) -> Self {
+        ParseMode::from(strict).into()
+    }
+}
+
+/// The position of `pair` in the input.
+fn position(pair: &Pair<'_, Rule>, clause: Option<usize>) -> Position {
+    let (line, column) = pair.line_col();
+    Position {
+        offset: pair.as_span().start(),
+        line,
+        column,
+        clause,
+    }
+}
+
+/// Pass a comment to `dim`, and its variables too if it is a `c ind` line.
+fn deliver_comment<D: AsDimacs>(comment: &str, dim: &mut D) {
+    let comment = comment.trim();
+    if let Some(vars) = independent_support(comment) {
+        dim.add_independent_support(vars);
+    }
+    dim.add_comment(comment.to_string());
+}
+
+/// The variables of a `c ind <vars> 0` comment, given without the leading `c`.
+///
+/// Malformed lines are plain comments.
+fn independent_support(comment: &str) -> Option<Vec<i32>> {
+    let rest = comment.strip_prefix("ind")?;
+    if !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let mut vars = Vec::new();
+    let mut tokens = rest.split_whitespace();
+    loop {
+        match tokens.next()?.parse::<i32>().ok()? {
+            0 => break,
+            var if var > 0 => vars.push(var),
+            _ => return None,
+        }
+    }
+    tokens.next().is_none().then_some(vars)
+}
+
+/// The checks both DIMACS parsers apply to each clause once its literals are read.
+#[derive(Clone)]
+struct Validator {
+    options: ParserOptions,
+    /// The declared variable and clause counts.
+    header: Option<(i32, i32)>,
+    num_clauses: i32,
+}
+
+impl Validator {
+    fn new(options: ParserOptions) -> Self {
+        Self {
+            options,
+            header: None,
+            num_clauses: 0,
+        }
+    }
+
+    fn check_len(&self, len: usize, line: usize) -> Result<(), ParserError> {
+        match self.options.max_clause_len {
+            Some(max) if len > max => Err(ParserError::InvalidLine(
+                line,
+                "clause exceeds the maximum length",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn check_clause(&mut self, clause: &[i32], line: usize) -> Result<(), ParserError> {
+        if clause.is_empty() && !self.options.allow_empty_clauses {
+            return Err(ParserError::InvalidLine(line, "empty clause"));
+        }
+        self.check_len(clause.len(), line)?;
+        let max_var = clause.iter().map(|lit| lit.abs()).max().unwrap_or(0);
+        if let Some(max) = self.options.max_vars {
+            if max_var as usize > max {
+                return Err(ParserError::TooManyVariables(max_var, max as i32));
+            }
+        }
+        if self.options.mode == ParseMode::Strict {
+            let Some((variables, clauses)) = self.header else {
+                return Err(ParserError::MissingHeader);
+            };
+            if self.num_clauses + 1 > clauses {
+                return Err(ParserError::TooManyClauses(self.num_clauses + 1, clauses));
+            }
+            if max_var > variables {
+                return Err(ParserError::TooManyVariables(max_var, variables));
+            }
+        }
+        // Counted once accepted, so errors report the index of the offending clause.
+        self.num_clauses += 1;
+        Ok(())
+    }
+
+    fn finish(&self) -> Result<(), ParserError> {
+        if self.options.mode == ParseMode::Strict && self.header.is_none() {
+            return Err(ParserError::MissingHeader);
+        }
+        Ok(())
+    }
+}
+
 /// Parses a DIMACS CNF format string into a `CnfFormula` struct.
 /// # Example
 /// ```rust
@@ -43,7 +230,7 @@ struct DIMACSParser;
 /// # Arguments
 ///
 /// * `input` - A string slice that holds the content of the DIMACS CNF file.
-/// * `strict` - A boolean flag that determines whether to enforce strict parsing rules.
+/// * `options` - A [`ParseMode`] (or a `bool`, where `true` means strict), or [`ParserOptions`] with limits on top of it.
 /// *  `dim` - A immutable reference to an implement of `AsDimacs`  containing the parsed DIMACS CNF data.
 ///
 /// # Returns
@@ -55,96 +242,148 @@ struct DIMACSParser;
 ///
 /// This function will return an error if:
 /// * The input does not conform to the DIMACS CNF format.
-/// * The number of variables exceeds the declared number when in strict mode.
+/// * The number of variables or clauses exceeds the declared number when in strict mode.
+/// * A clause breaks a limit of [`ParserOptions`].
+/// * The `p cnf` header is missing when in strict mode.
 /// * Any integer parsing fails.
 ///
+/// Errors found in a clause or the header are `ParserError::Located` with their
+/// [`Position`], see [`ParserError::render_snippet`].
+///
 /// # Behavior
 ///
-/// * Parses the input string according to DIMACS CNF format rules.
-/// * In strict mode, it enforces the declared number of variables and clauses.
+/// * Parses the input string according to DIMACS CNF format rules, with the fast lexer
+///   of [`ParseEngine::Auto`] unless `options` select the grammar.
+/// * In strict mode, it requires the `p cnf` header and enforces the declared number of variables and clauses.
+/// * In lenient mode, it reads the input like [`read_dimacs_streaming`], skipping malformed lines.
+/// * The limits of [`ParserOptions`] apply in either mode.
+/// * Otherwise the header is optional, as in the 2022 competition format.
+/// * `x` lines (CryptoMiniSat XOR clauses) go to `AsDimacs::push_xor` and count as clauses.
+/// * Comment lines go to `AsDimacs::add_comment` without the leading `c`, and the
+///   variables of `c ind <vars> 0` lines to `AsDimacs::add_independent_support`.
 /// * Constructs a `CnfFormula` with parsed clauses and variable information.
 pub fn parse_dimacs_cnf<D: AsDimacs>(
     input: &str,
-    strict: bool,
+    options: impl Into<ParserOptions>,
     dim: &mut D,
 ) -> Result<(), ParserError> {
-    let mut num_vars = 0;
-    let mut variables = 0;
-    let mut clauses = 0;
-    let mut num_clauses = 0;
+    let options = options.into();
+    if options.mode == ParseMode::Lenient {
+        // Skipping lines takes the line-based parser.
+        return read_dimacs_streaming(input.as_bytes(), options, dim);
+    }
+    let mut validator = Validator::new(options);
+    let lexed = match validator.options.engine {
+        ParseEngine::Auto => lexer::lex(input),
+        #[cfg(feature = "parallel")]
+        ParseEngine::Parallel => lexer::lex_parallel(input),
+        ParseEngine::Grammar => None,
+    };
+    if let Some(lexed) = lexed {
+        lexer::replay(input, lexed, &mut validator, dim)?;
+        return validator.finish();
+    }
     let pairs = DIMACSParser::parse(Rule::file, input)?;
     for pair in pairs {
         for inner_pair in pair.into_inner() {
             match inner_pair.as_rule() {
-                Rule::clause => {
+                rule @ (Rule::clause | Rule::xor | Rule::unterminated) => {
+                    let at = position(&inner_pair, Some(validator.num_clauses as usize));
+                    if rule == Rule::unterminated && !validator.options.allow_missing_terminator {
+                        let error =
+                            ParserError::InvalidLine(at.line, "clause is not terminated by 0");
+                        return Err(error.at(at));
+                    }
                     let mut clause = Vec::<i32>::new();
                     for lit_pair in inner_pair.into_inner() {
-                        let lit = lit_pair.as_str().parse::<i32>()?;
-                        let abs = lit.abs();
-                        num_vars = max(abs, num_vars);
-                        clause.push(lit);
-                    }
-                    num_clauses += 1;
-                    dim.push_clause(clause)?;
-                    if strict {
-                        if clauses > 0 && num_clauses >= clauses {
-                            return Err(ParserError::TooManyClauses(num_clauses, clauses));
+                        // A comment line can split a clause.
+                        if lit_pair.as_rule() == Rule::COMMENT {
+                            deliver_comment(&lit_pair.as_str()[1..], dim);
+                            continue;
                         }
-                        if num_vars > 0 && num_vars >= variables {
-                            return Err(ParserError::TooManyVariables(num_vars, variables));
+                        let lit_at = position(&lit_pair, at.clause);
+                        let lit = lit_pair
+                            .as_str()
+                            .parse::<i32>()
+                            .map_err(|err| ParserError::from(err).at(lit_at))?;
+                        if lit == i32::MIN {
+                            return Err(ParserError::InvalidLiteral(lit).at(lit_at));
                         }
+                        clause.push(lit);
+                    }
+                    validator
+                        .check_clause(&clause, at.line)
+                        .map_err(|err| err.at(at))?;
+                    if rule == Rule::xor {
+                        dim.push_xor(clause).map_err(|err| err.at(at))?;
+                    } else {
+                        dim.push_clause(clause).map_err(|err| err.at(at))?;
                     }
                 }
                 Rule::def => {
+                    let at = position(&inner_pair, None);
+                    let mut variables = 0;
+                    let mut clauses = 0;
+                    let mut comments = Vec::new();
                     for def_rule in inner_pair.into_inner() {
+                        let count = def_rule.as_str().parse::<i32>();
                         match def_rule.as_rule() {
                             Rule::variables => {
-                                variables = def_rule.as_str().parse::<i32>()?;
+                                variables = count.map_err(|err| ParserError::from(err).at(at))?;
                             }
                             Rule::clauses => {
-                                clauses = def_rule
-                                    .as_str()
-                                    .parse::<i32>()
-                                    .map(|o| o.try_into().unwrap())?;
+                                clauses = count.map_err(|err| ParserError::from(err).at(at))?;
                             }
+                            Rule::COMMENT => comments.push(def_rule.as_str()),
                             _ => {}
                         }
                     }
+                    validator.header = Some((variables, clauses));
+                    dim.set_header(variables as usize, clauses as usize);
+                    // Comments right below the header come after it.
+                    for comment in comments {
+                        deliver_comment(&comment[1..], dim);
+                    }
                 }
+                Rule::COMMENT => deliver_comment(&inner_pair.as_str()[1..], dim),
                 _ => {}
             };
         }
     }
-    Ok(())
+    validator.finish()
 }
 
 /// Reads a DIMACS CNF file from a given path or standard input and parses it`.
 pub fn read_dimacs_from_file<P: AsRef<Path>, D: AsDimacs>(
     path: P,
-    strict: bool,
+    options: impl Into<ParserOptions>,
     dim: &mut D,
 ) -> Result<(), ParserError> {
     let mut reader = File::open(path)?;
-    read_dimacs_from_reader(&mut reader, strict, dim)
+    read_dimacs_from_reader(&mut reader, options, dim)
 }
 /// Reads a DIMACS CNF file from a given reader and parses it.
 pub fn read_dimacs_from_reader<R: Read, D: AsDimacs>(
     reader: R,
-    strict: bool,
+    options: impl Into<ParserOptions>,
     dim: &mut D,
 ) -> Result<(), ParserError> {
     let mut reader = SmartReader::new(reader)?;
     let mut buf = String::new();
     reader.read_to_string(&mut buf)?;
-    parse_dimacs_cnf(&buf, strict, dim)
+    parse_dimacs_cnf(&buf, options, dim)
 }
 
-enum SmartReader<R: Read> {
+pub(crate) enum SmartReader<R: Read> {
     Plain(BufReader<R>),
     #[cfg(feature = "compression")]
     Gzip(GzDecoder<BufReader<R>>),
     #[cfg(feature = "compression")]
     Xz(XzDecoder<BufReader<R>>),
+    #[cfg(feature = "compression")]
+    Zstd(ZstdDecoder<'static, BufReader<R>>),
+    #[cfg(feature = "compression")]
+    Bzip2(MultiBzDecoder<BufReader<R>>),
 }
 
 impl<R: Read> Read for SmartReader<R> {
@@ -155,6 +394,10 @@ impl<R: Read> Read for SmartReader<R> {
             SmartReader::Gzip(r) => r.read(buf),
             #[cfg(feature = "compression")]
             SmartReader::Xz(r) => r.read(buf),
+            #[cfg(feature = "compression")]
+            SmartReader::Zstd(r) => r.read(buf),
+            #[cfg(feature = "compression")]
+            SmartReader::Bzip2(r) => r.read(buf),
         }
     }
 }
@@ -163,12 +406,19 @@ impl<R: Read> SmartReader<io::Chain<Cursor<Vec<u8>>, R>> {
     pub fn new(reader: R) -> Result<Self, io::Error> {
         let mut reader = reader;
         let mut header = [0u8; 6];
-        let len = reader.read(&mut header)?;
+        let mut len = 0;
+        // A short read must not hide the magic bytes.
+        while len < header.len() {
+            match reader.read(&mut header[len..])? {
+                0 => break,
+                n => len += n,
+            }
+        }
 
         let header_cursor = Cursor::new(header[..len].to_vec());
         let chained_reader = BufReader::new(header_cursor.chain(reader));
 
-        // Gzip file header: 0x1F 0x8B
+        // Magic bytes of xz, gzip, zstd and bzip2.
         match header {
             [0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00] => {
                 let decoder = XzDecoder::new(chained_reader);
@@ -178,6 +428,14 @@ impl<R: Read> SmartReader<io::Chain<Cursor<Vec<u8>>, R>> {
                 let decoder = GzDecoder::new(chained_reader);
                 Ok(Self::Gzip(decoder))
             }
+            [0x28, 0xB5, 0x2F, 0xFD, ..] => {
+                let decoder = ZstdDecoder::with_buffer(chained_reader)?;
+                Ok(Self::Zstd(decoder))
+            }
+            [b'B', b'Z', b'h', ..] => {
+                let decoder = MultiBzDecoder::new(chained_reader);
+                Ok(Self::Bzip2(decoder))
+            }
             _ => Ok(Self::Plain(chained_reader)),
         }
     }
@@ -0,0 +1,289 @@
+use std::{
+    io::{BufRead, BufReader, Read},
+    mem,
+};
+
+use crate::{
+    errors::{ParserError, Position},
+    parser::{AsDimacs, ParseMode},
+};
+
+use super::{deliver_comment, ParserOptions, SmartReader, Validator};
+
+/// Reads a DIMACS CNF file from a given reader, feeding each clause to `dim` as soon as
+/// it is complete.
+///
+/// Unlike [`read_dimacs_from_reader`](super::read_dimacs_from_reader), the input is never
+/// held in memory as a whole: memory use is bounded by the longest line and the longest
+/// clause. Comment lines are passed to `AsDimacs::add_comment` without the leading `c`
+/// (and `c ind` lines to `AsDimacs::add_independent_support` as well), and `x` lines
+/// to `AsDimacs::push_xor`; an XOR clause must fit on one line.
+/// Compressed input is detected automatically when the `compression` feature is enabled.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::{read_dimacs_streaming, ParseMode};
+/// let input = "c streamed\np cnf 3 2\n1 -3 0\n2 3\n0\n";
+/// let mut cnf = Vec::new();
+/// read_dimacs_streaming(input.as_bytes(), ParseMode::Strict, &mut cnf).unwrap();
+/// assert_eq!(cnf, vec![vec![1, -3], vec![2, 3]]);
+/// ```
+pub fn read_dimacs_streaming<R: Read, D: AsDimacs>(
+    reader: R,
+    options: impl Into<ParserOptions>,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let mut validator = Validator::new(options.into());
+    let mut reader = BufReader::new(SmartReader::new(reader)?);
+    let mut line = String::new();
+    let mut line_no = 0;
+    // The byte offset of `line` in the input.
+    let mut offset = 0;
+    let mut clause = Vec::new();
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 {
+            break;
+        }
+        line_no += 1;
+        let mut column = 0;
+        let header = line.trim_start().starts_with('p');
+        let locate = |err: ParserError, column: usize, validator: &Validator| {
+            err.at(Position {
+                offset: offset + column,
+                line: line_no,
+                column: line[..column].chars().count() + 1,
+                clause: (!header).then_some(validator.num_clauses as usize),
+            })
+        };
+        if validator.options.mode == ParseMode::Lenient {
+            let mut buffer = LineBuffer::new(&validator, &clause);
+            let result = stream_line(
+                &line,
+                line_no,
+                &mut column,
+                &mut validator,
+                &mut clause,
+                &mut buffer,
+            )
+            .map_err(|err| locate(err, column, &validator));
+            buffer
+                .finish(result, &mut validator, &mut clause, dim)
+                .map_err(|err| locate(err, column, &validator))?;
+        } else {
+            stream_line(
+                &line,
+                line_no,
+                &mut column,
+                &mut validator,
+                &mut clause,
+                dim,
+            )
+            .map_err(|err| locate(err, column, &validator))?;
+        }
+        offset += read;
+    }
+    let at = Position {
+        offset,
+        line: line_no,
+        column: 1,
+        clause: Some(validator.num_clauses as usize),
+    };
+    push_unterminated(&mut validator, clause, at, dim)?;
+    validator.finish()
+}
+
+/// Push the clause left open at the end of the input, if any, which is at `at`.
+pub(super) fn push_unterminated<D: AsDimacs>(
+    validator: &mut Validator,
+    clause: Vec<i32>,
+    at: Position,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    if clause.is_empty() {
+        return Ok(());
+    }
+    let result = if validator.options.allow_missing_terminator {
+        validator.check_clause(&clause, at.line)
+    } else {
+        Err(ParserError::InvalidLine(
+            at.line,
+            "clause is not terminated by 0",
+        ))
+    };
+    match result.map_err(|err| err.at(at)) {
+        Ok(()) => dim.push_clause(clause).map_err(|err| err.at(at)),
+        Err(err) if validator.options.mode == ParseMode::Lenient => {
+            dim.add_diagnostic(&err);
+            Ok(())
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// What a line sends to the sink.
+enum Item {
+    Clause(Vec<i32>),
+    Xor(Vec<i32>),
+    Comment(String),
+    Header(usize, usize),
+    IndependentSupport(Vec<i32>),
+}
+
+/// Holds back the output of a line in lenient mode until the line is read, so that a
+/// malformed line can be undone as a whole.
+pub(super) struct LineBuffer {
+    items: Vec<Item>,
+    /// The parser state before the line.
+    validator: Validator,
+    clause: Vec<i32>,
+}
+
+impl LineBuffer {
+    pub(super) fn new(validator: &Validator, clause: &[i32]) -> Self {
+        Self {
+            items: Vec::new(),
+            validator: validator.clone(),
+            clause: clause.to_vec(),
+        }
+    }
+
+    /// Send the line to `dim` if `result` is `Ok`; otherwise restore the parser state
+    /// and report the error as a diagnostic.
+    pub(super) fn finish<D: AsDimacs>(
+        self,
+        result: Result<(), ParserError>,
+        validator: &mut Validator,
+        clause: &mut Vec<i32>,
+        dim: &mut D,
+    ) -> Result<(), ParserError> {
+        if let Err(err) = result {
+            *validator = self.validator;
+            *clause = self.clause;
+            dim.add_diagnostic(&err);
+            return Ok(());
+        }
+        for item in self.items {
+            match item {
+                Item::Clause(clause) => dim.push_clause(clause)?,
+                Item::Xor(lits) => dim.push_xor(lits)?,
+                Item::Comment(comment) => dim.add_comment(comment),
+                Item::Header(num_vars, num_clauses) => dim.set_header(num_vars, num_clauses),
+                Item::IndependentSupport(vars) => dim.add_independent_support(vars),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl AsDimacs for LineBuffer {
+    fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
+        self.items.push(Item::Clause(clause));
+        Ok(())
+    }
+    fn push_xor(&mut self, lits: Vec<i32>) -> Result<(), ParserError> {
+        self.items.push(Item::Xor(lits));
+        Ok(())
+    }
+    fn add_comment(&mut self, comment: String) {
+        self.items.push(Item::Comment(comment));
+    }
+    fn set_header(&mut self, num_vars: usize, num_clauses: usize) {
+        self.items.push(Item::Header(num_vars, num_clauses));
+    }
+    fn add_independent_support(&mut self, vars: Vec<i32>) {
+        self.items.push(Item::IndependentSupport(vars));
+    }
+}
+
+/// Read one line into `dim`, leaving an unterminated clause in `clause`.
+///
+/// On error, `column` is the byte offset in `line` of the token that failed.
+fn stream_line<D: AsDimacs>(
+    line: &str,
+    line_no: usize,
+    column: &mut usize,
+    validator: &mut Validator,
+    clause: &mut Vec<i32>,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let offset_of = |token: &str| token.as_ptr() as usize - line.as_ptr() as usize;
+    let trimmed = line.trim();
+    *column = offset_of(trimmed);
+    if let Some(comment) = trimmed.strip_prefix('c') {
+        deliver_comment(comment, dim);
+        return Ok(());
+    }
+    if let Some(def) = trimmed.strip_prefix('p') {
+        if validator.header.is_some() || validator.num_clauses > 0 || !clause.is_empty() {
+            return Err(ParserError::InvalidLine(
+                line_no,
+                "unexpected `p cnf` header",
+            ));
+        }
+        let mut fields = def.split_whitespace();
+        if fields.next() != Some("cnf") {
+            return Err(ParserError::InvalidLine(line_no, "expected `p cnf`"));
+        }
+        let (Some(variables), Some(clauses), None) = (fields.next(), fields.next(), fields.next())
+        else {
+            return Err(ParserError::InvalidLine(
+                line_no,
+                "malformed `p cnf` header",
+            ));
+        };
+        *column = offset_of(variables);
+        let variables = variables.parse::<i32>()?;
+        *column = offset_of(clauses);
+        let clauses = clauses.parse::<i32>()?;
+        dim.set_header(variables as usize, clauses as usize);
+        validator.header = Some((variables, clauses));
+        return Ok(());
+    }
+    if let Some(xor) = trimmed.strip_prefix('x') {
+        if !clause.is_empty() {
+            return Err(ParserError::InvalidLine(
+                line_no,
+                "unterminated clause before XOR",
+            ));
+        }
+        let mut lits = Vec::new();
+        let mut tokens = xor.split_whitespace();
+        loop {
+            let Some(token) = tokens.next() else {
+                return Err(ParserError::InvalidLine(
+                    line_no,
+                    "XOR clause is not terminated by 0",
+                ));
+            };
+            *column = offset_of(token);
+            match token.parse::<i32>()? {
+                0 => break,
+                i32::MIN => return Err(ParserError::InvalidLiteral(i32::MIN)),
+                lit => lits.push(lit),
+            }
+        }
+        if lits.is_empty() || tokens.next().is_some() {
+            *column = offset_of(trimmed);
+            return Err(ParserError::InvalidLine(line_no, "malformed XOR clause"));
+        }
+        validator.check_clause(&lits, line_no)?;
+        return dim.push_xor(lits);
+    }
+    for token in trimmed.split_whitespace() {
+        *column = offset_of(token);
+        let lit = token.parse::<i32>()?;
+        if lit != 0 {
+            if lit == i32::MIN {
+                return Err(ParserError::InvalidLiteral(lit));
+            }
+            clause.push(lit);
+            // Checked as the clause grows, so an endless clause cannot exhaust memory.
+            validator.check_len(clause.len(), line_no)?;
+            continue;
+        }
+        validator.check_clause(clause, line_no)?;
+        dim.push_clause(mem::take(clause))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+#[cfg(feature = "compression")]
+use bzip2::write::BzEncoder;
+#[cfg(feature = "compression")]
+use flate2::{write::GzEncoder, Compression};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+#[cfg(feature = "compression")]
+use xz2::write::XzEncoder;
+#[cfg(feature = "compression")]
+use zstd::stream::write::Encoder as ZstdEncoder;
+
+/// Writes clauses in DIMACS CNF format, preceded by comments and the `p cnf` header.
+///
+/// # Arguments
+///
+/// * `writer` - The destination of the DIMACS text.
+/// * `num_vars` - The number of variables declared in the header.
+/// * `clauses` - The clauses to write, one per line.
+/// * `comments` - Comments written before the header; see [`write_comment`].
+pub fn write_dimacs<W: Write>(
+    mut writer: W,
+    num_vars: usize,
+    clauses: &[Vec<i32>],
+    comments: &[String],
+) -> io::Result<()> {
+    for comment in comments {
+        write_comment(&mut writer, comment)?;
+    }
+    writeln!(writer, "p cnf {} {}", num_vars, clauses.len())?;
+    for clause in clauses {
+        for lit in clause {
+            write!(writer, "{} ", lit)?;
+        }
+        writeln!(writer, "0")?;
+    }
+    writer.flush()
+}
+
+/// Writes a comment as one or more `c` lines.
+///
+/// Each physical line of `comment` gets its own `c ` prefix, so embedded newlines
+/// cannot break the DIMACS structure. Other control characters (except tabs) are
+/// escaped, e.g. `\x07`.
+pub fn write_comment<W: Write>(mut writer: W, comment: &str) -> io::Result<()> {
+    for line in comment.lines() {
+        let mut escaped = String::with_capacity(line.len());
+        for c in line.chars() {
+            match c {
+                '\t' => escaped.push(c),
+                c if c.is_ascii_control() => escaped.push_str(&format!("\\x{:02x}", c as u8)),
+                c if c.is_control() => escaped.extend(c.escape_unicode()),
+                c => escaped.push(c),
+            }
+        }
+        if escaped.is_empty() {
+            writeln!(writer, "c")?;
+        } else {
+            writeln!(writer, "c {}", escaped)?;
+        }
+    }
+    Ok(())
+}
+
+/// Writes clauses in DIMACS CNF format to the file at `path`, see [`write_dimacs`].
+///
+/// With the `compression` feature enabled, the output is compressed by the extension of
+/// `path`, see [`SmartWriter`].
+pub fn write_dimacs_to_file<P: AsRef<Path>>(
+    path: P,
+    num_vars: usize,
+    clauses: &[Vec<i32>],
+    comments: &[String],
+) -> io::Result<()> {
+    let mut writer = SmartWriter::create(path.as_ref())?;
+    write_dimacs(&mut writer, num_vars, clauses, comments)?;
+    writer.finish()
+}
+
+/// A buffered file writer that compresses by file extension, the write-side counterpart
+/// of the input detection of the readers.
+///
+/// With the `compression` feature enabled, `.gz`, `.xz`, `.zst` (or `.zstd`) and `.bz2`
+/// select gzip, xz, zstd and bzip2 output; any other path is written as plain text.
+/// Call [`finish`](SmartWriter::finish) once done, since compressed formats end with a
+/// trailer that dropping the writer does not write.
+/// # Example
+/// ```rust
+/// use std::io::Write;
+/// use satgalaxy::parser::{write_dimacs, SmartWriter};
+/// let path = std::env::temp_dir().join("satgalaxy_smart_writer.cnf");
+/// let mut writer = SmartWriter::create(&path).unwrap();
+/// write_dimacs(&mut writer, 2, &[vec![1, -2]], &[]).unwrap();
+/// writer.finish().unwrap();
+/// assert_eq!(std::fs::read_to_string(&path).unwrap(), "p cnf 2 1\n1 -2 0\n");
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub enum SmartWriter {
+    Plain(BufWriter<File>),
+    #[cfg(feature = "compression")]
+    Gzip(GzEncoder<BufWriter<File>>),
+    #[cfg(feature = "compression")]
+    Xz(XzEncoder<BufWriter<File>>),
+    #[cfg(feature = "compression")]
+    Zstd(ZstdEncoder<'static, BufWriter<File>>),
+    #[cfg(feature = "compression")]
+    Bzip2(BzEncoder<BufWriter<File>>),
+}
+
+impl SmartWriter {
+    /// Creates the file at `path`, truncating it if it exists.
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        let file = BufWriter::new(File::create(path)?);
+        #[cfg(feature = "compression")]
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => return Ok(Self::Gzip(GzEncoder::new(file, Compression::default()))),
+            Some("xz") => return Ok(Self::Xz(XzEncoder::new(file, 6))),
+            Some("zst" | "zstd") => return Ok(Self::Zstd(ZstdEncoder::new(file, 0)?)),
+            Some("bz2") => {
+                let encoder = BzEncoder::new(file, bzip2::Compression::default());
+                return Ok(Self::Bzip2(encoder));
+            }
+            _ => {}
+        }
+        Ok(Self::Plain(file))
+    }
+
+    /// Whether `create` compresses output to `path`.
+    pub fn compresses<P: AsRef<Path>>(path: P) -> bool {
+        let ext = path.as_ref().extension().and_then(|ext| ext.to_str());
+        cfg!(feature = "compression") && matches!(ext, Some("gz" | "xz" | "zst" | "zstd" | "bz2"))
+    }
+
+    /// Writes the trailer of compressed output and flushes the file.
+    pub fn finish(self) -> io::Result<()> {
+        match self {
+            SmartWriter::Plain(mut w) => w.flush(),
+            #[cfg(feature = "compression")]
+            SmartWriter::Gzip(w) => w.finish()?.flush(),
+            #[cfg(feature = "compression")]
+            SmartWriter::Xz(w) => w.finish()?.flush(),
+            #[cfg(feature = "compression")]
+            SmartWriter::Zstd(w) => w.finish()?.flush(),
+            #[cfg(feature = "compression")]
+            SmartWriter::Bzip2(w) => w.finish()?.flush(),
+        }
+    }
+}
+
+impl Write for SmartWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            SmartWriter::Plain(w) => w.write(buf),
+            #[cfg(feature = "compression")]
+            SmartWriter::Gzip(w) => w.write(buf),
+            #[cfg(feature = "compression")]
+            SmartWriter::Xz(w) => w.write(buf),
+            #[cfg(feature = "compression")]
+            SmartWriter::Zstd(w) => w.write(buf),
+            #[cfg(feature = "compression")]
+            SmartWriter::Bzip2(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            SmartWriter::Plain(w) => w.flush(),
+            #[cfg(feature = "compression")]
+            SmartWriter::Gzip(w) => w.flush(),
+            #[cfg(feature = "compression")]
+            SmartWriter::Xz(w) => w.flush(),
+            #[cfg(feature = "compression")]
+            SmartWriter::Zstd(w) => w.flush(),
+            #[cfg(feature = "compression")]
+            SmartWriter::Bzip2(w) => w.flush(),
+        }
+    }
+}
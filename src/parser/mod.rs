@@ -1,18 +1,74 @@
 mod dimacs;
+mod opb;
+mod qdimacs;
+mod wcnf;
 pub use dimacs::parse_dimacs_cnf;
 pub use dimacs::read_dimacs_from_file;
 pub use dimacs::read_dimacs_from_reader;
+pub use dimacs::read_dimacs_streaming;
 pub(crate) use dimacs::Rule;
+#[cfg(feature = "mmap")]
+pub use dimacs::{parse_dimacs_bytes, read_dimacs_mmap};
+pub use dimacs::{parse_inccnf, read_inccnf_from_file, read_inccnf_from_reader};
+pub use dimacs::{write_comment, write_dimacs, write_dimacs_to_file, SmartWriter};
+pub use dimacs::{ParseEngine, ParserOptions};
+pub(crate) use opb::Rule as OpbRule;
+pub use opb::{
+    parse_opb, read_opb_from_file, read_opb_from_reader, AsPseudoBoolean, PbConstraint, PbOperator,
+    PbProblem,
+};
+pub use qdimacs::{
+    parse_qdimacs, read_qdimacs_from_file, read_qdimacs_from_reader, AsQdimacs, QdimacsProblem,
+    Quantifier,
+};
+pub use wcnf::{parse_wcnf, read_wcnf_from_file, read_wcnf_from_reader, AsWcnf, WcnfProblem};
 
-use crate::errors::ParserError;
-use crate::solver::SatSolver;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::errors::{ParserError, SolverError};
+use crate::solver::{SatSolver, SatStatus};
+
+/// How the DIMACS parser treats the `p cnf` header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Require the `p cnf` header and enforce the declared counts.
+    Strict,
+    /// Accept files with or without a header (the 2022 competition format drops it)
+    /// and infer the counts from the clauses read.
+    #[default]
+    Inferred,
+    /// `Inferred`, but a malformed line is skipped and reported to
+    /// `AsDimacs::add_diagnostic` instead of failing the parse, for crunching large
+    /// corpora with the odd broken file. Lines are read as by `read_dimacs_streaming`.
+    Lenient,
+}
+
+impl From<bool> for ParseMode {
+    fn from(strict: bool) -> Self {
+        if strict {
+            Self::Strict
+        } else {
+            Self::Inferred
+        }
+    }
+}
 
 /// A problem to be solved.
 #[cfg(feature = "parser")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Problem {
     pub clauses: Vec<Vec<i32>>,
     pub num_vars: usize,
     pub num_clauses: usize,
+    pub comments: Vec<String>,
+    /// The variable count declared by the `p cnf` header, if any.
+    pub declared_vars: Option<usize>,
+    /// Keep `num_vars` at least the declared count, even if fewer variables are used.
+    pub keep_declared_vars: bool,
+    /// The variables of the `c ind` lines, the projection set of model counters, if any.
+    pub independent_support: Option<Vec<i32>>,
 }
 #[cfg(feature = "parser")]
 impl Default for Problem {
@@ -27,19 +83,163 @@ impl Problem {
             clauses: vec![],
             num_vars: 0,
             num_clauses: 0,
+            comments: vec![],
+            declared_vars: None,
+            keep_declared_vars: false,
+            independent_support: None,
+        }
+    }
+
+    /// An empty problem whose `num_vars` honors the declared header count.
+    ///
+    /// Use this when the solver must reserve every declared variable, e.g. a header of
+    /// `p cnf 100 ...` whose clauses only mention 90 variables.
+    pub fn with_declared_vars() -> Self {
+        Self {
+            keep_declared_vars: true,
+            ..Self::new()
+        }
+    }
+
+    /// Check that `model` satisfies every clause; see [`verify_model`](crate::verify::verify_model).
+    pub fn verify_model(&self, model: &[i32]) -> Result<(), crate::verify::UnsatisfiedClause> {
+        crate::verify::verify_model(&self.clauses, model)
+    }
+
+    /// Writes the problem in DIMACS CNF format, comments first.
+    ///
+    /// Multi-line comments are split into one `c` line per line.
+    pub fn write_dimacs<W: Write>(&self, writer: W) -> io::Result<()> {
+        write_dimacs(writer, self.num_vars, &self.clauses, &self.comments)
+    }
+
+    /// Writes the problem in DIMACS CNF format to a file.
+    ///
+    /// With the `compression` feature enabled, compressed paths are supported, see
+    /// [`SmartWriter`].
+    pub fn write_dimacs_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        write_dimacs_to_file(path, self.num_vars, &self.clauses, &self.comments)
+    }
+
+    /// Reads a DIMACS CNF problem from a reader.
+    ///
+    /// Compressed input is detected automatically when the `compression` feature is enabled.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::parser::{ParseMode, Problem};
+    /// let input = "p cnf 3 2\n1 -3 0\n2 3 0\n";
+    /// let problem = Problem::from_reader(input.as_bytes(), ParseMode::Inferred).unwrap();
+    /// assert_eq!(problem.num_vars, 3);
+    /// assert_eq!(problem.clauses, vec![vec![1, -3], vec![2, 3]]);
+    /// ```
+    pub fn from_reader<R: Read>(reader: R, mode: ParseMode) -> Result<Problem, ParserError> {
+        let mut problem = Problem::new();
+        read_dimacs_from_reader(reader, mode, &mut problem)?;
+        Ok(problem)
+    }
+
+    /// Reads a DIMACS CNF problem from a file.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::parser::{ParseMode, Problem};
+    /// let path = std::env::temp_dir().join("satgalaxy_problem_from_file.cnf");
+    /// std::fs::write(&path, "1 -3 0\n2 3 0\n").unwrap();
+    /// let problem = Problem::from_file(&path, ParseMode::Inferred).unwrap();
+    /// assert_eq!(problem.num_clauses, 2);
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn from_file<P: AsRef<Path>>(path: P, mode: ParseMode) -> Result<Problem, ParserError> {
+        let mut problem = Problem::new();
+        read_dimacs_from_file(path, mode, &mut problem)?;
+        Ok(problem)
+    }
+
+    /// Pushes every clause into `solver`, then reports [`SatSolver::is_ok`].
+    ///
+    /// A `false` result means the formula is already known to be unsatisfiable, so the
+    /// caller can skip the full solve.
+    pub fn load_into<S: SatSolver + ?Sized>(&self, solver: &mut S) -> Result<bool, SolverError> {
+        self.load_into_with_progress(solver, 0, |_| {})
+    }
+
+    /// [`load_into`](Problem::load_into), calling `on_progress` with the number of clauses
+    /// loaded so far after every `every` clauses, and once all are loaded.
+    ///
+    /// With `every` set to 0, `on_progress` is only called at the end.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::parser::{ParseMode, Problem};
+    /// use satgalaxy::solver::CaDiCaLSolver;
+    /// let input = "p cnf 3 5\n1 2 0\n-1 3 0\n2 -3 0\n-2 0\n1 3 0\n";
+    /// let problem = Problem::from_reader(input.as_bytes(), ParseMode::Strict).unwrap();
+    /// let mut progress = vec![];
+    /// let mut solver = CaDiCaLSolver::new();
+    /// let ok = problem
+    ///     .load_into_with_progress(&mut solver, 2, |loaded| progress.push(loaded))
+    ///     .unwrap();
+    /// assert!(ok);
+    /// assert_eq!(progress, vec![2, 4, 5]);
+    /// ```
+    pub fn load_into_with_progress<S: SatSolver + ?Sized>(
+        &self,
+        solver: &mut S,
+        every: usize,
+        mut on_progress: impl FnMut(usize),
+    ) -> Result<bool, SolverError> {
+        for (i, clause) in self.clauses.iter().enumerate() {
+            solver.push_clause(clause)?;
+            let loaded = i + 1;
+            if every > 0 && loaded % every == 0 && loaded < self.clauses.len() {
+                on_progress(loaded);
+            }
+        }
+        on_progress(self.clauses.len());
+        solver.is_ok()
+    }
+
+    /// Loads the problem into a new `S` and solves it.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::parser::{ParseMode, Problem};
+    /// use satgalaxy::solver::{CaDiCaLSolver, SatStatus};
+    /// let input = "p cnf 2 2\n1 2 0\n-1 0\n";
+    /// let problem = Problem::from_reader(input.as_bytes(), ParseMode::Strict).unwrap();
+    /// let status = problem.solve_with::<CaDiCaLSolver>().unwrap();
+    /// assert_eq!(status, SatStatus::Satisfiable(vec![2]));
+    /// ```
+    pub fn solve_with<S: SatSolver + Default>(&self) -> Result<SatStatus, SolverError> {
+        let mut solver = S::default();
+        if !self.load_into(&mut solver)? {
+            return Ok(SatStatus::Unsatisfiable);
         }
+        solver.solve_model()
     }
 }
 
 pub trait AsDimacs {
     /// Adds a clause to the underlying structure.
-    fn push_clause(&mut self, clause: Vec<i32>)->Result<(),ParserError>;
+    fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError>;
     /// Adds a comment line. Implementations can choose to store or ignore comments.
     fn add_comment(&mut self, comment: String);
+    /// Adds the XOR clause `l1 ⊕ ... ⊕ ln = true` of an `x` line (a CryptoMiniSat
+    /// extension). Rejected by default; wrap the sink in
+    /// [`XorCollector`](crate::encoder::xor::XorCollector) to encode XORs to CNF.
+    fn push_xor(&mut self, _lits: Vec<i32>) -> Result<(), ParserError> {
+        Err(SolverError::UnsupportedOperation("XOR clauses").into())
+    }
+    /// Receives the counts declared by the `p cnf` header. Ignored by default.
+    fn set_header(&mut self, _num_vars: usize, _num_clauses: usize) {}
+    /// Receives the variables of a `c ind <vars> 0` line, the independent support (or
+    /// projection set) used by model counters. A file may spread it over several lines.
+    /// The line is passed to `add_comment` as well. Ignored by default.
+    fn add_independent_support(&mut self, _vars: Vec<i32>) {}
+    /// Receives the error of a line skipped in [`ParseMode::Lenient`], located with its
+    /// position. Ignored by default.
+    fn add_diagnostic(&mut self, _error: &ParserError) {}
 }
 
 impl<T: SatSolver> AsDimacs for T {
-    fn push_clause(&mut self, clause: Vec<i32>) ->Result<(),ParserError>{
+    fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
         SatSolver::push_clause(self, &clause)?;
         Ok(())
     }
@@ -47,7 +247,7 @@ impl<T: SatSolver> AsDimacs for T {
 }
 
 impl AsDimacs for Vec<Vec<i32>> {
-    fn push_clause(&mut self, clause: Vec<i32>)->Result<(),ParserError> {
+    fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
         self.push(clause);
         Ok(())
     }
@@ -57,15 +257,72 @@ impl AsDimacs for Vec<Vec<i32>> {
     }
 }
 
+/// An `AsDimacs` sink that forwards every clause and comment to two inner sinks.
+///
+/// Useful to load a solver and build a [`Problem`] in a single parse pass.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::{parse_dimacs_cnf, ParseMode, Problem, Tee};
+/// let mut tee = Tee(Problem::new(), Vec::new());
+/// parse_dimacs_cnf("p cnf 2 1\n1 -2 0\n", ParseMode::Inferred, &mut tee).unwrap();
+/// let Tee(problem, clauses) = tee;
+/// assert_eq!(problem.clauses, clauses);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Tee<A, B>(pub A, pub B);
+
+impl<A: AsDimacs, B: AsDimacs> AsDimacs for Tee<A, B> {
+    fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
+        self.0.push_clause(clause.clone())?;
+        self.1.push_clause(clause)
+    }
+    fn push_xor(&mut self, lits: Vec<i32>) -> Result<(), ParserError> {
+        self.0.push_xor(lits.clone())?;
+        self.1.push_xor(lits)
+    }
+    fn add_comment(&mut self, comment: String) {
+        self.0.add_comment(comment.clone());
+        self.1.add_comment(comment);
+    }
+    fn set_header(&mut self, num_vars: usize, num_clauses: usize) {
+        self.0.set_header(num_vars, num_clauses);
+        self.1.set_header(num_vars, num_clauses);
+    }
+    fn add_independent_support(&mut self, vars: Vec<i32>) {
+        self.0.add_independent_support(vars.clone());
+        self.1.add_independent_support(vars);
+    }
+    fn add_diagnostic(&mut self, error: &ParserError) {
+        self.0.add_diagnostic(error);
+        self.1.add_diagnostic(error);
+    }
+}
+
 impl AsDimacs for Problem {
-    fn push_clause(&mut self, clause: Vec<i32>) ->Result<(),ParserError> {
-        let max = clause.iter().map(|v| v.abs()).max().unwrap_or(0);
+    fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
+        if let Some(&lit) = clause.iter().find(|&&v| v == i32::MIN) {
+            return Err(ParserError::InvalidLiteral(lit));
+        }
+        let max = clause.iter().map(|v| v.unsigned_abs()).max().unwrap_or(0);
         self.num_vars = self.num_vars.max(max as usize);
         self.clauses.push(clause);
         self.num_clauses += 1;
         Ok(())
     }
-    fn add_comment(&mut self, _comment: String) {}
+    fn add_comment(&mut self, comment: String) {
+        self.comments.push(comment);
+    }
+    fn set_header(&mut self, num_vars: usize, _num_clauses: usize) {
+        self.declared_vars = Some(num_vars);
+        if self.keep_declared_vars {
+            self.num_vars = self.num_vars.max(num_vars);
+        }
+    }
+    fn add_independent_support(&mut self, vars: Vec<i32>) {
+        self.independent_support
+            .get_or_insert_with(Vec::new)
+            .extend(vars);
+    }
 }
 
 #[cfg(test)]
@@ -73,13 +330,12 @@ mod tests {
     use super::*;
     #[test]
     fn dimacs() {
-
         let dimacs_content = "c This is a comment
         p cnf 3 2
         1 -3 0
         ";
-        let mut cnf=Vec::new();
-        match parse_dimacs_cnf(dimacs_content, false,&mut cnf) {
+        let mut cnf = Vec::new();
+        match parse_dimacs_cnf(dimacs_content, false, &mut cnf) {
             Ok(_) => {
                 assert_eq!(cnf.len(), 1);
             }
@@ -88,12 +344,264 @@ mod tests {
     }
     #[test]
     fn dimacs_strict() {
-
         let dimacs_content = "c This is a comment
         p cnf 2 2
         1 -3 0
         ";
-        let mut cnf=Vec::new();
-        assert!(matches!(parse_dimacs_cnf(dimacs_content, true,&mut cnf), Err(_)));
+        let mut cnf = Vec::new();
+        assert!(matches!(
+            parse_dimacs_cnf(dimacs_content, true, &mut cnf),
+            Err(_)
+        ));
+    }
+    #[test]
+    fn strict_uses_declared_maximum() {
+        let dimacs_content = "p cnf 3 2\n1 -3 0\n2 3 0\n";
+        let mut parsed = Vec::new();
+        parse_dimacs_cnf(dimacs_content, ParseMode::Strict, &mut parsed).unwrap();
+        let mut streamed = Vec::new();
+        read_dimacs_streaming(dimacs_content.as_bytes(), ParseMode::Strict, &mut streamed).unwrap();
+        assert_eq!(parsed, streamed);
+        assert!(matches!(
+            parse_dimacs_cnf("p cnf 3 1\n1 0\n2 0\n", ParseMode::Strict, &mut parsed)
+                .map_err(ParserError::into_inner),
+            Err(ParserError::TooManyClauses(2, 1))
+        ));
+    }
+    #[test]
+    fn parser_options() {
+        let options = ParserOptions {
+            max_vars: Some(3),
+            allow_empty_clauses: true,
+            ..ParserOptions::default()
+        };
+        let mut parsed = Vec::new();
+        parse_dimacs_cnf("1 2 0\n0\n", options, &mut parsed).unwrap();
+        let mut streamed = Vec::new();
+        read_dimacs_streaming("1 2 0\n0\n".as_bytes(), options, &mut streamed).unwrap();
+        assert_eq!(parsed, vec![vec![1, 2], vec![]]);
+        assert_eq!(parsed, streamed);
+        assert!(matches!(
+            read_dimacs_streaming("1 4 0\n".as_bytes(), options, &mut streamed)
+                .map_err(ParserError::into_inner),
+            Err(ParserError::TooManyVariables(4, 3))
+        ));
+        assert!(parse_dimacs_cnf("0\n", ParseMode::Inferred, &mut parsed).is_err());
+        assert!(parse_dimacs_cnf("1 2", ParseMode::Inferred, &mut parsed).is_err());
+    }
+    #[test]
+    fn error_positions() {
+        let input = "p cnf 3 2\n1 -3 0\n2 3 0 -1 0\n2 0\n";
+        let parsed = parse_dimacs_cnf(input, ParseMode::Strict, &mut Vec::new()).unwrap_err();
+        let streamed = read_dimacs_streaming(input.as_bytes(), ParseMode::Strict, &mut Vec::new())
+            .unwrap_err();
+        for error in [parsed, streamed] {
+            let position = error.position().unwrap();
+            assert_eq!((position.line, position.clause), (3, Some(2)));
+            assert!(error
+                .render_snippet(input)
+                .unwrap()
+                .contains("  3 | 2 3 0 -1 0\n"));
+            assert!(matches!(
+                error.into_inner(),
+                ParserError::TooManyClauses(3, 2)
+            ));
+        }
+        let grammar = parse_dimacs_cnf("1 ? 0\n", false, &mut Vec::new()).unwrap_err();
+        assert_eq!(grammar.position().map(|p| (p.line, p.column)), Some((1, 3)));
+    }
+    #[test]
+    fn dimacs_without_header() {
+        let dimacs_content = "c no header
+1 -3 0
+2 3 -4 0
+";
+        let mut problem = Problem::new();
+        parse_dimacs_cnf(dimacs_content, ParseMode::Inferred, &mut problem).unwrap();
+        assert_eq!(problem.num_vars, 4);
+        assert_eq!(problem.num_clauses, 2);
+        let mut cnf = Vec::new();
+        assert!(matches!(
+            parse_dimacs_cnf(dimacs_content, ParseMode::Strict, &mut cnf)
+                .map_err(ParserError::into_inner),
+            Err(ParserError::MissingHeader)
+        ));
+    }
+    #[test]
+    fn declared_vars() {
+        let dimacs_content = "p cnf 100 2\n1 -90 0\n2 0\n";
+        let mut problem = Problem::with_declared_vars();
+        parse_dimacs_cnf(dimacs_content, ParseMode::Inferred, &mut problem).unwrap();
+        assert_eq!(problem.num_vars, 100);
+        let mut problem = Problem::new();
+        parse_dimacs_cnf(dimacs_content, ParseMode::Inferred, &mut problem).unwrap();
+        assert_eq!(problem.num_vars, 90);
+        assert_eq!(problem.declared_vars, Some(100));
+    }
+    #[test]
+    fn write_dimacs_to_file() {
+        let mut problem = Problem::new();
+        AsDimacs::push_clause(&mut problem, vec![1, -2]).unwrap();
+        AsDimacs::push_clause(&mut problem, vec![3]).unwrap();
+        let exts: &[&str] = if cfg!(feature = "compression") {
+            &["cnf", "cnf.gz", "cnf.xz", "cnf.zst", "cnf.bz2"]
+        } else {
+            &["cnf"]
+        };
+        for ext in exts {
+            let path = std::env::temp_dir().join(format!("satgalaxy_write_dimacs.{ext}"));
+            problem.write_dimacs_to_file(&path).unwrap();
+            assert_eq!(SmartWriter::compresses(&path), *ext != "cnf");
+            let read = Problem::from_file(&path, ParseMode::Inferred).unwrap();
+            let mut streamed = Problem::new();
+            let file = std::fs::File::open(&path).unwrap();
+            read_dimacs_streaming(file, ParseMode::Strict, &mut streamed).unwrap();
+            std::fs::remove_file(&path).unwrap();
+            assert_eq!(read.clauses, problem.clauses);
+            assert_eq!(streamed.clauses, problem.clauses);
+        }
+    }
+    #[test]
+    fn streaming_matches_parser() {
+        let dimacs_content = "c header comment
+p cnf 5 4
+1 -3 0
+2 3 -4 0
+c between clauses
+5 0 -1 -2 0
+";
+        let mut parsed = Vec::new();
+        parse_dimacs_cnf(dimacs_content, ParseMode::Inferred, &mut parsed).unwrap();
+        let mut streamed = Problem::new();
+        read_dimacs_streaming(dimacs_content.as_bytes(), ParseMode::Strict, &mut streamed).unwrap();
+        assert_eq!(streamed.clauses, parsed);
+        assert_eq!(streamed.comments, vec!["header comment", "between clauses"]);
+        let mut cnf = Vec::new();
+        assert!(matches!(
+            read_dimacs_streaming("1 2 0\n3".as_bytes(), ParseMode::Inferred, &mut cnf)
+                .map_err(ParserError::into_inner),
+            Err(ParserError::InvalidLine(2, _))
+        ));
+        assert!(matches!(
+            read_dimacs_streaming("p cnf 2 1\n1 3 0\n".as_bytes(), ParseMode::Strict, &mut cnf)
+                .map_err(ParserError::into_inner),
+            Err(ParserError::TooManyVariables(3, 2))
+        ));
+    }
+    #[test]
+    fn comments_and_independent_support() {
+        let dimacs_content = "c t mc
+p cnf 4 2
+c ind 1 2 0
+1 -3 4 0
+c between clauses
+c ind 4 0
+c index
+-2 0
+c end";
+        let mut parsed = Problem::new();
+        parse_dimacs_cnf(dimacs_content, ParseMode::Strict, &mut parsed).unwrap();
+        let mut streamed = Problem::new();
+        read_dimacs_streaming(dimacs_content.as_bytes(), ParseMode::Strict, &mut streamed).unwrap();
+        assert_eq!(parsed, streamed);
+        assert_eq!(
+            parsed.comments,
+            vec![
+                "t mc",
+                "ind 1 2 0",
+                "between clauses",
+                "ind 4 0",
+                "index",
+                "end"
+            ]
+        );
+        assert_eq!(parsed.clauses, vec![vec![1, -3, 4], vec![-2]]);
+        assert_eq!(parsed.independent_support, Some(vec![1, 2, 4]));
+        let plain = Problem::from_reader("1 0\n".as_bytes(), ParseMode::Inferred).unwrap();
+        assert_eq!(plain.independent_support, None);
+    }
+    #[test]
+    fn lenient() {
+        #[derive(Default)]
+        struct Diagnostics(Problem, Vec<(usize, String)>);
+        impl AsDimacs for Diagnostics {
+            fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
+                self.0.push_clause(clause)
+            }
+            fn add_comment(&mut self, comment: String) {
+                self.0.add_comment(comment);
+            }
+            fn add_diagnostic(&mut self, error: &ParserError) {
+                let line = error.position().unwrap().line;
+                self.1.push((line, error.to_string()));
+            }
+        }
+        let dimacs_content = "p cnf 3 4
+1 -2 0 3 x 0
+c kept
+2 3
+-1 0
+p cnf 3 4
+0
+-3 0
+1 2";
+        let mut parsed = Diagnostics::default();
+        parse_dimacs_cnf(dimacs_content, ParseMode::Lenient, &mut parsed).unwrap();
+        let mut streamed = Diagnostics::default();
+        read_dimacs_streaming(dimacs_content.as_bytes(), ParseMode::Lenient, &mut streamed)
+            .unwrap();
+        assert_eq!(parsed.0, streamed.0);
+        assert_eq!(parsed.0.clauses, vec![vec![2, 3, -1], vec![-3]]);
+        assert_eq!(parsed.0.comments, vec!["kept"]);
+        let lines: Vec<usize> = parsed.1.iter().map(|&(line, _)| line).collect();
+        assert_eq!(lines, vec![2, 6, 7, 9]);
+        assert!(parse_dimacs_cnf(dimacs_content, ParseMode::Inferred, &mut Vec::new()).is_err());
+    }
+    #[test]
+    fn tee() {
+        let dimacs_content = "c two sinks
+p cnf 3 3
+1 -3 0
+2 3 -1 0
+-2 0
+";
+        let mut tee = Tee(Problem::new(), Vec::new());
+        parse_dimacs_cnf(dimacs_content, ParseMode::Inferred, &mut tee).unwrap();
+        let Tee(problem, clauses) = tee;
+        assert_eq!(problem.clauses, clauses);
+        assert_eq!(clauses, vec![vec![1, -3], vec![2, 3, -1], vec![-2]]);
+    }
+    #[test]
+    fn write_multiline_comment() {
+        let mut problem = Problem::new();
+        problem.add_comment("generated by\nsatgalaxy\x07".to_string());
+        AsDimacs::push_clause(&mut problem, vec![1, -2]).unwrap();
+        AsDimacs::push_clause(&mut problem, vec![2]).unwrap();
+        let mut out = Vec::new();
+        problem.write_dimacs(&mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(
+            text,
+            "c generated by\nc satgalaxy\\x07\np cnf 2 2\n1 -2 0\n2 0\n"
+        );
+
+        let mut parsed = Problem::new();
+        parse_dimacs_cnf(&text, false, &mut parsed).unwrap();
+        assert_eq!(parsed.clauses, problem.clauses);
+        assert_eq!(parsed.num_vars, problem.num_vars);
+    }
+    #[test]
+    fn problem_min_literal() {
+        let mut problem = Problem::new();
+        assert!(matches!(
+            AsDimacs::push_clause(&mut problem, vec![1, i32::MIN]),
+            Err(ParserError::InvalidLiteral(i32::MIN))
+        ));
+        assert_eq!(problem.num_clauses, 0);
+        let mut cnf = Vec::new();
+        assert!(matches!(
+            parse_dimacs_cnf("1 -2147483648 0\n", false, &mut cnf).map_err(ParserError::into_inner),
+            Err(ParserError::InvalidLiteral(i32::MIN))
+        ));
     }
 }
@@ -0,0 +1,272 @@
+use std::{fs::File, io::Read, path::Path};
+
+use pest::Parser;
+
+use crate::encoder::pb::{pb_at_least, pb_at_most, PbEncoding};
+use crate::encoder::VarManager;
+use crate::errors::ParserError;
+
+use super::{dimacs::SmartReader, AsDimacs};
+
+#[derive(pest_derive::Parser)]
+#[grammar = "../pest/opb.pest"]
+struct OPBParser;
+
+/// The relation of a pseudo-Boolean constraint to its right-hand side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PbOperator {
+    /// `>=`
+    AtLeast,
+    /// `=`
+    Equal,
+    /// `<=`, not part of the competition format but accepted by most tools.
+    AtMost,
+}
+
+/// A sink for pseudo-Boolean (OPB) formulas, the OPB counterpart of `AsDimacs`.
+///
+/// Terms are `(weight, lit)` pairs over DIMACS literals, so `~x3` becomes `-3`.
+pub trait AsPseudoBoolean {
+    /// Adds the constraint `Σ weight * lit <op> rhs`.
+    fn push_constraint(
+        &mut self,
+        terms: Vec<(i64, i32)>,
+        op: PbOperator,
+        rhs: i64,
+    ) -> Result<(), ParserError>;
+    /// Sets the objective function to minimize. Ignored by default.
+    fn set_objective(&mut self, _terms: Vec<(i64, i32)>) -> Result<(), ParserError> {
+        Ok(())
+    }
+    /// Adds a comment line. Ignored by default.
+    fn add_comment(&mut self, _comment: String) {}
+    /// Receives the counts declared by the `#variable= #constraint=` header. Ignored by default.
+    fn set_header(&mut self, _num_vars: usize, _num_constraints: usize) {}
+}
+
+/// A linear pseudo-Boolean constraint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PbConstraint {
+    pub terms: Vec<(i64, i32)>,
+    pub op: PbOperator,
+    pub rhs: i64,
+}
+
+/// A pseudo-Boolean problem, with an optional objective to minimize.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PbProblem {
+    pub constraints: Vec<PbConstraint>,
+    pub objective: Option<Vec<(i64, i32)>>,
+    pub num_vars: usize,
+    pub comments: Vec<String>,
+}
+
+impl PbProblem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn track_vars(&mut self, terms: &[(i64, i32)]) {
+        let max = terms
+            .iter()
+            .map(|(_, lit)| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        self.num_vars = self.num_vars.max(max as usize);
+    }
+
+    /// Encode the constraints to CNF into `dim` with `encoding`; the objective is ignored.
+    ///
+    /// Auxiliary variables are numbered after the problem's variables; the returned
+    /// manager hands out the next free ones.
+    pub fn encode_into<D: AsDimacs>(
+        &self,
+        encoding: PbEncoding,
+        dim: &mut D,
+    ) -> Result<VarManager, ParserError> {
+        let mut vars = VarManager::new(self.num_vars as i32);
+        for constraint in &self.constraints {
+            let terms = &constraint.terms;
+            if constraint.op != PbOperator::AtMost {
+                pb_at_least(terms, constraint.rhs, encoding, &mut vars, dim)?;
+            }
+            if constraint.op != PbOperator::AtLeast {
+                pb_at_most(terms, constraint.rhs, encoding, &mut vars, dim)?;
+            }
+        }
+        Ok(vars)
+    }
+}
+
+impl AsPseudoBoolean for PbProblem {
+    fn push_constraint(
+        &mut self,
+        terms: Vec<(i64, i32)>,
+        op: PbOperator,
+        rhs: i64,
+    ) -> Result<(), ParserError> {
+        self.track_vars(&terms);
+        self.constraints.push(PbConstraint { terms, op, rhs });
+        Ok(())
+    }
+    fn set_objective(&mut self, terms: Vec<(i64, i32)>) -> Result<(), ParserError> {
+        self.track_vars(&terms);
+        self.objective = Some(terms);
+        Ok(())
+    }
+    fn add_comment(&mut self, comment: String) {
+        self.comments.push(comment);
+    }
+    fn set_header(&mut self, num_vars: usize, _num_constraints: usize) {
+        self.num_vars = self.num_vars.max(num_vars);
+    }
+}
+
+/// Parses an OPB string (the pseudo-Boolean competition format) into `pb`.
+///
+/// Only linear constraints are supported. Comments start with `*`; a first comment of
+/// the form `* #variable= N #constraint= M` is passed to `AsPseudoBoolean::set_header`.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::{parse_opb, PbOperator, PbProblem};
+/// let mut problem = PbProblem::new();
+/// let opb = "* #variable= 3 #constraint= 2
+/// min: +1 x1 +2 x2 ;
+/// +1 x1 +1 x2 +1 ~x3 >= 2 ;
+/// +3 x1 -2 x3 = 1 ;
+/// ";
+/// parse_opb(opb, &mut problem).unwrap();
+/// assert_eq!(problem.objective, Some(vec![(1, 1), (2, 2)]));
+/// assert_eq!(problem.constraints[0].terms, vec![(1, 1), (1, 2), (1, -3)]);
+/// assert_eq!(problem.constraints[1].op, PbOperator::Equal);
+/// ```
+pub fn parse_opb<P: AsPseudoBoolean>(input: &str, pb: &mut P) -> Result<(), ParserError> {
+    let file = OPBParser::parse(Rule::file, input)?.next().unwrap();
+    let mut first = true;
+    for pair in file.into_inner() {
+        match pair.as_rule() {
+            Rule::comment => {
+                let comment = pair.as_str()[1..].trim().to_string();
+                if first {
+                    if let Some((vars, constraints)) = parse_header(&comment) {
+                        pb.set_header(vars, constraints);
+                    }
+                }
+                pb.add_comment(comment);
+            }
+            Rule::objective => pb.set_objective(parse_terms(pair.into_inner())?)?,
+            Rule::constraint => {
+                let mut inner = pair.into_inner();
+                let rhs = inner.next_back().unwrap().as_str().parse::<i64>()?;
+                let op = match inner.next_back().unwrap().as_str() {
+                    ">=" => PbOperator::AtLeast,
+                    "<=" => PbOperator::AtMost,
+                    _ => PbOperator::Equal,
+                };
+                pb.push_constraint(parse_terms(inner)?, op, rhs)?;
+            }
+            _ => {}
+        }
+        first = false;
+    }
+    Ok(())
+}
+
+fn parse_terms(pairs: pest::iterators::Pairs<'_, Rule>) -> Result<Vec<(i64, i32)>, ParserError> {
+    pairs
+        .map(|term| {
+            let mut inner = term.into_inner();
+            let weight = inner.next().unwrap().as_str().parse::<i64>()?;
+            let literal = inner.next().unwrap();
+            let negated = literal.as_str().starts_with('~');
+            let var = literal.as_str().trim_start_matches('~')[1..].parse::<i32>()?;
+            if var == 0 {
+                return Err(ParserError::InvalidLiteral(0));
+            }
+            Ok((weight, if negated { -var } else { var }))
+        })
+        .collect()
+}
+
+/// Reads `#variable= N #constraint= M` from a comment.
+fn parse_header(comment: &str) -> Option<(usize, usize)> {
+    let mut words = comment.split_whitespace();
+    let mut vars = None;
+    let mut constraints = None;
+    while let Some(word) = words.next() {
+        match word {
+            "#variable=" => vars = words.next()?.parse().ok(),
+            "#constraint=" => constraints = words.next()?.parse().ok(),
+            _ => {}
+        }
+    }
+    Some((vars?, constraints?))
+}
+
+/// Reads an OPB file from a given path and parses it.
+pub fn read_opb_from_file<P: AsRef<Path>, S: AsPseudoBoolean>(
+    path: P,
+    pb: &mut S,
+) -> Result<(), ParserError> {
+    read_opb_from_reader(File::open(path)?, pb)
+}
+
+/// Reads an OPB file from a given reader and parses it.
+pub fn read_opb_from_reader<R: Read, S: AsPseudoBoolean>(
+    reader: R,
+    pb: &mut S,
+) -> Result<(), ParserError> {
+    let mut reader = SmartReader::new(reader)?;
+    let mut buf = String::new();
+    reader.read_to_string(&mut buf)?;
+    parse_opb(&buf, pb)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::tests::extends;
+
+    #[test]
+    fn header_and_errors() {
+        let mut problem = PbProblem::new();
+        parse_opb(
+            "* #variable= 7 #constraint= 1\n+2 x1 -1 ~x2 <= 1 ;\n* done\n",
+            &mut problem,
+        )
+        .unwrap();
+        assert_eq!(problem.num_vars, 7);
+        assert_eq!(
+            problem.comments,
+            vec!["#variable= 7 #constraint= 1", "done"]
+        );
+        assert_eq!(
+            problem.constraints,
+            vec![PbConstraint {
+                terms: vec![(2, 1), (-1, -2)],
+                op: PbOperator::AtMost,
+                rhs: 1
+            }]
+        );
+        assert!(problem.objective.is_none());
+
+        assert!(parse_opb("+1 x1 >= 1\n", &mut PbProblem::new()).is_err());
+        assert!(parse_opb("+1 x0 >= 1 ;\n", &mut PbProblem::new()).is_err());
+        assert!(parse_opb("+1 x1 x2 >= 1 ;\n", &mut PbProblem::new()).is_err());
+    }
+
+    #[test]
+    fn encode_into() {
+        let mut problem = PbProblem::new();
+        parse_opb("+2 x1 +1 x2 +1 x3 = 2 ;\n+1 x1 >= 1 ;\n", &mut problem).unwrap();
+        let mut cnf: Vec<Vec<i32>> = Vec::new();
+        let vars = problem.encode_into(PbEncoding::Bdd, &mut cnf).unwrap();
+        assert!(vars.max_var() >= 3);
+        assert!(extends(&cnf, &[1, -2, -3]));
+        assert!(!extends(&cnf, &[1, 2, -3]));
+        assert!(!extends(&cnf, &[-1, 2, 3]));
+    }
+}
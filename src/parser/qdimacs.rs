@@ -0,0 +1,256 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    mem,
+    path::Path,
+};
+
+use crate::errors::ParserError;
+
+use super::dimacs::SmartReader;
+
+/// The quantifier of a QDIMACS prefix block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quantifier {
+    /// `e` lines.
+    Exists,
+    /// `a` lines.
+    Forall,
+}
+
+/// A sink for quantified CNF (QDIMACS) formulas, the QDIMACS counterpart of `AsDimacs`.
+pub trait AsQdimacs {
+    /// Adds the next block of the quantifier prefix, outermost first.
+    fn push_quantifier(
+        &mut self,
+        quantifier: Quantifier,
+        vars: Vec<i32>,
+    ) -> Result<(), ParserError>;
+    /// Adds a clause of the matrix.
+    fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError>;
+    /// Adds a comment line. Ignored by default.
+    fn add_comment(&mut self, _comment: String) {}
+    /// Receives the counts declared by the `p cnf` header. Ignored by default.
+    fn set_header(&mut self, _num_vars: usize, _num_clauses: usize) {}
+}
+
+/// A quantified CNF formula: a quantifier prefix and a CNF matrix.
+///
+/// Variables of the matrix that no block binds are free, which QDIMACS reads as
+/// existentially quantified in front of the prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QdimacsProblem {
+    /// The quantifier blocks, outermost first.
+    pub prefix: Vec<(Quantifier, Vec<i32>)>,
+    pub clauses: Vec<Vec<i32>>,
+    pub num_vars: usize,
+    pub comments: Vec<String>,
+}
+
+impl QdimacsProblem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn track_vars(&mut self, lits: &[i32]) {
+        let max = lits.iter().map(|v| v.unsigned_abs()).max().unwrap_or(0);
+        self.num_vars = self.num_vars.max(max as usize);
+    }
+
+    /// The quantifier binding `var`, or `None` if it is free.
+    pub fn quantifier(&self, var: i32) -> Option<Quantifier> {
+        self.prefix
+            .iter()
+            .find(|(_, vars)| vars.contains(&var.abs()))
+            .map(|&(quantifier, _)| quantifier)
+    }
+}
+
+impl AsQdimacs for QdimacsProblem {
+    fn push_quantifier(
+        &mut self,
+        quantifier: Quantifier,
+        vars: Vec<i32>,
+    ) -> Result<(), ParserError> {
+        self.track_vars(&vars);
+        // Consecutive blocks of the same quantifier form one block.
+        match self.prefix.last_mut() {
+            Some((last, block)) if *last == quantifier => block.extend(vars),
+            _ => self.prefix.push((quantifier, vars)),
+        }
+        Ok(())
+    }
+    fn push_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
+        self.track_vars(&clause);
+        self.clauses.push(clause);
+        Ok(())
+    }
+    fn add_comment(&mut self, comment: String) {
+        self.comments.push(comment);
+    }
+    fn set_header(&mut self, num_vars: usize, _num_clauses: usize) {
+        self.num_vars = self.num_vars.max(num_vars);
+    }
+}
+
+/// Parses a QDIMACS string: a `p cnf` header, the quantifier prefix as `e` and `a`
+/// lines terminated by 0, then the clauses of the matrix.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::{parse_qdimacs, QdimacsProblem, Quantifier};
+/// let mut problem = QdimacsProblem::new();
+/// parse_qdimacs("p cnf 3 2\na 1 0\ne 2 3 0\n1 2 0\n-1 3 0\n", &mut problem).unwrap();
+/// assert_eq!(
+///     problem.prefix,
+///     vec![(Quantifier::Forall, vec![1]), (Quantifier::Exists, vec![2, 3])]
+/// );
+/// assert_eq!(problem.clauses, vec![vec![1, 2], vec![-1, 3]]);
+/// ```
+pub fn parse_qdimacs<D: AsQdimacs>(input: &str, dim: &mut D) -> Result<(), ParserError> {
+    read_qdimacs_from_reader(input.as_bytes(), dim)
+}
+
+/// Reads a QDIMACS file from a given path and parses it, see [`parse_qdimacs`].
+pub fn read_qdimacs_from_file<P: AsRef<Path>, D: AsQdimacs>(
+    path: P,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    read_qdimacs_from_reader(File::open(path)?, dim)
+}
+
+/// Reads a QDIMACS file from a given reader and parses it, see [`parse_qdimacs`].
+///
+/// Compressed input is detected automatically when the `compression` feature is enabled.
+pub fn read_qdimacs_from_reader<R: Read, D: AsQdimacs>(
+    reader: R,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let mut reader = BufReader::new(SmartReader::new(reader)?);
+    let mut line = String::new();
+    let mut line_no = 0;
+    let mut seen_header = false;
+    let mut seen_clause = false;
+    let mut clause = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_no += 1;
+        let trimmed = line.trim();
+        if let Some(comment) = trimmed.strip_prefix('c') {
+            dim.add_comment(comment.trim_start().to_string());
+            continue;
+        }
+        if let Some(def) = trimmed.strip_prefix('p') {
+            if seen_header {
+                return Err(ParserError::InvalidLine(
+                    line_no,
+                    "duplicate `p cnf` header",
+                ));
+            }
+            let fields: Vec<&str> = def.split_whitespace().collect();
+            let ["cnf", vars, clauses] = fields[..] else {
+                return Err(ParserError::InvalidLine(
+                    line_no,
+                    "expected `p cnf <vars> <clauses>`",
+                ));
+            };
+            dim.set_header(vars.parse()?, clauses.parse()?);
+            seen_header = true;
+            continue;
+        }
+        let quantifier = match trimmed.chars().next() {
+            Some('e') => Some(Quantifier::Exists),
+            Some('a') => Some(Quantifier::Forall),
+            _ => None,
+        };
+        if let Some(quantifier) = quantifier {
+            if seen_clause || !clause.is_empty() {
+                return Err(ParserError::InvalidLine(
+                    line_no,
+                    "quantifier after the matrix",
+                ));
+            }
+            let mut tokens = trimmed[1..].split_whitespace();
+            let mut vars = Vec::new();
+            loop {
+                match tokens.next().map(str::parse::<i32>).transpose()? {
+                    Some(0) => break,
+                    Some(var) if var > 0 => vars.push(var),
+                    Some(var) => return Err(ParserError::InvalidLiteral(var)),
+                    None => {
+                        return Err(ParserError::InvalidLine(
+                            line_no,
+                            "quantifier block is not terminated by 0",
+                        ))
+                    }
+                }
+            }
+            if tokens.next().is_some() {
+                return Err(ParserError::InvalidLine(
+                    line_no,
+                    "trailing tokens after quantifier block",
+                ));
+            }
+            dim.push_quantifier(quantifier, vars)?;
+            continue;
+        }
+        for token in trimmed.split_whitespace() {
+            let lit = token.parse::<i32>()?;
+            if lit == i32::MIN {
+                return Err(ParserError::InvalidLiteral(lit));
+            }
+            if lit != 0 {
+                clause.push(lit);
+                continue;
+            }
+            dim.push_clause(mem::take(&mut clause))?;
+            seen_clause = true;
+        }
+    }
+    if !clause.is_empty() {
+        return Err(ParserError::InvalidLine(
+            line_no,
+            "clause is not terminated by 0",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefix_and_matrix() {
+        let input = "c forall-exists
+p cnf 4 2
+a 1 2 0
+a 3 0
+e 4 0
+1 -4 0
+3 4
+-2 0
+";
+        let mut problem = QdimacsProblem::new();
+        parse_qdimacs(input, &mut problem).unwrap();
+        assert_eq!(
+            problem.prefix,
+            vec![
+                (Quantifier::Forall, vec![1, 2, 3]),
+                (Quantifier::Exists, vec![4])
+            ]
+        );
+        assert_eq!(problem.clauses, vec![vec![1, -4], vec![3, 4, -2]]);
+        assert_eq!(problem.quantifier(-4), Some(Quantifier::Exists));
+        assert_eq!(problem.comments, vec!["forall-exists"]);
+        assert!(matches!(
+            parse_qdimacs("1 2 0\ne 1 0\n", &mut QdimacsProblem::new()),
+            Err(ParserError::InvalidLine(2, _))
+        ));
+        assert!(parse_qdimacs("a 1 2\n", &mut QdimacsProblem::new()).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, Read},
+    mem,
+    path::Path,
+};
+
+use crate::errors::ParserError;
+
+use super::dimacs::SmartReader;
+
+/// A sink for weighted CNF (MaxSAT) formulas, the WCNF counterpart of `AsDimacs`.
+pub trait AsWcnf {
+    /// Adds a hard clause, which every solution must satisfy.
+    fn push_hard_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError>;
+    /// Adds a soft clause whose violation costs `weight`.
+    fn push_soft_clause(&mut self, weight: u64, clause: Vec<i32>) -> Result<(), ParserError>;
+    /// Adds a comment line. Ignored by default.
+    fn add_comment(&mut self, _comment: String) {}
+}
+
+/// A weighted CNF formula.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WcnfProblem {
+    pub hard_clauses: Vec<Vec<i32>>,
+    pub soft_clauses: Vec<(u64, Vec<i32>)>,
+    pub num_vars: usize,
+    pub comments: Vec<String>,
+}
+
+impl WcnfProblem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn track_vars(&mut self, clause: &[i32]) {
+        let max = clause.iter().map(|v| v.unsigned_abs()).max().unwrap_or(0);
+        self.num_vars = self.num_vars.max(max as usize);
+    }
+}
+
+impl AsWcnf for WcnfProblem {
+    fn push_hard_clause(&mut self, clause: Vec<i32>) -> Result<(), ParserError> {
+        self.track_vars(&clause);
+        self.hard_clauses.push(clause);
+        Ok(())
+    }
+    fn push_soft_clause(&mut self, weight: u64, clause: Vec<i32>) -> Result<(), ParserError> {
+        self.track_vars(&clause);
+        self.soft_clauses.push((weight, clause));
+        Ok(())
+    }
+    fn add_comment(&mut self, comment: String) {
+        self.comments.push(comment);
+    }
+}
+
+/// Parses a WCNF string, in either the classic `p wcnf` format or the 2022 format.
+///
+/// In the classic format every clause starts with its weight, and clauses weighing at
+/// least the `top` value of the header are hard. In the 2022 format there is no header
+/// and hard clauses start with `h`.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::{parse_wcnf, WcnfProblem};
+/// let mut problem = WcnfProblem::new();
+/// parse_wcnf("c 2022 format\nh 1 2 0\n5 -1 0\n3 -2 0\n", &mut problem).unwrap();
+/// assert_eq!(problem.hard_clauses, vec![vec![1, 2]]);
+/// assert_eq!(problem.soft_clauses, vec![(5, vec![-1]), (3, vec![-2])]);
+/// ```
+pub fn parse_wcnf<D: AsWcnf>(input: &str, dim: &mut D) -> Result<(), ParserError> {
+    read_wcnf_from_reader(input.as_bytes(), dim)
+}
+
+/// Reads a WCNF file from a given path and parses it, see [`parse_wcnf`].
+pub fn read_wcnf_from_file<P: AsRef<Path>, D: AsWcnf>(
+    path: P,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    read_wcnf_from_reader(File::open(path)?, dim)
+}
+
+/// Reads a WCNF file from a given reader and parses it, see [`parse_wcnf`].
+///
+/// Clauses are fed to `dim` as they are read. Compressed input is detected
+/// automatically when the `compression` feature is enabled.
+pub fn read_wcnf_from_reader<R: Read, D: AsWcnf>(
+    reader: R,
+    dim: &mut D,
+) -> Result<(), ParserError> {
+    let mut reader = BufReader::new(SmartReader::new(reader)?);
+    let mut line = String::new();
+    let mut line_no = 0;
+    let mut top: Option<u64> = None;
+    let mut seen_clause = false;
+    // `Some(None)` for a hard clause in progress, `Some(Some(w))` for a soft one.
+    let mut weight: Option<Option<u64>> = None;
+    let mut clause = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_no += 1;
+        let trimmed = line.trim();
+        if let Some(comment) = trimmed.strip_prefix('c') {
+            dim.add_comment(comment.trim_start().to_string());
+            continue;
+        }
+        if let Some(def) = trimmed.strip_prefix('p') {
+            if seen_clause || weight.is_some() {
+                return Err(ParserError::InvalidLine(
+                    line_no,
+                    "unexpected `p wcnf` header",
+                ));
+            }
+            let mut fields = def.split_whitespace();
+            if fields.next() != Some("wcnf") {
+                return Err(ParserError::InvalidLine(line_no, "expected `p wcnf`"));
+            }
+            // The variable and clause counts are informative only.
+            let fields: Vec<&str> = fields.collect();
+            match fields[..] {
+                [_, _] => {}
+                [_, _, t] => top = Some(t.parse()?),
+                _ => {
+                    return Err(ParserError::InvalidLine(
+                        line_no,
+                        "malformed `p wcnf` header",
+                    ))
+                }
+            }
+            continue;
+        }
+        for token in trimmed.split_whitespace() {
+            let Some(current) = weight else {
+                weight = Some(match token {
+                    "h" => None,
+                    _ => match token.parse::<u64>()? {
+                        0 => {
+                            return Err(ParserError::InvalidLine(
+                                line_no,
+                                "weight must be positive",
+                            ))
+                        }
+                        w if top.is_some_and(|top| w >= top) => None,
+                        w => Some(w),
+                    },
+                });
+                continue;
+            };
+            let lit = token.parse::<i32>()?;
+            if lit == i32::MIN {
+                return Err(ParserError::InvalidLiteral(lit));
+            }
+            if lit != 0 {
+                clause.push(lit);
+                continue;
+            }
+            let clause = mem::take(&mut clause);
+            match current {
+                None => dim.push_hard_clause(clause)?,
+                Some(w) => dim.push_soft_clause(w, clause)?,
+            }
+            weight = None;
+            seen_clause = true;
+        }
+    }
+    if weight.is_some() {
+        return Err(ParserError::InvalidLine(
+            line_no,
+            "clause is not terminated by 0",
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn classic_format() {
+        let wcnf_content = "c classic
+p wcnf 3 4 10
+10 1 -2 0
+4 -1 0
+12 3 0
+1 2 -3 0
+";
+        let mut problem = WcnfProblem::new();
+        parse_wcnf(wcnf_content, &mut problem).unwrap();
+        assert_eq!(problem.hard_clauses, vec![vec![1, -2], vec![3]]);
+        assert_eq!(problem.soft_clauses, vec![(4, vec![-1]), (1, vec![2, -3])]);
+        assert_eq!(problem.num_vars, 3);
+        assert!(matches!(
+            parse_wcnf("0 1 0\n", &mut WcnfProblem::new()),
+            Err(ParserError::InvalidLine(1, _))
+        ));
+    }
+}
@@ -0,0 +1,37 @@
+//! The `prelude` module re-exports the most commonly used types and traits.
+//!
+//! Trait methods such as `push_clause` and `solve_model` are only callable when the
+//! trait is in scope, so glob-importing the prelude is usually all that is needed:
+//! ```rust
+//! use satgalaxy::prelude::*;
+//!
+//! let mut problem = Problem::new();
+//! parse_dimacs_cnf("p cnf 2 1\n1 -2 0\n", ParseMode::Inferred, &mut problem).unwrap();
+//! assert_eq!(problem.num_clauses, 1);
+//! ```
+pub use crate::errors::SolverError;
+pub use crate::solver::{
+    Backend, Decision, IncrementalSolver, Inprocessing, Lit, MusSolver, MusStatus, RawStatus,
+    SatSolver, SatStatus, Var,
+};
+
+#[cfg(feature = "async")]
+pub use crate::solver::AsyncSolve;
+
+#[cfg(feature = "cadical")]
+pub use crate::solver::CaDiCaLSolver;
+#[cfg(feature = "glucose")]
+pub use crate::solver::GlucoseSolver;
+#[cfg(feature = "minisat")]
+pub use crate::solver::MinisatSolver;
+#[cfg(feature = "picosat")]
+pub use crate::solver::PicoSATSolver;
+
+#[cfg(feature = "parser")]
+pub use crate::errors::ParserError;
+#[cfg(feature = "parser")]
+pub use crate::parser::{
+    parse_dimacs_cnf, read_dimacs_from_file, read_dimacs_from_reader, AsDimacs, ParseMode, Problem,
+};
+#[cfg(feature = "parser")]
+pub use crate::solver::{solve_file, SolveOptions};
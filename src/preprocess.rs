@@ -0,0 +1,258 @@
+//! The `preprocess` module runs a backend's simplification alone and returns the
+//! simplified formula.
+//!
+//! This lets one backend's preprocessing be combined with another backend's search:
+//! [`preprocess`] loads a [`Problem`] into the chosen backend, simplifies it, and
+//! rebuilds the formula from the literals the backend fixed at root level. The
+//! remaining variables are renumbered densely, and [`Preprocessed::reconstruct`] maps a
+//! model of the simplified formula back to the original variables.
+//!
+//! # Usage
+//! The `preprocess` module needs the `parser` feature, which provides `Problem`, and
+//! at least one solver backend:
+//! ```toml
+//! [dependencies]
+//! satgalaxy = { version = "x.y.z", features = ["parser", "cadical"] }
+//! ```
+use std::collections::HashMap;
+
+use crate::errors::SolverError;
+use crate::parser::Problem;
+use crate::solver::Backend;
+#[cfg(feature = "glucose")]
+use crate::solver::GlucoseSolver;
+#[cfg(feature = "minisat")]
+use crate::solver::MinisatSolver;
+#[cfg(feature = "cadical")]
+use crate::solver::{CaDiCaLSolver, Fixedness, RawStatus};
+#[cfg(feature = "picosat")]
+use crate::solver::{Inprocessing, PicoSATSolver, SatSolver};
+
+/// A simplified formula and what is needed to map its models back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Preprocessed {
+    /// The simplified formula over the renumbered variables. It contains an empty
+    /// clause if preprocessing refuted the formula.
+    pub problem: Problem,
+    /// `var_map[v - 1]` is the original variable of the simplified variable `v`.
+    pub var_map: Vec<i32>,
+    /// The literals the backend fixed at root level, over the original variables.
+    pub fixed: Vec<i32>,
+}
+
+impl Preprocessed {
+    /// Map a model of `problem` back to the original variables.
+    ///
+    /// The result lists the true variables in ascending order, like `SatSolver::model`.
+    /// Original variables that neither were fixed nor remain in `problem` only occurred
+    /// in satisfied clauses, so any value works; they are reported as false.
+    pub fn reconstruct(&self, model: &[i32]) -> Vec<i32> {
+        let mut original: Vec<i32> = self.fixed.iter().copied().filter(|&lit| lit > 0).collect();
+        original.extend(
+            model
+                .iter()
+                .filter(|&&lit| lit > 0)
+                .filter_map(|&lit| self.var_map.get(lit as usize - 1).copied()),
+        );
+        original.sort_unstable();
+        original
+    }
+}
+
+/// Simplify `problem` with `backend`, running its preprocessing `rounds` times.
+///
+/// * `CaDiCaL` - `CaDiCaLSolver::simplify`, read back with `fixed_value`.
+/// * `Glucose`, `Minisat` - `eliminate`, read back with the root-level assignment.
+/// * `PicoSAT` - `Inprocessing::simplify`, read back with `deref_toplevel`.
+///
+/// Only root-level units are read back, since `satgalaxy-core` exports neither the
+/// clause databases nor the elimination witnesses. The result therefore has the same
+/// models as `problem` over the variables it keeps.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::{AsDimacs, Problem};
+/// use satgalaxy::preprocess::preprocess;
+/// use satgalaxy::solver::{quick_solve, Backend, SatStatus};
+/// let mut problem = Problem::new();
+/// problem.push_clause(vec![1]).unwrap();
+/// problem.push_clause(vec![-1, 3, 4]).unwrap();
+/// problem.push_clause(vec![1, 2]).unwrap();
+/// let simplified = preprocess(&problem, Backend::CaDiCaL, 1).unwrap();
+/// assert_eq!(simplified.problem.clauses, vec![vec![1, 2]]);
+/// let SatStatus::Satisfiable(model) = quick_solve(&simplified.problem.clauses).unwrap() else {
+///     unreachable!()
+/// };
+/// problem.verify_model(&simplified.reconstruct(&model)).unwrap();
+/// ```
+pub fn preprocess(
+    problem: &Problem,
+    backend: Backend,
+    rounds: usize,
+) -> Result<Preprocessed, SolverError> {
+    let roots = match root_values(problem, backend, rounds)? {
+        Some(roots) => roots,
+        None => {
+            let mut refuted = Problem::new();
+            refuted.clauses.push(vec![]);
+            refuted.num_clauses = 1;
+            return Ok(Preprocessed {
+                problem: refuted,
+                var_map: vec![],
+                fixed: vec![],
+            });
+        }
+    };
+    Ok(compact(problem, &roots))
+}
+
+/// The root-level value of every variable after preprocessing, indexed by `var - 1`,
+/// or `None` if preprocessing refuted the formula.
+fn root_values(
+    problem: &Problem,
+    backend: Backend,
+    rounds: usize,
+) -> Result<Option<Vec<Option<bool>>>, SolverError> {
+    let vars = problem.num_vars as i32;
+    let mut roots: Vec<Option<bool>> = match backend {
+        #[cfg(feature = "cadical")]
+        Backend::CaDiCaL => {
+            let mut solver = CaDiCaLSolver::new();
+            problem.load_into(&mut solver)?;
+            for _ in 0..rounds {
+                if solver.simplify()? == RawStatus::Unsatisfiable {
+                    return Ok(None);
+                }
+            }
+            (1..=solver.vars()?.min(vars))
+                .map(|var| {
+                    Ok(match solver.fixed_value(var)? {
+                        Fixedness::True => Some(true),
+                        Fixedness::False => Some(false),
+                        Fixedness::Unknown => None,
+                    })
+                })
+                .collect::<Result<Vec<_>, SolverError>>()?
+        }
+        #[cfg(feature = "glucose")]
+        Backend::Glucose => {
+            let mut solver = GlucoseSolver::new();
+            problem.load_into(&mut solver)?;
+            for _ in 0..rounds {
+                if solver.eliminate(false)? == 0 {
+                    return Ok(None);
+                }
+            }
+            (1..=solver.nvars()?.min(vars))
+                .map(|var| {
+                    Ok(match solver.value(var)? {
+                        0 => Some(false),
+                        1 => Some(true),
+                        _ => None,
+                    })
+                })
+                .collect::<Result<Vec<_>, SolverError>>()?
+        }
+        #[cfg(feature = "minisat")]
+        Backend::Minisat => {
+            let mut solver = MinisatSolver::new();
+            problem.load_into(&mut solver)?;
+            for _ in 0..rounds {
+                solver.eliminate(false);
+                if !solver.okay() {
+                    return Ok(None);
+                }
+            }
+            (1..=solver.vars().min(vars))
+                .map(|var| solver.root_value(var))
+                .collect()
+        }
+        #[cfg(feature = "picosat")]
+        Backend::PicoSAT => {
+            let mut solver = PicoSATSolver::new();
+            problem.load_into(&mut solver)?;
+            for _ in 0..rounds {
+                Inprocessing::simplify(&mut solver)?;
+            }
+            if !SatSolver::is_ok(&mut solver)? {
+                return Ok(None);
+            }
+            (1..=solver.variables()?.min(vars))
+                .map(|var| solver.deref_toplevel(var))
+                .collect::<Result<Vec<_>, SolverError>>()?
+        }
+        #[allow(unreachable_patterns)]
+        _ => {
+            return Err(SolverError::UnsupportedOperation(
+                "this backend, which is not enabled",
+            ))
+        }
+    };
+    roots.resize(problem.num_vars, None);
+    Ok(Some(roots))
+}
+
+/// Drop satisfied clauses and falsified literals, and renumber the remaining variables.
+fn compact(problem: &Problem, roots: &[Option<bool>]) -> Preprocessed {
+    let value = |lit: i32| {
+        let root = roots
+            .get(lit.unsigned_abs() as usize - 1)
+            .copied()
+            .flatten();
+        root.map(|value| value == (lit > 0))
+    };
+    let fixed: Vec<i32> = (1..=roots.len() as i32)
+        .filter_map(|var| value(var).map(|value| if value { var } else { -var }))
+        .collect();
+    let mut simplified = Problem::new();
+    let mut renumbered: HashMap<i32, i32> = HashMap::new();
+    let mut var_map = vec![];
+    for clause in &problem.clauses {
+        if clause.iter().any(|&lit| value(lit) == Some(true)) {
+            continue;
+        }
+        let clause: Vec<i32> = clause
+            .iter()
+            .filter(|&&lit| value(lit).is_none())
+            .map(|&lit| {
+                let var = *renumbered.entry(lit.abs()).or_insert_with(|| {
+                    var_map.push(lit.abs());
+                    var_map.len() as i32
+                });
+                if lit > 0 {
+                    var
+                } else {
+                    -var
+                }
+            })
+            .collect();
+        simplified.clauses.push(clause);
+    }
+    simplified.num_vars = var_map.len();
+    simplified.num_clauses = simplified.clauses.len();
+    Preprocessed {
+        problem: simplified,
+        var_map,
+        fixed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::AsDimacs;
+
+    #[test]
+    fn compact_and_reconstruct() {
+        let mut problem = Problem::new();
+        for clause in [vec![1], vec![-1, 3, -4], vec![1, 2], vec![-2, 4]] {
+            problem.push_clause(clause).unwrap();
+        }
+        let pre = compact(&problem, &[Some(true), None, None, None]);
+        assert_eq!(pre.problem.clauses, vec![vec![1, -2], vec![-3, 2]]);
+        assert_eq!(pre.var_map, vec![3, 4, 2]);
+        assert_eq!(pre.fixed, vec![1]);
+        let original = pre.reconstruct(&[1, 2, -3]);
+        assert_eq!(original, vec![1, 3, 4]);
+        problem.verify_model(&original).unwrap();
+    }
+}
@@ -0,0 +1,154 @@
+//! The `signal` module stops running solvers on Ctrl-C (SIGINT) or SIGTERM.
+//!
+//! Command-line tools register their solvers with a process-wide registry and install
+//! the handler once. On a signal, every registered solver is asked to stop through its
+//! termination callback, so `solve_sat` returns `RawStatus::Unknown` and the tool can
+//! still print the best status it knows before exiting.
+//!
+//! Only backends that support `SatSolver::set_terminate` can be registered.
+//!
+//! # Usage
+//! The `signal` module needs the `signal` feature:
+//! ```toml
+//! [dependencies]
+//! satgalaxy = { version = "x.y.z", features = ["signal", "cadical"] }
+//! ```
+//! # Example
+//! ```rust,no_run
+//! use satgalaxy::signal;
+//! use satgalaxy::solver::{CaDiCaLSolver, RawStatus, SatSolver};
+//! signal::install_handler().unwrap();
+//! let mut solver = CaDiCaLSolver::new();
+//! solver.push_clause(&[1, 2]).unwrap();
+//! match signal::solve_interruptible(&mut solver).unwrap() {
+//!     RawStatus::Unknown if signal::interrupted() => println!("s UNKNOWN (interrupted)"),
+//!     status => println!("{status:?}"),
+//! }
+//! ```
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+use crate::errors::SolverError;
+use crate::solver::{RawStatus, SatSolver};
+
+/// Set once a signal arrived (or `interrupt` was called), until `reset`.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The stop flags of the registered solvers, by registration id.
+fn registry() -> &'static Mutex<HashMap<u64, Arc<AtomicBool>>> {
+    static REGISTRY: OnceLock<Mutex<HashMap<u64, Arc<AtomicBool>>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Install the SIGINT/SIGTERM handler, which calls [`interrupt`].
+///
+/// A second signal before [`reset`] exits the process with status 130, so a solver
+/// that ignores its termination callback can still be killed from the terminal.
+///
+/// Calling it again is a no-op. Fails if another handler was installed through the
+/// `ctrlc` crate.
+pub fn install_handler() -> Result<(), SolverError> {
+    static INSTALLED: OnceLock<Result<(), String>> = OnceLock::new();
+    INSTALLED
+        .get_or_init(|| {
+            ctrlc::set_handler(|| {
+                if interrupted() {
+                    std::process::exit(130);
+                }
+                interrupt();
+            })
+            .map_err(|err| err.to_string())
+        })
+        .clone()
+        .map_err(SolverError::Io)
+}
+
+/// Ask every registered solver to stop, as a signal does. Returns how many were asked.
+pub fn interrupt() -> usize {
+    INTERRUPTED.store(true, Ordering::Relaxed);
+    let registry = registry().lock().unwrap_or_else(PoisonError::into_inner);
+    for stop in registry.values() {
+        stop.store(true, Ordering::Relaxed);
+    }
+    registry.len()
+}
+
+/// Whether a signal arrived since the start of the process or the last [`reset`].
+pub fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::Relaxed)
+}
+
+/// Forget an earlier interruption, so newly registered solvers run normally.
+pub fn reset() {
+    INTERRUPTED.store(false, Ordering::Relaxed);
+}
+
+/// Keeps a solver in the registry; dropping it unregisters the solver.
+#[derive(Debug)]
+pub struct Registration {
+    id: u64,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut registry = registry().lock().unwrap_or_else(PoisonError::into_inner);
+        registry.remove(&self.id);
+    }
+}
+
+/// Register `solver`, so that a signal stops its running and later solves.
+///
+/// This installs a termination callback on the solver, replacing any earlier one. A
+/// solver registered after an interruption stops at once, until [`reset`] is called.
+pub fn register<S: SatSolver + ?Sized>(solver: &mut S) -> Result<Registration, SolverError> {
+    let stop = Arc::new(AtomicBool::new(interrupted()));
+    let flag = Arc::clone(&stop);
+    solver.set_terminate(Box::new(move || flag.load(Ordering::Relaxed)))?;
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut registry = registry().lock().unwrap_or_else(PoisonError::into_inner);
+    registry.insert(id, stop);
+    Ok(Registration { id })
+}
+
+/// Run `solve_sat` with `solver` registered for the duration of the call.
+///
+/// An interrupted solve returns `RawStatus::Unknown`; [`interrupted`] tells it apart
+/// from other limits.
+pub fn solve_interruptible<S: SatSolver + ?Sized>(
+    solver: &mut S,
+) -> Result<RawStatus, SolverError> {
+    let _registration = register(solver)?;
+    let status = solver.solve_sat();
+    // The callback stays installed, so a solver that outlives its registration must
+    // not keep reading a flag nobody sets anymore.
+    solver.set_terminate(Box::new(|| false))?;
+    status
+}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+    use crate::solver::CaDiCaLSolver;
+
+    #[test]
+    fn interrupt_registered() {
+        let mut solver = CaDiCaLSolver::new();
+        SatSolver::push_clause(&mut solver, &[1, 2]).unwrap();
+        let registration = register(&mut solver).unwrap();
+        assert!(interrupt() >= 1);
+        assert!(interrupted());
+        assert_eq!(
+            SatSolver::solve_sat(&mut solver).unwrap(),
+            RawStatus::Unknown
+        );
+        drop(registration);
+
+        reset();
+        assert_eq!(
+            solve_interruptible(&mut solver).unwrap(),
+            RawStatus::Satisfiable
+        );
+    }
+}
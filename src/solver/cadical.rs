@@ -21,11 +21,24 @@ mod binding {
     include!("../../bindings/cadical_bindings.rs");
 }
 
-use std::{ffi::c_char, ptr::NonNull};
+use std::{
+    collections::BTreeSet,
+    ffi::{c_char, c_int, c_void, CString},
+    fmt,
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 use crate::{errors::SolverError, solver::RawStatus};
 
-use super::SatSolver;
+use super::{
+    check_clause, ffi_util::Callback, Budget, BudgetLimit, Inprocessing, ModelBitset,
+    ModelIterator, SatSolver, SolverStats, CADICAL_OPTIONS,
+};
 
 macro_rules! ffi_bind {
     (
@@ -58,9 +71,105 @@ macro_rules! ffi_bind {
     };
 }
 
+/// Whether a literal is fixed at root level, as reported by [`CaDiCaLSolver::fixed_value`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fixedness {
+    /// The literal is implied by the formula.
+    True,
+    /// The negation of the literal is implied by the formula.
+    False,
+    /// Neither the literal nor its negation is implied.
+    Unknown,
+}
+
+impl From<i32> for Fixedness {
+    fn from(value: i32) -> Self {
+        match value {
+            1 => Self::True,
+            -1 => Self::False,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// A user callback polled by the terminator; returning `true` stops the solve.
+type TerminateCallback = Box<dyn FnMut() -> bool + Send>;
+
+/// State read by the terminator callback installed on every `CaDiCaLSolver`.
+struct Terminator {
+    /// Shared with `TerminationHandle`s.
+    cancelled: Arc<AtomicBool>,
+    /// Set by `solve_with_timeout` for the duration of the solve.
+    deadline: Mutex<Option<Instant>>,
+    /// Installed with `set_terminate`. A panic in it stops the solve and is resumed
+    /// once CaDiCaL returns.
+    callback: Mutex<Option<Callback<TerminateCallback>>>,
+    /// Whether the terminator stopped the current or last solve.
+    fired: AtomicBool,
+}
+
+impl fmt::Debug for Terminator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Terminator")
+            .field("cancelled", &self.cancelled)
+            .field("deadline", &self.deadline)
+            .finish_non_exhaustive()
+    }
+}
+
+unsafe extern "C" fn terminate_requested(state: *mut c_void) -> c_int {
+    let terminator = &*(state as *const Terminator);
+    let stop = terminator.cancelled.load(Ordering::Relaxed)
+        || terminator
+            .deadline
+            .lock()
+            .is_ok_and(|deadline| deadline.is_some_and(|deadline| Instant::now() >= deadline))
+        || terminator.callback.lock().is_ok_and(|mut callback| {
+            callback
+                .as_mut()
+                .is_some_and(|callback| callback.call(|callback| callback()).unwrap_or(true))
+        });
+    if stop {
+        terminator.fired.store(true, Ordering::Relaxed);
+    }
+    stop as c_int
+}
+
+/// An option name as the NUL-terminated string CaDiCaL expects.
+fn option_name(name: &str) -> Result<CString, SolverError> {
+    CString::new(name).map_err(|_| SolverError::InvalidArgument("option name contains a NUL byte"))
+}
+
+/// Clauses kept on the Rust side, see [`CaDiCaLSolver::keep_clauses`].
+#[derive(Debug, Clone, Default)]
+struct ClauseRecord {
+    clauses: Vec<Vec<i32>>,
+    /// Literals passed to `add` since the last terminating 0.
+    pending: Vec<i32>,
+}
+
+impl ClauseRecord {
+    fn add(&mut self, lit: i32) {
+        if lit == 0 {
+            self.clauses.push(std::mem::take(&mut self.pending));
+        } else {
+            self.pending.push(lit);
+        }
+    }
+}
+
 /// `CaDiCaLSolver` is a wrapper for the [CaDiCaL](https://github.com/arminbiere/cadical) Solver .
 /// It also allows creating a `CaDiCaL_Solver` instance for more low-level operations.
 /// This struct is only available when the `cadical` feature is enabled.
+///
+/// It owns the C solver, so it is not `Clone`; use `try_clone` to copy the formula
+/// into a new instance.
+/// ```compile_fail
+/// fn clone<T: Clone>(value: &T) -> T {
+///     value.clone()
+/// }
+/// clone(&satgalaxy::solver::CaDiCaLSolver::new());
+/// ```
 /// # Example
 /// ```rust
 /// use satgalaxy::solver::{CaDiCaLSolver, SatStatus, SatSolver};
@@ -86,36 +195,127 @@ macro_rules! ffi_bind {
 ///  ```toml
 ///  [dependencies]
 ///  satgalaxy = { version = "x.y.z", features = ["cadical"] }
-#[derive(Debug, Clone)]
+///  ```
+#[derive(Debug)]
 pub struct CaDiCaLSolver {
     inner: NonNull<binding::CaDiCaLSolver>,
+    /// Assumptions for the next `solve` call.
+    assumptions: Vec<i32>,
+    /// Assumptions used by the last `solve` call.
+    last_assumptions: Vec<i32>,
+    /// Result of the last `solve` call.
+    status: RawStatus,
+    /// Whether `finish` has concluded the solver.
+    concluded: bool,
+    /// State read by the terminator callback.
+    terminator: Arc<Terminator>,
+    /// Copy of the added clauses, if `keep_clauses` is on.
+    record: Option<ClauseRecord>,
+    /// Variables the application cares about, see `observe`.
+    observed: BTreeSet<i32>,
+    /// Limits applied to every `solve`, see `set_budget`.
+    budget: Budget,
+    /// The limit that stopped the last `solve`.
+    exhausted: Option<BudgetLimit>,
 }
+// SAFETY: `inner` is owned by this value alone and CaDiCaL keeps no thread-local
+// state. The terminator CaDiCaL polls is a raw pointer into `terminator`, whose `Arc`
+// keeps it alive and at the same address for as long as `inner` exists; it is only
+// read through atomics and mutexes, and its callback is `Send`, so it may be polled
+// from whichever thread solves.
+unsafe impl Send for CaDiCaLSolver {}
 impl Default for CaDiCaLSolver {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// A token that cancels the running `solve` of a [`CaDiCaLSolver`] from any thread.
+///
+/// Obtained from [`CaDiCaLSolver::termination_handle`]. Cancellation is sticky: once
+/// `cancel` is called, every `solve` returns `RawStatus::Unknown` until `reset` is called.
+#[derive(Debug, Clone)]
+pub struct TerminationHandle(Arc<AtomicBool>);
+
+impl TerminationHandle {
+    /// Ask the solver to stop; a running `solve` returns `RawStatus::Unknown` shortly after.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+    /// Whether `cancel` has been called since the last `reset`.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+    /// Clear the cancellation so that later solves run normally.
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
+
 impl CaDiCaLSolver {
     pub fn new() -> Self {
         unsafe {
-            CaDiCaLSolver {
+            let solver = CaDiCaLSolver {
                 inner: NonNull::new(binding::cadical_new_solver()).unwrap(),
-            }
+                assumptions: vec![],
+                last_assumptions: vec![],
+                status: RawStatus::Unknown,
+                concluded: false,
+                terminator: Arc::new(Terminator {
+                    cancelled: Arc::new(AtomicBool::new(false)),
+                    deadline: Mutex::new(None),
+                    callback: Mutex::new(None),
+                    fired: AtomicBool::new(false),
+                }),
+                record: None,
+                observed: BTreeSet::new(),
+                budget: Budget::default(),
+                exhausted: None,
+            };
+            binding::cadical_set_terminate(
+                solver.inner.as_ptr(),
+                Arc::as_ptr(&solver.terminator) as *mut c_void,
+                Some(terminate_requested),
+            );
+            solver
         }
     }
+
+    /// A `Send + Clone` token whose `cancel` aborts the current `solve` from another thread.
+    pub fn termination_handle(&self) -> TerminationHandle {
+        TerminationHandle(self.terminator.cancelled.clone())
+    }
     fn error(&mut self) -> Result<(), SolverError> {
         unsafe {
             let code = binding::cadical_error(self.inner.as_ptr());
             if code != 0 {
-                let msg = binding::cadical_error_message(code);
-                let msg = std::ffi::CStr::from_ptr(msg);
-                return Err(SolverError(msg.to_str().unwrap()));
+                return Err(SolverError::ffi(code, binding::cadical_error_message(code)));
             }
         }
         Ok(())
     }
 
-    /// Add a clause to the solver.
+    /// Resume a panic of the `set_terminate` callback, caught so that it did not
+    /// unwind into CaDiCaL.
+    fn resume_terminate_panic(&self) {
+        let panic = self
+            .terminator
+            .callback
+            .lock()
+            .ok()
+            .and_then(|mut callback| callback.as_mut()?.take_panic());
+        if let Some(panic) = panic {
+            std::panic::resume_unwind(panic);
+        }
+    }
+
+    /// The largest variable index CaDiCaL accepts.
+    pub const MAX_VAR: i32 = i32::MAX;
+
+    /// Add a clause to the solver, without validating its literals.
+    ///
+    /// This is the unchecked fast path: a 0 literal ends the clause early at the C level.
+    /// `SatSolver::push_clause` rejects 0 and variables above `MAX_VAR` first.
     ///
     /// # Arguments
     /// * `ps` - Pointer to an array of literals
@@ -125,43 +325,301 @@ impl CaDiCaLSolver {
             binding::cadical_add_clause(self.inner.as_ptr(), clause.as_ptr(), clause.len());
         }
         self.error()?;
+        self.unsolve();
+        if let Some(record) = &mut self.record {
+            record.clauses.push(clause.to_vec());
+        }
         Ok(())
     }
 
-    ffi_bind! {
-        /// Add an empty clause to the solver.
-        cadical_add_empty_clause() -> ();
-        as add_empty_clause
+    /// Add an empty clause to the solver.
+    pub fn add_empty_clause(&mut self) -> Result<(), SolverError> {
+        unsafe {
+            binding::cadical_add_empty_clause(self.inner.as_ptr());
+        }
+        self.error()?;
+        self.unsolve();
+        if let Some(record) = &mut self.record {
+            record.clauses.push(vec![]);
+        }
+        Ok(())
     }
 
-    ffi_bind! {
-        /// Add a literal to the current clause.
-        ///
-        /// # Arguments
-        /// * `lit` - Literal to add
-        cadical_add(lit: i32) -> ();
-        as add
+    /// Add a literal to the current clause; 0 ends the clause.
+    ///
+    /// # Arguments
+    /// * `lit` - Literal to add
+    pub fn add(&mut self, lit: i32) -> Result<(), SolverError> {
+        unsafe {
+            binding::cadical_add(self.inner.as_ptr(), lit);
+        }
+        self.error()?;
+        self.unsolve();
+        if let Some(record) = &mut self.record {
+            record.add(lit);
+        }
+        Ok(())
     }
 
-    ffi_bind! {
-        /// Assume a literal for the next solve call.
-        ///
-        /// # Arguments
-        /// * `lit` - Literal to assume
-        cadical_assume(lit: i32) -> ();
-        as assume
+    /// Keep a copy of every clause added from now on, for `for_each_clause` and
+    /// `extract_cnf`. Turning it off drops the copy.
+    ///
+    /// `satgalaxy-core` does not export CaDiCaL's `traverse_clauses`, so the clause
+    /// database cannot be read back from the C side; enable this before adding clauses.
+    pub fn keep_clauses(&mut self, keep: bool) {
+        match (keep, &self.record) {
+            (true, None) => self.record = Some(ClauseRecord::default()),
+            (false, _) => self.record = None,
+            _ => {}
+        }
     }
 
-    ffi_bind! {
-        /// Solve the formula under current assumptions.
-        ///
-        /// # Returns
-        /// `SolveResult` enum:
-        /// * `Satisfiable` (10)
-        /// * `Unsatisfiable` (20)
-        /// * `Unknown` (30)
-        cadical_solve() -> RawStatus;
-        as solve
+    /// Call `callback` on every clause of the simplified formula.
+    ///
+    /// The formula is built from the clauses kept since `keep_clauses(true)` and the
+    /// literals CaDiCaL has fixed at root level: every fixed literal is reported as a
+    /// unit, clauses satisfied by one are skipped, and falsified literals are removed.
+    /// It has the same models as the clauses added. An empty clause means the formula
+    /// is unsatisfiable. Variable elimination witnesses are not exported by the core
+    /// and are not reflected.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::solver::{CaDiCaLSolver, SatSolver};
+    /// let mut solver = CaDiCaLSolver::new();
+    /// solver.keep_clauses(true);
+    /// solver.push_clause(&[1]).unwrap();
+    /// solver.push_clause(&[-1, 2, 3]).unwrap();
+    /// solver.push_clause(&[1, 4]).unwrap();
+    /// solver.simplify().unwrap();
+    /// assert_eq!(solver.extract_cnf().unwrap(), vec![vec![1], vec![2, 3]]);
+    /// ```
+    pub fn for_each_clause<F>(&mut self, mut callback: F) -> Result<(), SolverError>
+    where
+        F: FnMut(&[i32]),
+    {
+        let mut fixed = vec![Fixedness::Unknown; self.vars()? as usize + 1];
+        for var in 1..fixed.len() as i32 {
+            fixed[var as usize] = self.fixed_value(var)?;
+        }
+        let value = |lit: i32| match fixed.get(lit.unsigned_abs() as usize) {
+            Some(Fixedness::True) if lit > 0 => Fixedness::True,
+            Some(Fixedness::False) if lit < 0 => Fixedness::True,
+            Some(Fixedness::Unknown) | None => Fixedness::Unknown,
+            Some(_) => Fixedness::False,
+        };
+        let Some(record) = &self.record else {
+            return Err(SolverError::InvalidState(
+                "Clauses are not kept, see keep_clauses",
+            ));
+        };
+        for var in 1..fixed.len() as i32 {
+            match fixed[var as usize] {
+                Fixedness::True => callback(&[var]),
+                Fixedness::False => callback(&[-var]),
+                Fixedness::Unknown => {}
+            }
+        }
+        let mut simplified = vec![];
+        for clause in &record.clauses {
+            if clause.iter().any(|&lit| value(lit) == Fixedness::True) {
+                continue;
+            }
+            simplified.clear();
+            simplified.extend(
+                clause
+                    .iter()
+                    .filter(|&&lit| value(lit) == Fixedness::Unknown),
+            );
+            callback(&simplified);
+        }
+        Ok(())
+    }
+
+    /// Collect the simplified formula of `for_each_clause`.
+    pub fn extract_cnf(&mut self) -> Result<Vec<Vec<i32>>, SolverError> {
+        let mut clauses = vec![];
+        self.for_each_clause(|clause| clauses.push(clause.to_vec()))?;
+        Ok(clauses)
+    }
+
+    /// A new solver with the same options, kept clauses, observed variables, budget and
+    /// pending assumptions.
+    ///
+    /// `satgalaxy-core` cannot copy a CaDiCaL instance, so the clauses kept since
+    /// `keep_clauses(true)` are added to a fresh one; without them this fails with
+    /// `SolverError::InvalidState`. Learned clauses and the search state are not copied,
+    /// and the copy has its own termination handles and no terminate callback.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::solver::{CaDiCaLSolver, RawStatus, SatSolver};
+    /// let mut solver = CaDiCaLSolver::new();
+    /// solver.keep_clauses(true);
+    /// solver.push_clause(&[1, 2]).unwrap();
+    /// let mut copy = solver.try_clone().unwrap();
+    /// copy.push_clause(&[-1]).unwrap();
+    /// copy.push_clause(&[-2]).unwrap();
+    /// assert_eq!(copy.solve().unwrap(), RawStatus::Unsatisfiable);
+    /// assert_eq!(solver.solve().unwrap(), RawStatus::Satisfiable);
+    /// ```
+    pub fn try_clone(&mut self) -> Result<Self, SolverError> {
+        let Some(record) = self.record.clone() else {
+            return Err(SolverError::InvalidState(
+                "Clauses are not kept, see keep_clauses",
+            ));
+        };
+        let mut copy = CaDiCaLSolver::new();
+        self.copy_options(&mut copy)?;
+        copy.keep_clauses(true);
+        for clause in &record.clauses {
+            if clause.is_empty() {
+                copy.add_empty_clause()?;
+            } else {
+                copy.add_clause(clause)?;
+            }
+        }
+        for &lit in &record.pending {
+            copy.add(lit)?;
+        }
+        for &var in &self.observed {
+            copy.observe(var)?;
+        }
+        for &lit in &self.assumptions {
+            copy.assume(lit)?;
+        }
+        copy.budget = self.budget;
+        Ok(copy)
+    }
+
+    /// Set the options of `other` that differ from ours to our values.
+    fn copy_options(&mut self, other: &mut CaDiCaLSolver) -> Result<(), SolverError> {
+        for info in CADICAL_OPTIONS {
+            let value = self.get_option(info.name)?;
+            if other.get_option(info.name)? != value {
+                other.set_option(info.name, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Mark `var` as observed: models and enumeration can then be projected onto the
+    /// observed variables.
+    ///
+    /// `satgalaxy-core` does not export CaDiCaL's external propagator interface
+    /// (`add_observed_var`), so observing is emulated: the variable is frozen, which
+    /// keeps it out of variable elimination so that clauses over it can still be added,
+    /// and it is recorded for `projected_model` and `projected_models`.
+    pub fn observe(&mut self, var: i32) -> Result<(), SolverError> {
+        check_clause(&[var], Self::MAX_VAR)?;
+        if self.observed.insert(var.abs()) {
+            self.freeze(var.abs())?;
+        }
+        Ok(())
+    }
+
+    /// Stop observing `var`, melting it again.
+    pub fn unobserve(&mut self, var: i32) -> Result<(), SolverError> {
+        if self.observed.remove(&var.abs()) {
+            self.melt(var.abs())?;
+        }
+        Ok(())
+    }
+
+    /// The observed variables, in ascending order.
+    pub fn observed_vars(&self) -> Vec<i32> {
+        self.observed.iter().copied().collect()
+    }
+
+    /// The observed variables assigned true by the last satisfiable solve.
+    pub fn projected_model(&mut self) -> Result<Vec<i32>, SolverError> {
+        let mut model = vec![];
+        for var in self.observed.clone() {
+            if self.val(var)? > 0 {
+                model.push(var);
+            }
+        }
+        Ok(model)
+    }
+
+    /// Enumerate the distinct assignments of the observed variables.
+    ///
+    /// Only observed variables are reported and blocked, so the blocking clauses stay
+    /// short and each projected model is found once.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::solver::{CaDiCaLSolver, SatSolver};
+    /// let mut solver = CaDiCaLSolver::new();
+    /// solver.push_clause(&[1, 2]).unwrap();
+    /// solver.push_clause(&[-2, 3, 4]).unwrap();
+    /// solver.observe(1).unwrap();
+    /// solver.observe(2).unwrap();
+    /// assert_eq!(solver.projected_models().count(), 3);
+    /// ```
+    pub fn projected_models(&mut self) -> ModelIterator<'_, Self> {
+        let observed = self.observed_vars();
+        ModelIterator::new(self).project(&observed)
+    }
+
+    /// Assume a literal for the next solve call.
+    ///
+    /// # Arguments
+    /// * `lit` - Literal to assume
+    pub fn assume(&mut self, lit: i32) -> Result<(), SolverError> {
+        unsafe {
+            binding::cadical_assume(self.inner.as_ptr(), lit);
+        }
+        self.error()?;
+        self.unsolve();
+        self.assumptions.push(lit);
+        Ok(())
+    }
+
+    /// Record that CaDiCaL has left the state of the last solve, in which alone
+    /// `conclude` is allowed, by adding a clause, an assumption or a constraint.
+    fn unsolve(&mut self) {
+        self.status = RawStatus::Unknown;
+        self.concluded = false;
+    }
+
+    /// Solve the formula under current assumptions.
+    ///
+    /// # Returns
+    /// `SolveResult` enum:
+    /// * `Satisfiable` (10)
+    /// * `Unsatisfiable` (20)
+    /// * `Unknown` (30)
+    pub fn solve(&mut self) -> Result<RawStatus, SolverError> {
+        // CaDiCaL resets its limits after every solve, so the budget is applied each time.
+        let budget = self.budget;
+        if let Some(conflicts) = budget.conflicts {
+            let conflicts = i32::try_from(conflicts).unwrap_or(i32::MAX);
+            self.limit(c"conflicts".as_ptr(), conflicts)?;
+        }
+        if let Some(decisions) = budget.decisions {
+            let decisions = i32::try_from(decisions).unwrap_or(i32::MAX);
+            self.limit(c"decisions".as_ptr(), decisions)?;
+        }
+        self.terminator.fired.store(false, Ordering::Relaxed);
+        let status = unsafe { binding::cadical_solve(self.inner.as_ptr()) }.into();
+        self.resume_terminate_panic();
+        self.error()?;
+        self.last_assumptions = std::mem::take(&mut self.assumptions);
+        self.status = status;
+        self.concluded = false;
+        // CaDiCaL does not export its counters, so with both limits set the conflict
+        // limit is reported.
+        self.exhausted = None;
+        if status == RawStatus::Unknown && !self.terminator.fired.load(Ordering::Relaxed) {
+            self.exhausted = match budget {
+                Budget {
+                    conflicts: Some(_), ..
+                } => Some(BudgetLimit::Conflicts),
+                Budget {
+                    decisions: Some(_), ..
+                } => Some(BudgetLimit::Decisions),
+                _ => None,
+            };
+        }
+        Ok(status)
     }
 
     ffi_bind! {
@@ -190,10 +648,14 @@ impl CaDiCaLSolver {
         as failed
     }
 
-    ffi_bind! {
-        /// Add a literal to the constraint clause.
-        cadical_constrain(lit: i32) -> ();
-        as constrain
+    /// Add a literal to the constraint clause.
+    pub fn constrain(&mut self, lit: i32) -> Result<(), SolverError> {
+        unsafe {
+            binding::cadical_constrain(self.inner.as_ptr(), lit);
+        }
+        self.error()?;
+        self.unsolve();
+        Ok(())
     }
 
     ffi_bind! {
@@ -207,6 +669,8 @@ impl CaDiCaLSolver {
 
     /// Set a solver option.
     ///
+    /// The name is not checked; see [`CadicalOptions`](super::CadicalOptions) for validated configurations.
+    ///
     /// # Arguments
     /// * `name` - Option name
     /// * `val` - Option value
@@ -214,10 +678,9 @@ impl CaDiCaLSolver {
     /// # Returns
     /// `true` if successful, `false` otherwise.
     pub fn set_option(&mut self, name: &str, val: i32) -> Result<bool, SolverError> {
-        let name = name.as_bytes();
-        let name = name.as_ptr() as *const c_char;
+        let name = option_name(name)?;
         unsafe {
-            binding::cadical_set_option(self.inner.as_ptr(), name, val);
+            binding::cadical_set_option(self.inner.as_ptr(), name.as_ptr(), val);
         }
         self.error()?;
         Ok(true)
@@ -244,9 +707,8 @@ impl CaDiCaLSolver {
     /// # Returns
     /// Current value of the option.
     pub fn get_option(&mut self, name: &str) -> Result<i32, SolverError> {
-        let name = name.as_bytes();
-        let name = name.as_ptr() as *const c_char;
-        let ret = unsafe { binding::cadical_get_option(self.inner.as_ptr(), name) };
+        let name = option_name(name)?;
+        let ret = unsafe { binding::cadical_get_option(self.inner.as_ptr(), name.as_ptr()) };
         self.error()?;
         Ok(ret)
     }
@@ -289,12 +751,43 @@ impl CaDiCaLSolver {
         as fixed
     }
 
+    ffi_bind! {
+        /// Check if a literal is fixed at root level.
+        ///
+        /// # Arguments
+        /// * `lit` - Literal to check
+        ///
+        /// # Returns
+        /// `Fixedness` of the literal.
+        cadical_fixed(lit: i32) -> Fixedness;
+        as fixed_value
+    }
+
     ffi_bind! {
         /// Conclude solving process.
         cadical_conclude() -> ();
         as conclude
     }
 
+    /// Conclude the solving process explicitly.
+    ///
+    /// This runs `conclude`, which finalizes statistics and any proof output, so the
+    /// results can be inspected before the solver is dropped. Calling it more than
+    /// once, or dropping the solver afterwards, does not conclude again. It does nothing
+    /// unless the last `solve` returned SAT or UNSAT and no clause, assumption or
+    /// constraint was added since, as CaDiCaL only allows concluding in that state.
+    ///
+    /// `satgalaxy-core` does not export CaDiCaL's proof tracing (`trace_proof`,
+    /// `close_proof`), so there is no proof file to flush; use
+    /// `PicoSATSolver::write_trace` for proofs.
+    pub fn finish(&mut self) -> Result<(), SolverError> {
+        if !self.concluded && self.status != RawStatus::Unknown {
+            self.conclude()?;
+            self.concluded = true;
+        }
+        Ok(())
+    }
+
     ffi_bind! {
         /// Terminate solving asynchronously.
         cadical_terminate() -> ();
@@ -336,13 +829,59 @@ impl CaDiCaLSolver {
         as melt
     }
 
-    ffi_bind! {
-        /// Execute preprocessing.
-        ///
-        /// # Returns
-        /// `RawStatus` status after preprocessing.
-        cadical_simplify() -> RawStatus;
-        as simplify
+    /// Execute preprocessing.
+    ///
+    /// Like `solve`, this resets the current assumptions.
+    ///
+    /// # Returns
+    /// `RawStatus` status after preprocessing.
+    pub fn simplify(&mut self) -> Result<RawStatus, SolverError> {
+        let status = unsafe { binding::cadical_simplify(self.inner.as_ptr()) }.into();
+        self.resume_terminate_panic();
+        self.error()?;
+        self.assumptions.clear();
+        Ok(status)
+    }
+
+    /// Freeze every literal in `lits`.
+    ///
+    /// # Arguments
+    /// * `lits` - Literals to freeze
+    pub fn freeze_all(&mut self, lits: &[i32]) -> Result<(), SolverError> {
+        for &lit in lits {
+            unsafe {
+                binding::cadical_freeze(self.inner.as_ptr(), lit);
+            }
+        }
+        self.error()
+    }
+
+    /// Unfreeze every literal in `lits`.
+    ///
+    /// # Arguments
+    /// * `lits` - Literals to unfreeze
+    pub fn melt_all(&mut self, lits: &[i32]) -> Result<(), SolverError> {
+        for &lit in lits {
+            unsafe {
+                binding::cadical_melt(self.inner.as_ptr(), lit);
+            }
+        }
+        self.error()
+    }
+
+    /// Get all currently frozen variables.
+    ///
+    /// # Returns
+    /// Variables in `1..=vars()` that are frozen, in ascending order.
+    pub fn frozen_vars(&mut self) -> Result<Vec<i32>, SolverError> {
+        let vars = self.vars()?;
+        let mut frozen = vec![];
+        for var in 1..=vars {
+            if self.frozen(var)? {
+                frozen.push(var);
+            }
+        }
+        Ok(frozen)
     }
     ffi_bind! {
     /// Allocate clauses in arena
@@ -886,6 +1425,7 @@ impl CaDiCaLSolver {
 
 impl SatSolver for CaDiCaLSolver {
     fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        check_clause(clause, Self::MAX_VAR)?;
         CaDiCaLSolver::add_clause(self, clause)
     }
 
@@ -903,9 +1443,116 @@ impl SatSolver for CaDiCaLSolver {
         }
         Ok(model)
     }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        for &lit in assumptions {
+            self.assume(lit)?;
+        }
+        self.solve()
+    }
+
+    fn statistics(&mut self) -> Result<SolverStats, SolverError> {
+        Ok(SolverStats {
+            clauses: Some(self.irredundant()? as u64),
+            ..SolverStats::default()
+        })
+    }
+
+    fn set_terminate(
+        &mut self,
+        callback: Box<dyn FnMut() -> bool + Send>,
+    ) -> Result<(), SolverError> {
+        *self.terminator.callback.lock().unwrap() = Some(Callback::new(callback));
+        Ok(())
+    }
+
+    fn solve_with_timeout(&mut self, timeout: Duration) -> Result<RawStatus, SolverError> {
+        *self.terminator.deadline.lock().unwrap() = Some(Instant::now() + timeout);
+        let status = self.solve();
+        *self.terminator.deadline.lock().unwrap() = None;
+        status
+    }
+
+    fn set_budget(&mut self, budget: Budget) -> Result<(), SolverError> {
+        if budget.propagations.is_some() || budget.memory_mb.is_some() {
+            return Err(SolverError::UnsupportedOperation(
+                "propagation and memory budgets",
+            ));
+        }
+        self.budget = budget;
+        Ok(())
+    }
+
+    fn exhausted_budget(&mut self) -> Result<Option<BudgetLimit>, SolverError> {
+        Ok(self.exhausted)
+    }
+
+    fn model_bitset(&mut self) -> Result<ModelBitset, SolverError> {
+        let vars = self.vars()?;
+        ModelBitset::from_fn(vars as usize, |var| Ok(self.val(var)? > 0))
+    }
+
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        self.vars()
+    }
+
+    fn reset(&mut self) -> Result<(), SolverError> {
+        self.finish()?;
+        let mut fresh = CaDiCaLSolver::new();
+        self.copy_options(&mut fresh)?;
+        // `fresh` takes the old instance and destroys it; the new one must report to
+        // our terminator, which keeps the callback and the termination handles.
+        std::mem::swap(&mut self.inner, &mut fresh.inner);
+        unsafe {
+            binding::cadical_set_terminate(
+                self.inner.as_ptr(),
+                Arc::as_ptr(&self.terminator) as *mut c_void,
+                Some(terminate_requested),
+            );
+        }
+        self.assumptions.clear();
+        self.last_assumptions.clear();
+        self.status = RawStatus::Unknown;
+        self.concluded = false;
+        self.terminator.fired.store(false, Ordering::Relaxed);
+        if self.record.is_some() {
+            self.record = Some(ClauseRecord::default());
+        }
+        self.observed.clear();
+        self.exhausted = None;
+        Ok(())
+    }
+
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        if self.status != RawStatus::Unsatisfiable {
+            return Ok(None);
+        }
+        let mut conflict = vec![];
+        for lit in self.last_assumptions.clone() {
+            if self.failed(lit)? {
+                conflict.push(-lit);
+            }
+        }
+        Ok(Some(conflict))
+    }
+}
+impl Inprocessing for CaDiCaLSolver {
+    fn simplify(&mut self) -> Result<(), SolverError> {
+        CaDiCaLSolver::simplify(self)?;
+        Ok(())
+    }
+
+    fn remove_learned(&mut self, percentage: u32) -> Result<(), SolverError> {
+        // `reducetarget` accepts 10 to 100 percent.
+        self.set_opt_reduce(1)?;
+        self.set_opt_reducetarget(percentage.clamp(10, 100) as i32)?;
+        Ok(())
+    }
 }
+
 impl Drop for CaDiCaLSolver {
     fn drop(&mut self) {
+        let _ = self.finish();
         unsafe {
             binding::cadical_destroy(self.inner.as_ptr());
         }
@@ -914,7 +1561,7 @@ impl Drop for CaDiCaLSolver {
 
 #[cfg(test)]
 mod tests {
-    use crate::solver::SatStatus;
+    use crate::solver::{ModelCount, SatStatus};
 
     use super::*;
     #[test]
@@ -928,6 +1575,47 @@ mod tests {
         ));
     }
     #[test]
+    fn reset_keeps_configuration() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.set_option("elim", 0).unwrap();
+        solver.keep_clauses(true);
+        solver.push_clause(&[1]).unwrap();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Satisfiable);
+        let handle = solver.termination_handle();
+        solver.reset().unwrap();
+        assert_eq!(solver.get_option("elim").unwrap(), 0);
+        assert_eq!(solver.vars().unwrap(), 0);
+        solver.push_clause(&[-1]).unwrap();
+        handle.cancel();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Unknown);
+        handle.reset();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Satisfiable);
+        assert_eq!(solver.extract_cnf().unwrap(), vec![vec![-1]]);
+    }
+    #[test]
+    fn try_clone_owns_its_solver() {
+        let mut solver = CaDiCaLSolver::new();
+        assert!(matches!(
+            solver.try_clone(),
+            Err(SolverError::InvalidState(_))
+        ));
+        solver.set_option("elim", 0).unwrap();
+        solver.keep_clauses(true);
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.add(3).unwrap();
+        solver.observe(2).unwrap();
+        let mut copy = solver.try_clone().unwrap();
+        // Dropping the original must leave the copy's instance intact.
+        drop(solver);
+        assert_eq!(copy.get_option("elim").unwrap(), 0);
+        assert_eq!(copy.observed_vars(), vec![2]);
+        copy.add(0).unwrap();
+        assert_eq!(copy.extract_cnf().unwrap(), vec![vec![1, 2], vec![3]]);
+        let copy_of_copy = copy.try_clone().unwrap();
+        drop(copy);
+        drop(copy_of_copy);
+    }
+    #[test]
     fn sat() {
         let mut solver = CaDiCaLSolver::new();
         solver.push_clause(&vec![1, 2]).unwrap();
@@ -936,4 +1624,305 @@ mod tests {
             matches!(solver.solve_model().unwrap(),SatStatus::Satisfiable(x) if x.eq(&vec![2]))
         );
     }
+    /// Pigeonhole with 12 pigeons and 11 holes is far too hard to finish in time.
+    fn hard_solver() -> CaDiCaLSolver {
+        let (pigeons, holes) = (12, 11);
+        let var = |p: i32, h: i32| p * holes + h + 1;
+        let mut solver = CaDiCaLSolver::new();
+        for p in 0..pigeons {
+            solver
+                .push_clause(&(0..holes).map(|h| var(p, h)).collect::<Vec<_>>())
+                .unwrap();
+        }
+        for h in 0..holes {
+            for p in 0..pigeons {
+                for q in p + 1..pigeons {
+                    solver.push_clause(&[-var(p, h), -var(q, h)]).unwrap();
+                }
+            }
+        }
+        solver
+    }
+    #[test]
+    fn solve_with_timeout() {
+        let mut solver = hard_solver();
+        let timeout = std::time::Duration::from_millis(100);
+        assert_eq!(
+            solver.solve_with_timeout(timeout).unwrap(),
+            RawStatus::Unknown
+        );
+
+        let mut solver = hard_solver();
+        let mut polls = 0;
+        solver
+            .set_terminate(Box::new(move || {
+                polls += 1;
+                polls > 1000
+            }))
+            .unwrap();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Unknown);
+    }
+    #[test]
+    fn budget() {
+        let mut solver = hard_solver();
+        let budget = Budget {
+            conflicts: Some(100),
+            ..Budget::default()
+        };
+        solver.set_budget(budget).unwrap();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Unknown);
+        assert_eq!(
+            solver.exhausted_budget().unwrap(),
+            Some(BudgetLimit::Conflicts)
+        );
+        let memory = Budget {
+            memory_mb: Some(64),
+            ..Budget::default()
+        };
+        assert!(solver.set_budget(memory).is_err());
+
+        solver.set_budget(Budget::default()).unwrap();
+        solver.termination_handle().cancel();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Unknown);
+        assert_eq!(solver.exhausted_budget().unwrap(), None);
+    }
+    #[test]
+    fn termination_handle() {
+        let mut solver = hard_solver();
+        let handle = solver.termination_handle();
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            handle.cancel();
+        });
+        assert_eq!(solver.solve().unwrap(), RawStatus::Unknown);
+        canceller.join().unwrap();
+        assert!(solver.termination_handle().is_cancelled());
+    }
+    #[test]
+    fn panicking_terminate() {
+        let mut solver = hard_solver();
+        solver.set_terminate(Box::new(|| panic!("stop"))).unwrap();
+        let panic =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| solver.solve())).unwrap_err();
+        assert_eq!(panic.downcast_ref::<&str>(), Some(&"stop"));
+        solver.set_terminate(Box::new(|| true)).unwrap();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Unknown);
+    }
+    #[test]
+    fn retractable_clause() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        let s1 = solver.add_retractable_clause(&[-1]).unwrap();
+        let s2 = solver.add_retractable_clause(&[-2]).unwrap();
+        assert_eq!((s1, s2), (3, 4));
+        assert_eq!(
+            solver.solve_with_assumptions(&[s1, s2]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        solver.activate(s1).unwrap();
+        assert_eq!(
+            solver.solve_with_assumptions(&[s2]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        solver.retract(s2).unwrap();
+        assert!(
+            matches!(solver.solve_model().unwrap(), SatStatus::Satisfiable(m) if m.contains(&2))
+        );
+    }
+    #[test]
+    fn failed_assumptions() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-1, 3]).unwrap();
+        assert_eq!(
+            solver.solve_with_assumptions(&[-2, -3, 4]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        let mut failed = SatSolver::failed_assumptions(&mut solver).unwrap();
+        failed.sort();
+        assert_eq!(failed, vec![-3, -2]);
+        assert_eq!(
+            solver.solve_with_assumptions(&[-2]).unwrap(),
+            RawStatus::Satisfiable
+        );
+        assert!(SatSolver::failed_assumptions(&mut solver)
+            .unwrap()
+            .is_empty());
+    }
+    #[test]
+    fn finish() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1]).unwrap();
+        solver.push_clause(&[-1]).unwrap();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Unsatisfiable);
+        solver.finish().unwrap();
+        assert!(solver.concluded);
+        solver.finish().unwrap();
+        // Adding or assuming leaves the solved state, where concluding would abort.
+        solver.push_clause(&[2]).unwrap();
+        assert!(!solver.concluded);
+        solver.finish().unwrap();
+        assert!(!solver.concluded);
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Satisfiable);
+        solver.assume(-1).unwrap();
+        solver.reset().unwrap();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Satisfiable);
+        solver.push_clause(&[-1]).unwrap();
+        drop(solver);
+    }
+    #[test]
+    fn solve_benchmarked() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-1]).unwrap();
+        let outcome = solver.solve_benchmarked().unwrap();
+        assert!(outcome.elapsed > std::time::Duration::ZERO);
+        assert_eq!(outcome.status, solver.solve_model().unwrap());
+    }
+    #[test]
+    fn count_models() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        assert_eq!(
+            solver.count_models(10).unwrap(),
+            ModelCount {
+                exact: Some(3),
+                hit_cap: false
+            }
+        );
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(
+            solver.count_models(10).unwrap(),
+            ModelCount {
+                exact: None,
+                hit_cap: true
+            }
+        );
+    }
+    #[test]
+    fn add_xor() {
+        for n in 1..=7i32 {
+            for rhs in [false, true] {
+                let mut solver = CaDiCaLSolver::new();
+                let lits: Vec<i32> = (1..=n).map(|v| if v % 2 == 0 { -v } else { v }).collect();
+                solver.add_xor(&lits, rhs).unwrap();
+                let SatStatus::Satisfiable(model) = solver.solve_model().unwrap() else {
+                    panic!("parity constraint must be satisfiable");
+                };
+                let parity = lits
+                    .iter()
+                    .filter(|&&lit| model.contains(&lit.abs()) == (lit > 0))
+                    .count()
+                    % 2
+                    == 1;
+                assert_eq!(parity, rhs);
+                assert_eq!(
+                    solver.count_models(1 << n).unwrap().exact,
+                    Some(1 << (n - 1))
+                );
+            }
+        }
+    }
+    #[test]
+    fn model_bitset() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-1]).unwrap();
+        solver.push_clause(&[70]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        let bits = solver.model_bitset().unwrap();
+        assert_eq!(bits.vars, 70);
+        assert_eq!(bits.words.len(), 2);
+        assert!(!bits.bit(1));
+        assert!(bits.bit(2));
+        assert!(bits.bit(70));
+        assert!(!bits.bit(71));
+    }
+    #[test]
+    fn remove_learned() {
+        let mut solver = CaDiCaLSolver::new();
+        // The C API exposes no learned clause count, so check the reduction options.
+        Inprocessing::remove_learned(&mut solver, 5).unwrap();
+        assert_eq!(solver.get_option("reduce").unwrap(), 1);
+        assert_eq!(solver.get_option("reducetarget").unwrap(), 10);
+        Inprocessing::remove_learned(&mut solver, 250).unwrap();
+        assert_eq!(solver.get_option("reducetarget").unwrap(), 100);
+    }
+    #[test]
+    fn last_conflict() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[3, 4]).unwrap();
+        assert_eq!(solver.last_conflict().unwrap(), None);
+        solver.assume(-1).unwrap();
+        solver.assume(-2).unwrap();
+        solver.assume(3).unwrap();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Unsatisfiable);
+        let mut conflict = solver.last_conflict().unwrap().unwrap();
+        conflict.sort();
+        assert_eq!(conflict, vec![1, 2]);
+    }
+    #[test]
+    fn observe() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-1, 3]).unwrap();
+        solver.push_clause(&[-3, 4, 5]).unwrap();
+        solver.observe(-1).unwrap();
+        solver.observe(3).unwrap();
+        assert_eq!(solver.observed_vars(), vec![1, 3]);
+        assert!(solver.frozen(1).unwrap());
+        assert_eq!(solver.solve().unwrap(), RawStatus::Satisfiable);
+        let projected = solver.projected_model().unwrap();
+        assert!(projected.iter().all(|var| [1, 3].contains(var)));
+
+        let mut models: Vec<Vec<i32>> = solver.projected_models().map(Result::unwrap).collect();
+        models.sort();
+        assert_eq!(models, vec![vec![], vec![1, 3], vec![3]]);
+        solver.unobserve(1).unwrap();
+        assert!(!solver.frozen(1).unwrap());
+    }
+    #[test]
+    fn extract_cnf() {
+        let mut solver = CaDiCaLSolver::new();
+        assert!(solver.extract_cnf().is_err());
+        solver.keep_clauses(true);
+        solver.push_clause(&[-1]).unwrap();
+        solver.push_clause(&[1, 2, 3]).unwrap();
+        for lit in [-2, 4, 0] {
+            solver.add(lit).unwrap();
+        }
+        solver.push_clause(&[-4, 5]).unwrap();
+        solver.simplify().unwrap();
+        let cnf = solver.extract_cnf().unwrap();
+        assert!(cnf.contains(&vec![-1]));
+        assert!(cnf.contains(&vec![2, 3]));
+        assert!(cnf.iter().all(|clause| !clause.contains(&1)));
+        assert!(cnf.contains(&vec![-2, 4]) || cnf.contains(&vec![4]));
+    }
+    #[test]
+    fn fixed_value() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1]).unwrap();
+        solver.push_clause(&[2, 3]).unwrap();
+        assert_eq!(solver.solve().unwrap(), RawStatus::Satisfiable);
+        assert_eq!(solver.fixed_value(1).unwrap(), Fixedness::True);
+        assert_eq!(solver.fixed_value(-1).unwrap(), Fixedness::False);
+        assert_eq!(solver.fixed_value(2).unwrap(), Fixedness::Unknown);
+    }
+    #[test]
+    fn freeze_all() {
+        let mut solver = CaDiCaLSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-2, 3]).unwrap();
+        solver.push_clause(&[-3, 4]).unwrap();
+        solver.freeze_all(&[1, 3, 4]).unwrap();
+        solver.simplify().unwrap();
+        assert_eq!(solver.frozen_vars().unwrap(), vec![1, 3, 4]);
+        solver.melt_all(&[1, 3, 4]).unwrap();
+        assert!(solver.frozen_vars().unwrap().is_empty());
+    }
 }
@@ -0,0 +1,348 @@
+use std::collections::BTreeMap;
+
+use crate::errors::SolverError;
+
+use super::CaDiCaLSolver;
+
+/// Static description of a CaDiCaL option: its name, meaning and valid range.
+///
+/// Defaults are not listed, since they depend on the CaDiCaL build; read them with
+/// [`CadicalOptions::defaults`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptionInfo {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl OptionInfo {
+    /// Look up an option by name.
+    pub fn find(name: &str) -> Option<&'static OptionInfo> {
+        CADICAL_OPTIONS.iter().find(|info| info.name == name)
+    }
+
+    /// Whether the option is an on/off switch.
+    pub fn is_bool(&self) -> bool {
+        self.min == 0 && self.max == 1
+    }
+
+    /// Check `value` against the option's range.
+    pub fn check(&self, value: i32) -> Result<(), SolverError> {
+        if (self.min..=self.max).contains(&value) {
+            Ok(())
+        } else {
+            Err(SolverError::InvalidArgument("option value out of range"))
+        }
+    }
+}
+
+macro_rules! option_table {
+    ($($name:ident: $min:literal..=$max:literal, $description:literal;)*) => {
+        /// Every option `satgalaxy-core` exports for CaDiCaL, in alphabetical order.
+        pub const CADICAL_OPTIONS: &[OptionInfo] = &[
+            $(OptionInfo {
+                name: stringify!($name),
+                description: $description,
+                min: $min,
+                max: $max,
+            },)*
+        ];
+    };
+}
+
+option_table! {
+    arena: 0..=1, "Allocate clauses in arena";
+    arenacompact: 0..=1, "Keep clauses compact";
+    arenasort: 0..=1, "Sort clauses in arena";
+    arenatype: 1..=3, "Set arena type (1=clause, 2=var, 3=queue)";
+    binary: 0..=1, "Use binary proof format";
+    block: 0..=1, "Enable blocked clause elimination";
+    blockmaxclslim: 1..=2000000000, "Set maximum clause size for blocked clause elimination";
+    blockminclslim: 2..=2000000000, "Set minimum clause size for blocked clause elimination";
+    blockocclim: 1..=2000000000, "Set occurrence limit for blocked clause elimination";
+    bump: 0..=1, "Bump variables";
+    bumpreason: 0..=1, "Bump reason literals too";
+    bumpreasondepth: 1..=3, "Set bump reason depth";
+    check: 0..=1, "Enable internal checking";
+    checkassumptions: 0..=1, "Check assumptions satisfied";
+    checkconstraint: 0..=1, "Check constraint satisfied";
+    checkfailed: 0..=1, "Check failed literals form core";
+    checkfrozen: 0..=1, "Check all frozen semantics";
+    checkproof: 0..=3, "Set proof checking mode (1=drat, 2=lrat, 3=both)";
+    checkwitness: 0..=1, "Check witness internally";
+    chrono: 0..=2, "Set chronological backtracking mode";
+    chronoalways: 0..=1, "Force always chronological backtracking";
+    chronolevelim: 0..=2000000000, "Set chronological level limit";
+    chronoreusetrail: 0..=1, "Reuse trail chronologically";
+    compact: 0..=1, "Compact internal variables";
+    compactint: 1..=2000000000, "Set compacting interval";
+    compactlim: 0..=1000, "Set inactive limit per mille";
+    compactmin: 1..=2000000000, "Set minimum inactive limit";
+    condition: 0..=1, "Enable globally blocked clause elimination";
+    conditionint: 1..=2000000000, "Set initial conflict interval for condition";
+    conditionmaxeff: 0..=2000000000, "Set maximum condition efficiency";
+    conditionmaxrat: 1..=2000000000, "Set maximum clause variable ratio";
+    conditionmineff: 0..=2000000000, "Set minimum condition efficiency";
+    conditionreleff: 1..=100000, "Set relative efficiency per mille for condition";
+    cover: 0..=1, "Enable covered clause elimination";
+    covermaxclslim: 1..=2000000000, "Set maximum clause size for cover";
+    covermaxeff: 0..=2000000000, "Set maximum cover efficiency";
+    coverminclslim: 2..=2000000000, "Set minimum clause size for cover";
+    covermineff: 0..=2000000000, "Set minimum cover efficiency";
+    coverreleff: 1..=100000, "Set relative efficiency per mille for cover";
+    decompose: 0..=1, "Decompose BIG in SCCs and ELS";
+    decomposerounds: 1..=16, "Set number of decompose rounds";
+    deduplicate: 0..=1, "Remove duplicated binaries";
+    eagersubsume: 0..=1, "Subsume recently learned clauses";
+    eagersubsumelim: 1..=1000, "Set limit on subsumed candidates";
+    elim: 0..=1, "Enable bounded variable elimination";
+    elimands: 0..=1, "Find AND gates";
+    elimaxeff: 0..=2000000000, "Set maximum elimination efficiency";
+    elimbackward: 0..=1, "Enable eager backward subsumption";
+    elimboundmax: -1..=2000000, "Set maximum elimination bound";
+    elimboundmin: -1..=2000000, "Set minimum elimination bound";
+    elimclslim: 2..=2000000000, "Set resolvent size limit";
+    elimequivs: 0..=1, "Find equivalence gates";
+    elimineff: 0..=2000000000, "Set minimum elimination efficiency";
+    elimint: 1..=2000000000, "Set elimination interval";
+    elimites: 0..=1, "Find if-then-else gates";
+    elimlimited: 0..=1, "Limit resolutions";
+    elimocclim: 0..=2000000000, "Set occurrence limit for elimination";
+    elimprod: 0..=10000, "Set elimination score product weight";
+    elimreleff: 1..=100000, "Set relative efficiency per mille for elimination";
+    elimrounds: 1..=512, "Set usual number of elimination rounds";
+    elimsubst: 0..=1, "Enable elimination by substitution";
+    elimsum: 0..=10000, "Set elimination score sum weight";
+    elimxorlim: 2..=27, "Set maximum XOR size";
+    elimxors: 0..=1, "Find XOR gates";
+    emagluefast: 1..=2000000000, "Set window fast glue";
+    emaglueslow: 1..=2000000000, "Set window slow glue";
+    emajump: 1..=2000000000, "Set window back-jump level";
+    emalevel: 1..=2000000000, "Set window back-track level";
+    emasize: 1..=2000000000, "Set window learned clause size";
+    ematrailfast: 1..=2000000000, "Set window fast trail";
+    ematrailslow: 1..=2000000000, "Set window slow trail";
+    exteagerreasons: 0..=1, "Eagerly ask for all reasons (0: only when needed)";
+    exteagerrecalc: 0..=1,
+        "Recalculate all levels after eagerly asking for reasons (0: trust the external tool)";
+    externallrat: 0..=1, "Enable external LRAT";
+    flush: 0..=1, "Flush redundant clauses";
+    flushfactor: 1..=1000, "Set interval increase for flushing";
+    flushint: 1..=2000000000, "Set initial limit for flushing";
+    forcephase: 0..=1, "Always use initial phase";
+    frat: 0..=2, "Set FRAT proof format (1=frat(lrat), 2=frat(drat))";
+    idrup: 0..=1, "Use incremental proof format";
+    ilb: 0..=1, "Enable ILB (incremental lazy backtrack)";
+    ilbassumptions: 0..=1, "Enable trail reuse for assumptions (ILB-like)";
+    inprocessing: 0..=1, "Enable inprocessing";
+    instantiate: 0..=1, "Enable variable instantiation";
+    instantiateclslim: 2..=2000000000, "Set minimum clause size for instantiation";
+    instantiateocclim: 1..=2000000000, "Set maximum occurrence limit for instantiation";
+    instantiateonce: 0..=1, "Instantiate each clause once";
+    lidrup: 0..=1, "Use linear incremental proof format";
+    log: 0..=1, "Enable logging";
+    logsort: 0..=1, "Sort logged clauses";
+    lrat: 0..=1, "Use LRAT proof format";
+    lucky: 0..=1, "Search for lucky phases";
+    minimize: 0..=1, "Minimize learned clauses";
+    minimizedepth: 0..=1000, "Set minimization depth";
+    otfs: 0..=1, "Enable on-the-fly self subsumption";
+    phase: 0..=1, "Set initial phase";
+    probe: 0..=1, "Enable failed literal probing";
+    probehbr: 0..=1, "Learn hyper binary clauses";
+    probeint: 1..=2000000000, "Set probing interval";
+    probemaxeff: 0..=2000000000, "Set maximum probing efficiency";
+    probemineff: 0..=2000000000, "Set minimum probing efficiency";
+    probereleff: 1..=100000, "Set relative efficiency per mille for probing";
+    proberounds: 1..=16, "Set probing rounds";
+    profile: 0..=4, "Set profiling level";
+    quiet: 0..=1, "Disable all messages";
+    radixsortlim: 0..=2000000000, "Set radix sort limit";
+    realtime: 0..=1, "Use real instead of process time";
+    reduce: 0..=1, "Reduce useless clauses";
+    reduceint: 10..=1000000, "Set reduce interval";
+    reducetarget: 10..=100, "Set reduce fraction in percent";
+    reducetier1glue: 1..=2000000000, "Set glue of kept learned clauses";
+    reducetier2glue: 1..=2000000000, "Set glue of tier two clauses";
+    reluctant: 0..=2000000000, "Set reluctant doubling period";
+    reluctantmax: 0..=2000000000, "Set maximum reluctant doubling period";
+    rephase: 0..=1, "Enable resetting phase";
+    rephaseint: 1..=2000000000, "Set rephase interval";
+    report: 0..=1, "Enable reporting";
+    reportall: 0..=1, "Report even if not successful";
+    reportsolve: 0..=1, "Use solving not process time for reporting";
+    restart: 0..=1, "Enable restarts";
+    restartint: 1..=2000000000, "Set restart interval";
+    restartmargin: 0..=100, "Set slow fast margin in percent";
+    restartreusetrail: 0..=1, "Enable trail reuse";
+    restoreall: 0..=2, "Restore all clauses (2=really)";
+    restoreflush: 0..=1, "Remove satisfied clauses";
+    reverse: 0..=1, "Reverse variable ordering";
+    score: 0..=1, "Use EVSIDS scores";
+    scorefactor: 500..=1000, "Set score factor per mille";
+    seed: 0..=2000000000, "Set random seed";
+    shrink: 0..=3, "Shrink conflict clause (1=only with binary, 2=minimize when pulling, 3=full)";
+    shrinkreap: 0..=1, "Use a reap for shrinking";
+    shuffle: 0..=1, "Shuffle variables";
+    shufflequeue: 0..=1, "Shuffle variable queue";
+    shufflerandom: 0..=1, "Use random instead of reverse shuffling";
+    shufflescores: 0..=1, "Shuffle variable scores";
+    stabilize: 0..=1, "Enable stabilizing phases";
+    stabilizefactor: 101..=2000000000, "Set phase increase in percent";
+    stabilizeint: 1..=2000000000, "Set stabilizing interval";
+    stabilizemaxint: 1..=2000000000, "Set maximum stabilizing phase";
+    stabilizeonly: 0..=1, "Use only stabilizing phases";
+    stats: 0..=1, "Print all statistics at the end of the run";
+    subsume: 0..=1, "Enable clause subsumption";
+    subsumebinlim: 0..=2000000000, "Set watch list length limit for subsumption";
+    subsumeclslim: 0..=2000000000, "Set clause length limit for subsumption";
+    subsumeint: 1..=2000000000, "Set subsume interval";
+    subsumelimited: 0..=1, "Limit subsumption checks";
+    subsumemaxeff: 0..=2000000000, "Set maximum subsuming efficiency";
+    subsumemineff: 0..=2000000000, "Set minimum subsuming efficiency";
+    subsumeocclim: 0..=2000000000, "Set watch list length limit for subsumption";
+    subsumereleff: 1..=100000, "Set relative efficiency per mille for subsumption";
+    subsumestr: 0..=1, "Strengthen during subsume";
+    target: 0..=2, "Set target phases (1=stable only)";
+    terminateint: 0..=10000, "Set termination check interval";
+    ternary: 0..=1, "Enable hyper ternary resolution";
+    ternarymaxadd: 0..=10000, "Set max clauses added in percent for ternary";
+    ternarymaxeff: 0..=2000000000, "Set ternary maximum efficiency";
+    ternarymineff: 1..=2000000000, "Set minimum ternary efficiency";
+    ternaryocclim: 1..=2000000000, "Set ternary occurrence limit";
+    ternaryreleff: 1..=100000, "Set relative efficiency per mille for ternary";
+    ternaryrounds: 1..=16, "Set maximum ternary rounds";
+    transred: 0..=1, "Enable transitive reduction of BIG";
+    transredmaxeff: 0..=2000000000, "Set maximum efficiency for transitive reduction";
+    transredmineff: 0..=2000000000, "Set minimum efficiency for transitive reduction";
+    transredreleff: 1..=100000, "Set relative efficiency per mille for transitive reduction";
+    verbose: 0..=3, "Set verbose message level";
+    veripb: 0..=4, "Set VeriPB mode (odd=checkdeletions, > 2=drat)";
+    vivify: 0..=1, "Enable vivification";
+    vivifyinst: 0..=1, "Instantiate last literal when vivifying";
+    vivifymaxeff: 0..=2000000000, "Set maximum efficiency for vivification";
+    vivifymineff: 0..=2000000000, "Set minimum efficiency for vivification";
+    vivifyonce: 0..=2, "Set vivify once mode (1=red, 2=red+irr)";
+    vivifyredeff: 0..=1000, "Set redundant efficiency per mille for vivification";
+    vivifyreleff: 1..=100000, "Set relative efficiency per mille for vivification";
+    walk: 0..=1, "Enable random walks";
+    walkmaxeff: 0..=2000000000, "Set maximum efficiency for random walks";
+    walkmineff: 0..=10000000, "Set minimum efficiency for random walks";
+    walknonstable: 0..=1, "Walk in non-stabilizing phase";
+    walkredundant: 0..=1, "Walk redundant clauses too";
+    walkreleff: 1..=100000, "Set relative efficiency per mille for random walks";
+}
+
+/// A set of CaDiCaL option values, validated against [`CADICAL_OPTIONS`].
+///
+/// Options not set keep the solver's current value. With the `serde` feature a
+/// configuration can be stored as a plain name-to-value map.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CadicalOptions, CaDiCaLSolver};
+/// let options = CadicalOptions::new()
+///     .set("seed", 42)
+///     .and_then(|options| options.set("elim", 0))
+///     .unwrap();
+/// assert!(CadicalOptions::new().set("seed", -1).is_err());
+/// assert!(CadicalOptions::new().set("no-such-option", 1).is_err());
+/// let mut solver = CaDiCaLSolver::new();
+/// solver.apply_options(&options).unwrap();
+/// assert_eq!(solver.get_option("seed").unwrap(), 42);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CadicalOptions {
+    values: BTreeMap<String, i32>,
+}
+
+impl CadicalOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value of every option in a freshly created solver.
+    pub fn defaults() -> Result<Self, SolverError> {
+        let mut solver = CaDiCaLSolver::new();
+        let mut options = Self::new();
+        for info in CADICAL_OPTIONS {
+            options
+                .values
+                .insert(info.name.to_string(), solver.get_option(info.name)?);
+        }
+        Ok(options)
+    }
+
+    /// Set `name` to `value`, rejecting unknown options and out-of-range values.
+    pub fn set(mut self, name: &str, value: i32) -> Result<Self, SolverError> {
+        let info = OptionInfo::find(name).ok_or(SolverError::InvalidArgument("unknown option"))?;
+        info.check(value)?;
+        self.values.insert(info.name.to_string(), value);
+        Ok(self)
+    }
+
+    /// The value set for `name`, if any.
+    pub fn get(&self, name: &str) -> Option<i32> {
+        self.values.get(name).copied()
+    }
+
+    /// The options set, in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, i32)> {
+        self.values
+            .iter()
+            .map(|(name, &value)| (name.as_str(), value))
+    }
+
+    /// Check every value again, e.g. after deserializing.
+    pub fn validate(&self) -> Result<(), SolverError> {
+        for (name, value) in self.iter() {
+            let info =
+                OptionInfo::find(name).ok_or(SolverError::InvalidArgument("unknown option"))?;
+            info.check(value)?;
+        }
+        Ok(())
+    }
+}
+
+impl CaDiCaLSolver {
+    /// Validate and apply every option of `options`.
+    ///
+    /// Nothing is applied if any value is invalid.
+    pub fn apply_options(&mut self, options: &CadicalOptions) -> Result<(), SolverError> {
+        options.validate()?;
+        for (name, value) in options.iter() {
+            self.set_option(name, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn option_table() {
+        assert_eq!(CADICAL_OPTIONS.len(), 179);
+        assert!(CADICAL_OPTIONS
+            .windows(2)
+            .all(|pair| pair[0].name < pair[1].name));
+        let seed = OptionInfo::find("seed").unwrap();
+        assert_eq!((seed.min, seed.max), (0, 2_000_000_000));
+        assert!(OptionInfo::find("arena").unwrap().is_bool());
+
+        let options = CadicalOptions::new()
+            .set("elim", 0)
+            .unwrap()
+            .set("seed", 7)
+            .unwrap();
+        let set: Vec<(&str, i32)> = options.iter().collect();
+        assert_eq!(set, vec![("elim", 0), ("seed", 7)]);
+        assert!(options.clone().set("elim", 2).is_err());
+        let mut solver = CaDiCaLSolver::new();
+        solver.apply_options(&options).unwrap();
+        assert_eq!(solver.get_option("elim").unwrap(), 0);
+        assert_eq!(CadicalOptions::defaults().unwrap().get("elim"), Some(1));
+    }
+}
@@ -0,0 +1,236 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::errors::SolverError;
+
+#[cfg(feature = "glucose")]
+use super::GlucoseSolver;
+#[cfg(feature = "minisat")]
+use super::MinisatSolver;
+#[cfg(feature = "picosat")]
+use super::PicoSATSolver;
+use super::{Backend, SatSolver};
+#[cfg(feature = "cadical")]
+use super::{CaDiCaLSolver, CadicalOptions};
+
+/// A named tuning of a backend, independent of its option names.
+///
+/// # Mapping
+/// * `CaDiCaLSolver` - CaDiCaL's own `plain`, `sat` and `unsat` configurations, see
+///   [`CadicalOptions::preset`].
+/// * `GlucoseSolver` - `Plain` turns off variable elimination. Glucose's defaults already
+///   target UNSAT, and its SAT-oriented `adapt` strategy can only be set globally, so
+///   `Sat` and `Unsat` keep the defaults.
+/// * `MinisatSolver` - `Plain` turns off variable elimination; `Sat` and `Unsat` keep
+///   the defaults.
+/// * `PicoSATSolver` - `Plain` turns off failed literal probing; `Sat` and `Unsat` keep
+///   the defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Preset {
+    /// The backend's default configuration.
+    #[default]
+    Default,
+    /// No preprocessing or inprocessing, plain CDCL search.
+    Plain,
+    /// Tuned for satisfiable instances.
+    Sat,
+    /// Tuned for proving unsatisfiability.
+    Unsat,
+}
+
+impl Preset {
+    /// Every preset, in declaration order.
+    pub const ALL: [Preset; 4] = [Preset::Default, Preset::Plain, Preset::Sat, Preset::Unsat];
+}
+
+impl fmt::Display for Preset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Preset::Default => "default",
+            Preset::Plain => "plain",
+            Preset::Sat => "sat",
+            Preset::Unsat => "unsat",
+        })
+    }
+}
+
+impl FromStr for Preset {
+    type Err = SolverError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Preset::ALL
+            .into_iter()
+            .find(|preset| preset.to_string().eq_ignore_ascii_case(s))
+            .ok_or(SolverError::InvalidArgument("unknown configuration preset"))
+    }
+}
+
+/// A backend-independent solver configuration.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{Backend, Preset, SatSolver, SatStatus, SolverConfig};
+/// let mut solver = SolverConfig::preset(Preset::Unsat).build(Backend::CaDiCaL).unwrap();
+/// solver.push_clause(&[1, 2]).unwrap();
+/// solver.push_clause(&[-1]).unwrap();
+/// assert_eq!(solver.solve_model().unwrap(), SatStatus::Satisfiable(vec![2]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverConfig {
+    pub preset: Preset,
+}
+
+impl SolverConfig {
+    /// A configuration using `preset`.
+    pub fn preset(preset: Preset) -> Self {
+        Self { preset }
+    }
+
+    /// Create a solver of `backend` with this configuration applied.
+    ///
+    /// The solver is ready for incremental use: MiniSat and Glucose eliminate variables
+    /// by default, after which clauses and assumptions over them are invalid, so their
+    /// variable elimination is turned off whatever the preset.
+    pub fn build(&self, backend: Backend) -> Result<Box<dyn SatSolver + Send>, SolverError> {
+        match backend {
+            #[cfg(feature = "cadical")]
+            Backend::CaDiCaL => {
+                let mut solver = CaDiCaLSolver::new();
+                solver.apply_preset(self.preset)?;
+                Ok(Box::new(solver))
+            }
+            #[cfg(feature = "glucose")]
+            Backend::Glucose => {
+                let mut solver = GlucoseSolver::new();
+                solver.apply_preset(self.preset)?;
+                solver.set_opt_use_elim(false)?;
+                Ok(Box::new(solver))
+            }
+            #[cfg(feature = "minisat")]
+            Backend::Minisat => {
+                let mut solver = MinisatSolver::new();
+                solver.apply_preset(self.preset)?;
+                solver.set_use_simp(false);
+                Ok(Box::new(solver))
+            }
+            #[cfg(feature = "picosat")]
+            Backend::PicoSAT => {
+                let mut solver = PicoSATSolver::new();
+                solver.apply_preset(self.preset)?;
+                Ok(Box::new(solver))
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(SolverError::UnsupportedOperation(
+                "this backend, which is not enabled",
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "cadical")]
+impl CadicalOptions {
+    /// The options of CaDiCaL's configuration named like `preset`.
+    pub fn preset(preset: Preset) -> Self {
+        let values: &[(&str, i32)] = match preset {
+            Preset::Default => &[],
+            Preset::Plain => &[
+                ("block", 0),
+                ("compact", 0),
+                ("condition", 0),
+                ("cover", 0),
+                ("decompose", 0),
+                ("deduplicate", 0),
+                ("eagersubsume", 0),
+                ("elim", 0),
+                ("inprocessing", 0),
+                ("instantiate", 0),
+                ("lucky", 0),
+                ("probe", 0),
+                ("subsume", 0),
+                ("ternary", 0),
+                ("transred", 0),
+                ("vivify", 0),
+                ("walk", 0),
+            ],
+            Preset::Sat => &[
+                ("elimreleff", 10),
+                ("stabilizeonly", 1),
+                ("subsumereleff", 60),
+            ],
+            Preset::Unsat => &[("stabilize", 0), ("walk", 0)],
+        };
+        values.iter().fold(Self::new(), |options, &(name, value)| {
+            options
+                .set(name, value)
+                .expect("preset values are in range")
+        })
+    }
+}
+
+#[cfg(feature = "cadical")]
+impl CaDiCaLSolver {
+    /// Apply `preset`, see [`Preset`].
+    pub fn apply_preset(&mut self, preset: Preset) -> Result<(), SolverError> {
+        self.apply_options(&CadicalOptions::preset(preset))
+    }
+}
+
+#[cfg(feature = "glucose")]
+impl GlucoseSolver {
+    /// Apply `preset`, see [`Preset`].
+    pub fn apply_preset(&mut self, preset: Preset) -> Result<(), SolverError> {
+        if preset == Preset::Plain {
+            self.set_opt_use_elim(false)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "minisat")]
+impl MinisatSolver {
+    /// Apply `preset`, see [`Preset`].
+    pub fn apply_preset(&mut self, preset: Preset) -> Result<(), SolverError> {
+        if preset == Preset::Plain {
+            self.set_use_simp(false);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "picosat")]
+impl PicoSATSolver {
+    /// Apply `preset`, see [`Preset`].
+    pub fn apply_preset(&mut self, preset: Preset) -> Result<(), SolverError> {
+        if preset == Preset::Plain {
+            self.set_plain(1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::SatStatus;
+
+    #[test]
+    fn presets() {
+        for preset in Preset::ALL {
+            assert_eq!(preset.to_string().parse::<Preset>().unwrap(), preset);
+            for backend in Backend::ALL {
+                let Ok(mut solver) = SolverConfig::preset(preset).build(backend) else {
+                    assert!(!backend.is_enabled());
+                    continue;
+                };
+                solver.push_clause(&[1, 2]).unwrap();
+                solver.push_clause(&[-1]).unwrap();
+                assert_eq!(
+                    solver.solve_model().unwrap(),
+                    SatStatus::Satisfiable(vec![2])
+                );
+            }
+        }
+        assert!("fast".parse::<Preset>().is_err());
+    }
+}
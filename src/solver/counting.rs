@@ -0,0 +1,182 @@
+use crate::errors::SolverError;
+use crate::rng::Rng;
+
+use super::SatSolver;
+
+/// The result of [`ApproxCounter::count`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ApproxCount {
+    /// The estimated number of models over the projection variables.
+    pub estimate: u64,
+    /// Whether the formula had few enough models to be counted exactly.
+    pub exact: bool,
+}
+
+/// Hash-based approximate model counting (ApproxMC).
+///
+/// Random XOR constraints over the projection variables cut the solution space into
+/// cells of about equal size; a cell small enough to enumerate is counted and scaled
+/// back up. With probability at least `1 - delta`, the estimate is within a factor of
+/// `1 + epsilon` of the true count. Each cell is counted on a fresh solver from the
+/// factory, so the formula is never modified by the XORs.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{ApproxCounter, CaDiCaLSolver};
+/// // x1 ∨ x2 over 12 variables: 3 * 2^10 = 3072 models.
+/// let clauses = vec![vec![1, 2]];
+/// let count = ApproxCounter::new()
+///     .project(&(1..=12).collect::<Vec<_>>())
+///     .count(&clauses, CaDiCaLSolver::new)
+///     .unwrap();
+/// assert!(count.estimate > 3072 * 10 / 18 && count.estimate < 3072 * 18 / 10);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApproxCounter {
+    epsilon: f64,
+    delta: f64,
+    seed: u64,
+    projection: Option<Vec<i32>>,
+}
+
+impl Default for ApproxCounter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApproxCounter {
+    /// A counter with tolerance `0.8` and confidence `0.8`, as in ApproxMC.
+    pub fn new() -> Self {
+        Self {
+            epsilon: 0.8,
+            delta: 0.2,
+            seed: 0,
+            projection: None,
+        }
+    }
+
+    /// Allowed relative error: the estimate is within a factor of `1 + epsilon`.
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Allowed probability of an estimate outside the tolerance.
+    pub fn delta(mut self, delta: f64) -> Self {
+        self.delta = delta;
+        self
+    }
+
+    /// Seed of the random XOR constraints.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Count assignments of `vars` only. Defaults to every variable of the clauses.
+    pub fn project(mut self, vars: &[i32]) -> Self {
+        self.projection = Some(vars.iter().map(|var| var.abs()).collect());
+        self
+    }
+
+    /// Estimate the number of models of `clauses`, building solvers with `factory`.
+    pub fn count<S, F>(
+        &self,
+        clauses: &[Vec<i32>],
+        mut factory: F,
+    ) -> Result<ApproxCount, SolverError>
+    where
+        S: SatSolver,
+        F: FnMut() -> S,
+    {
+        if !(self.epsilon > 0.0 && self.delta > 0.0 && self.delta < 1.0) {
+            let err = SolverError::InvalidArgument("epsilon must be positive and delta in (0, 1)");
+            return Err(err);
+        }
+        let vars = match &self.projection {
+            Some(vars) => vars.clone(),
+            None => {
+                let max_var = clauses
+                    .iter()
+                    .flatten()
+                    .map(|lit| lit.abs())
+                    .max()
+                    .unwrap_or(0);
+                (1..=max_var).collect()
+            }
+        };
+        let ratio = 1.0 + 1.0 / self.epsilon;
+        let threshold =
+            (1.0 + 9.84 * (1.0 + self.epsilon / (1.0 + self.epsilon)) * ratio * ratio) as u64;
+
+        let mut cell = |xors: &[(Vec<i32>, bool)]| -> Result<Option<u64>, SolverError> {
+            let mut solver = factory();
+            for clause in clauses {
+                solver.push_clause(clause)?;
+            }
+            for (lits, rhs) in xors {
+                solver.add_xor(lits, *rhs)?;
+            }
+            Ok(solver.count_projected_models(&vars, threshold)?.exact)
+        };
+        if let Some(exact) = cell(&[])? {
+            return Ok(ApproxCount {
+                estimate: exact,
+                exact: true,
+            });
+        }
+
+        let rounds = (17.0 * (3.0 / self.delta).log2()).ceil() as usize;
+        let mut rng = Rng::new(self.seed);
+        let mut estimates = Vec::with_capacity(rounds);
+        for _ in 0..rounds {
+            let mut xors: Vec<(Vec<i32>, bool)> = vec![];
+            // Add XORs until a cell has fewer than `threshold` models.
+            while xors.len() < vars.len() {
+                let lits = vars.iter().copied().filter(|_| rng.chance(0.5)).collect();
+                xors.push((lits, rng.chance(0.5)));
+                if let Some(count) = cell(&xors)? {
+                    estimates.push(count << xors.len());
+                    break;
+                }
+            }
+        }
+        estimates.sort_unstable();
+        Ok(ApproxCount {
+            estimate: estimates.get(estimates.len() / 2).copied().unwrap_or(0),
+            exact: false,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "picosat"))]
+mod tests {
+    use super::*;
+    use crate::solver::PicoSATSolver;
+
+    #[test]
+    fn approx_count() {
+        let small = ApproxCounter::new()
+            .count(&[vec![1, 2]], PicoSATSolver::new)
+            .unwrap();
+        assert_eq!(
+            small,
+            ApproxCount {
+                estimate: 3,
+                exact: true
+            }
+        );
+
+        let clauses = vec![vec![1, 2], vec![-3, 4]];
+        let vars: Vec<i32> = (1..=10).collect();
+        let count = ApproxCounter::new()
+            .project(&vars)
+            .seed(3)
+            .count(&clauses, PicoSATSolver::new)
+            .unwrap();
+        // 3/4 * 3/4 * 2^10 = 576 models.
+        assert!(!count.exact);
+        assert!(count.estimate > 576 * 10 / 18 && count.estimate < 576 * 18 / 10);
+    }
+}
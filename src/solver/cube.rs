@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::errors::SolverError;
+
+use super::{CaDiCaLSolver, RawStatus, SatSolver};
+
+/// Result of [`CubeAndConquer::solve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CubeStatus {
+    /// `cube` is the cube whose worker found `model`.
+    Satisfiable {
+        cube: Vec<i32>,
+        model: Vec<i32>,
+    },
+    Unsatisfiable,
+    Unknown,
+}
+
+/// A cube-and-conquer driver over `CaDiCaLSolver` workers.
+///
+/// The formula is split into cubes over the `depth` highest-scoring variables, and the
+/// cubes are solved as assumptions by a pool of worker threads, each with its own
+/// solver. The first satisfiable cube stops all workers.
+///
+/// `satgalaxy-core` does not export CaDiCaL's lookahead cube generation, so variables
+/// are scored with the Jeroslow-Wang heuristic instead (occurrences weighted by `2^-len`
+/// of their clauses). With a nonzero cutoff, each cube is first checked under that
+/// conflict limit while cubing, and refuted cubes are dropped.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CubeAndConquer, CubeStatus};
+/// let mut cnc = CubeAndConquer::new();
+/// cnc.depth(2).threads(2);
+/// cnc.push_clause(&[1, 2]);
+/// cnc.push_clause(&[-1, 3]);
+/// cnc.push_clause(&[-2, -3]);
+/// match cnc.solve().unwrap() {
+///     CubeStatus::Satisfiable { model, .. } => assert!(model.contains(&1) || model.contains(&2)),
+///     other => panic!("unexpected {other:?}"),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CubeAndConquer {
+    clauses: Vec<Vec<i32>>,
+    depth: usize,
+    cutoff: i32,
+    threads: usize,
+}
+
+impl Default for CubeAndConquer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CubeAndConquer {
+    pub fn new() -> Self {
+        Self {
+            clauses: vec![],
+            depth: 4,
+            cutoff: 0,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    /// Set the number of variables each cube fixes; at most `2^depth` cubes are made.
+    pub fn depth(&mut self, depth: usize) -> &mut Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Set the conflict limit used to refute cubes while cubing; `0` disables it.
+    pub fn cutoff(&mut self, conflicts: i32) -> &mut Self {
+        self.cutoff = conflicts;
+        self
+    }
+
+    /// Set the number of worker threads.
+    pub fn threads(&mut self, threads: usize) -> &mut Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn push_clause(&mut self, clause: &[i32]) -> &mut Self {
+        self.clauses.push(clause.to_vec());
+        self
+    }
+
+    fn new_solver(&self) -> Result<CaDiCaLSolver, SolverError> {
+        let mut solver = CaDiCaLSolver::new();
+        for clause in &self.clauses {
+            solver.push_clause(clause)?;
+        }
+        Ok(solver)
+    }
+
+    /// The variables to split on, by descending Jeroslow-Wang score.
+    fn split_vars(&self) -> Vec<i32> {
+        let mut scores: HashMap<i32, f64> = HashMap::new();
+        for clause in &self.clauses {
+            let weight = 0.5f64.powi(clause.len().min(64) as i32);
+            for lit in clause {
+                *scores.entry(lit.abs()).or_default() += weight;
+            }
+        }
+        let mut vars: Vec<(i32, f64)> = scores.into_iter().collect();
+        vars.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        vars.into_iter()
+            .take(self.depth)
+            .map(|(var, _)| var)
+            .collect()
+    }
+
+    /// Generate the cubes that `solve` distributes over the workers.
+    pub fn cubes(&self) -> Result<Vec<Vec<i32>>, SolverError> {
+        let mut cubes = vec![vec![]];
+        let mut refuter = match self.cutoff {
+            0 => None,
+            _ => Some(self.new_solver()?),
+        };
+        for var in self.split_vars() {
+            let mut next = Vec::with_capacity(cubes.len() * 2);
+            for cube in cubes {
+                for lit in [var, -var] {
+                    let mut cube = cube.clone();
+                    cube.push(lit);
+                    if let Some(solver) = refuter.as_mut() {
+                        solver.limit(c"conflicts".as_ptr(), self.cutoff)?;
+                        if solver.solve_with_assumptions(&cube)? == RawStatus::Unsatisfiable {
+                            continue;
+                        }
+                    }
+                    next.push(cube);
+                }
+            }
+            cubes = next;
+        }
+        Ok(cubes)
+    }
+
+    /// Cube the formula and solve the cubes in parallel.
+    pub fn solve(&self) -> Result<CubeStatus, SolverError> {
+        let cubes = self.cubes()?;
+        let next = AtomicUsize::new(0);
+        let found = Arc::new(AtomicBool::new(false));
+        let result: Mutex<Option<CubeStatus>> = Mutex::new(None);
+        let unknown = AtomicBool::new(false);
+        thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads.min(cubes.len()))
+                .map(|_| {
+                    scope.spawn(|| -> Result<(), SolverError> {
+                        let mut solver = self.new_solver()?;
+                        let found_flag = Arc::clone(&found);
+                        solver
+                            .set_terminate(Box::new(move || found_flag.load(Ordering::Relaxed)))?;
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            if index >= cubes.len() || found.load(Ordering::Relaxed) {
+                                return Ok(());
+                            }
+                            match solver.solve_with_assumptions(&cubes[index])? {
+                                RawStatus::Satisfiable => {
+                                    if !found.swap(true, Ordering::Relaxed) {
+                                        *result.lock().unwrap() = Some(CubeStatus::Satisfiable {
+                                            cube: cubes[index].clone(),
+                                            model: solver.model()?,
+                                        });
+                                    }
+                                    return Ok(());
+                                }
+                                RawStatus::Unsatisfiable => {}
+                                RawStatus::Unknown => unknown.store(true, Ordering::Relaxed),
+                            }
+                        }
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .try_for_each(|worker| worker.join().unwrap())
+        })?;
+        Ok(match result.into_inner().unwrap() {
+            Some(status) => status,
+            None if unknown.load(Ordering::Relaxed) => CubeStatus::Unknown,
+            None => CubeStatus::Unsatisfiable,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pigeonhole_unsat() {
+        let (pigeons, holes) = (5, 4);
+        let var = |p: i32, h: i32| p * holes + h + 1;
+        let mut cnc = CubeAndConquer::new();
+        cnc.depth(3).cutoff(10).threads(3);
+        for p in 0..pigeons {
+            cnc.push_clause(&(0..holes).map(|h| var(p, h)).collect::<Vec<_>>());
+        }
+        for h in 0..holes {
+            for p in 0..pigeons {
+                for q in p + 1..pigeons {
+                    cnc.push_clause(&[-var(p, h), -var(q, h)]);
+                }
+            }
+        }
+        assert!(cnc.cubes().unwrap().len() <= 8);
+        assert_eq!(cnc.solve().unwrap(), CubeStatus::Unsatisfiable);
+    }
+
+    #[test]
+    fn reports_cube() {
+        let mut cnc = CubeAndConquer::new();
+        cnc.depth(2).threads(2);
+        cnc.push_clause(&[1, 2])
+            .push_clause(&[-1])
+            .push_clause(&[2, 3]);
+        let CubeStatus::Satisfiable { cube, model } = cnc.solve().unwrap() else {
+            panic!("expected SAT");
+        };
+        assert_eq!(cube.len(), 2);
+        assert!(model.contains(&2));
+        for lit in cube {
+            assert_eq!(model.contains(&lit.abs()), lit > 0);
+        }
+    }
+}
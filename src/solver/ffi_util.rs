@@ -0,0 +1,124 @@
+//! Helpers for reading arrays returned by the C solvers and calling back into Rust.
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// The literals of the 0-terminated array at `ptr`, without the terminating 0.
+///
+/// A null `ptr` is read as an empty array. Where null means something else, e.g.
+/// "no more sets" for PicoSAT's enumeration functions, check it before calling.
+///
+/// # Safety
+/// `ptr` must be null or point to a 0-terminated array of `i32` that is neither freed
+/// nor modified during `'a`.
+#[cfg(feature = "picosat")]
+pub(crate) unsafe fn zero_terminated<'a>(ptr: *const i32) -> &'a [i32] {
+    if ptr.is_null() {
+        return &[];
+    }
+    let mut len = 0;
+    while *ptr.add(len) != 0 {
+        len += 1;
+    }
+    std::slice::from_raw_parts(ptr, len)
+}
+
+/// A Rust closure passed to a C solver as the `state` of a callback.
+///
+/// Unwinding into C is undefined behavior, so the callback trampoline calls the
+/// closure through [`Callback::call`], which catches a panic and stops calling it.
+/// `Callback::finish` or [`Callback::take_panic`] hands the panic back once the
+/// solver has returned.
+pub(crate) struct Callback<F> {
+    closure: F,
+    panic: Option<Box<dyn Any + Send>>,
+}
+
+impl<F> Callback<F> {
+    pub(crate) fn new(closure: F) -> Self {
+        Callback {
+            closure,
+            panic: None,
+        }
+    }
+
+    /// Call the closure through `call`, unless an earlier call panicked.
+    ///
+    /// Returns `None` if this or an earlier call panicked.
+    pub(crate) fn call<R>(&mut self, call: impl FnOnce(&mut F) -> R) -> Option<R> {
+        if self.panic.is_some() {
+            return None;
+        }
+        let closure = &mut self.closure;
+        match panic::catch_unwind(AssertUnwindSafe(|| call(closure))) {
+            Ok(value) => Some(value),
+            Err(panic) => {
+                self.panic = Some(panic);
+                None
+            }
+        }
+    }
+
+    /// The panic of the closure, if it panicked; the closure is called again afterwards.
+    ///
+    /// For callbacks that outlive a single solve, which `finish` cannot consume.
+    pub(crate) fn take_panic(&mut self) -> Option<Box<dyn Any + Send>> {
+        self.panic.take()
+    }
+
+    /// Resume the panic of the closure, if it panicked.
+    #[cfg(feature = "picosat")]
+    pub(crate) fn finish(mut self) {
+        if let Some(panic) = self.take_panic() {
+            panic::resume_unwind(panic);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "picosat")]
+    #[test]
+    fn reads_until_zero() {
+        let array = [3, -1, 0, 7];
+        assert_eq!(unsafe { zero_terminated(array.as_ptr()) }, [3, -1]);
+        assert_eq!(
+            unsafe { zero_terminated(array[2..].as_ptr()) },
+            [] as [i32; 0]
+        );
+        assert_eq!(unsafe { zero_terminated(std::ptr::null()) }, [] as [i32; 0]);
+    }
+
+    #[cfg(feature = "picosat")]
+    #[test]
+    fn callback_panics_after_finish() {
+        let mut calls = 0;
+        let mut callback = Callback::new(|n: i32| {
+            calls += 1;
+            assert!(n < 2, "{n} is too large");
+        });
+        for n in 0..4 {
+            callback.call(|closure| closure(n));
+        }
+        let panic = panic::catch_unwind(AssertUnwindSafe(|| callback.finish())).unwrap_err();
+        assert_eq!(panic.downcast_ref::<String>().unwrap(), "2 is too large");
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn callback_stops_after_panic() {
+        let mut callback = Callback::new(|n: i32| {
+            assert!(n < 2, "{n} is too large");
+            n
+        });
+        assert_eq!(callback.call(|closure| closure(1)), Some(1));
+        assert_eq!(callback.call(|closure| closure(2)), None);
+        assert_eq!(callback.call(|closure| closure(1)), None);
+        let panic = callback.take_panic().unwrap();
+        assert_eq!(panic.downcast_ref::<String>().unwrap(), "2 is too large");
+        assert_eq!(callback.call(|closure| closure(1)), Some(1));
+    }
+}
@@ -0,0 +1,164 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use crate::errors::SolverError;
+
+use super::{SatSolver, SatStatus};
+
+/// What a [`SolveFuture`] resolves to: the solver, handed back for further use, and the
+/// result of `solve_model`.
+pub type SolveOutput<S> = (S, Result<SatStatus, SolverError>);
+
+struct Shared<S> {
+    output: Option<SolveOutput<S>>,
+    waker: Option<Waker>,
+}
+
+/// A `solve_model` running on a background thread, see [`AsyncSolve::solve_async`].
+///
+/// Dropping the future (e.g. when a request handler is cancelled) or calling `cancel`
+/// asks the solve to stop through `SatSolver::set_terminate`. Backends without
+/// termination support run to completion in the background and their result is dropped.
+pub struct SolveFuture<S> {
+    shared: Arc<Mutex<Shared<S>>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<S> SolveFuture<S> {
+    /// Ask the solve to stop; it then resolves with `SatStatus::Unknown`.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl<S> Future for SolveFuture<S> {
+    type Output = SolveOutput<S>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut shared = self.shared.lock().unwrap();
+        match shared.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<S> Drop for SolveFuture<S> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Solving without blocking an async executor.
+///
+/// Implemented for every `Send` solver. The solver moves to a dedicated thread, so the
+/// blocking FFI call never runs on an executor thread, and comes back with the result.
+/// No particular runtime is required.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{AsyncSolve, CaDiCaLSolver, SatSolver, SatStatus};
+/// # async fn run() {
+/// let mut solver = CaDiCaLSolver::new();
+/// solver.push_clause(&[1, 2]).unwrap();
+/// solver.push_clause(&[-1]).unwrap();
+/// let (solver, status) = solver.solve_async().await;
+/// assert_eq!(status.unwrap(), SatStatus::Satisfiable(vec![2]));
+/// # }
+/// ```
+pub trait AsyncSolve: SatSolver + Send + Sized + 'static {
+    /// Run `solve_model` on a background thread.
+    ///
+    /// A termination callback wired to the future's cancellation is installed for the
+    /// solve and replaced by one that never fires before the solver is handed back.
+    fn solve_async(mut self) -> SolveFuture<Self> {
+        let shared = Arc::new(Mutex::new(Shared {
+            output: None,
+            waker: None,
+        }));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let (result, stop) = (Arc::clone(&shared), Arc::clone(&cancelled));
+        thread::spawn(move || {
+            // Backends without termination support simply run to completion.
+            let _ = self.set_terminate(Box::new(move || stop.load(Ordering::Relaxed)));
+            let status = self.solve_model();
+            let _ = self.set_terminate(Box::new(|| false));
+            let waker = {
+                let mut result = result.lock().unwrap();
+                result.output = Some((self, status));
+                result.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        });
+        SolveFuture { shared, cancelled }
+    }
+}
+
+impl<S: SatSolver + Send + 'static> AsyncSolve for S {}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+    use crate::solver::CaDiCaLSolver;
+    use std::task::Wake;
+
+    struct Unpark(thread::Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn solve_async() {
+        let mut solver = CaDiCaLSolver::new();
+        SatSolver::push_clause(&mut solver, &[1, 2]).unwrap();
+        SatSolver::push_clause(&mut solver, &[-1]).unwrap();
+        let (mut solver, status) = block_on(solver.solve_async());
+        assert_eq!(status.unwrap(), SatStatus::Satisfiable(vec![2]));
+        SatSolver::push_clause(&mut solver, &[-2]).unwrap();
+        let (_, status) = block_on(solver.solve_async());
+        assert_eq!(status.unwrap(), SatStatus::Unsatisfiable);
+
+        // Pigeonhole with 11 pigeons and 10 holes is far too hard to finish.
+        let mut hard = CaDiCaLSolver::new();
+        let var = |pigeon: i32, hole: i32| pigeon * 10 + hole + 1;
+        for pigeon in 0..11 {
+            SatSolver::push_clause(
+                &mut hard,
+                &(0..10).map(|h| var(pigeon, h)).collect::<Vec<_>>(),
+            )
+            .unwrap();
+        }
+        for hole in 0..10 {
+            for a in 0..11 {
+                for b in a + 1..11 {
+                    SatSolver::push_clause(&mut hard, &[-var(a, hole), -var(b, hole)]).unwrap();
+                }
+            }
+        }
+        let future = hard.solve_async();
+        future.cancel();
+        assert_eq!(block_on(future).1.unwrap(), SatStatus::Unknown);
+    }
+}
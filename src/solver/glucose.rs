@@ -6,6 +6,12 @@
 //! The `GlucoseSolver` struct acts as a wrapper for the [Glucose](https://github.com/audemard/glucose) Solver, allowing users to
 //! leverage its functionality for solving SAT problems.
 //!
+//! # Proofs
+//! Glucose can write certified-UNSAT (DRUP) proofs, but `satgalaxy-core` does not export
+//! its `certifiedUNSAT` option or a proof output stream, so `GlucoseSolver` cannot produce
+//! them yet. `PicoSATSolver::write_trace` with `TraceFormat::Rup` is the backend that
+//! currently writes checkable proofs.
+//!
 //! # Usage
 //! To use the `glucose` module, ensure the `glucose` feature is enabled in your `Cargo.toml`:
 //! ```toml
@@ -20,11 +26,13 @@
 mod bindings {
     include!("../../bindings/glucose_bindings.rs");
 }
+use std::collections::BTreeSet;
 use std::ptr::NonNull;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 use crate::{
     errors::SolverError,
-    solver::{RawStatus, SatSolver},
+    solver::{check_clause, shrink_core, ModelBitset, RawStatus, SatSolver, SolverStats},
 };
 
 /// `GlucoseSolver` is a wrapper for the [Glucose](https://github.com/audemard/glucose) SimpSolver.
@@ -54,11 +62,28 @@ use crate::{
 ///  ```toml
 ///  [dependencies]
 ///  satgalaxy = { version = "x.y.z", features = ["glucose"] }
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct GlucoseSolver {
     inner: NonNull<bindings::GlucoseSolver>,
+    /// Variables that must survive simplification, see `set_frozen`.
+    frozen: BTreeSet<i32>,
+    /// Assumptions of the last solve if it returned UNSAT, empty after `solve_sat`.
+    unsat_assumptions: Option<Vec<i32>>,
+    /// Whether `unsat_core` has already shrunk `unsat_assumptions` to a minimal core.
+    core_shrunk: bool,
+    /// The clauses added so far, kept while solving may still eliminate variables;
+    /// `None` once elimination is off for good, see `add_clause`.
+    history: Option<Vec<Vec<i32>>>,
+    /// Whether a solve has eliminated variables since the instance was built.
+    eliminated: bool,
+    /// The options this instance was built with or given since, applied again by
+    /// `reset`.
+    options: GlucoseOptions,
 }
-unsafe impl Sync for GlucoseSolver {}
+// SAFETY: `inner` is owned by this value alone and Glucose keeps no thread-local
+// state; its C++ globals are only read while building an instance, under the
+// process-wide lock of `globals`. Glucose takes no callbacks, and the other fields
+// are plain Rust data.
 unsafe impl Send for GlucoseSolver {}
 impl Default for GlucoseSolver {
     fn default() -> Self {
@@ -73,7 +98,12 @@ macro_rules! glucose_opt_set {
     ($name:ident,$ffi_name:ident,$type:ty,$doc:expr) => {
         paste::paste! {
             #[doc=$doc]
+            #[deprecated(note = "global options race with solvers built on other threads; use `GlucoseSolver::with_options` or the `set_opt_*` method")]
             pub fn [<set_global_opt_$name>](value: $type) -> Result<(), SolverError> {
+                if !$crate::solver::OptValue::is_valid(value) {
+                    return Err(SolverError::InvalidArgument("option value must be finite"));
+                }
+                let _globals = GlucoseSolver::globals();
                 let code = unsafe {
                      bindings::[<glucose_set_global_opt_$ffi_name>](value.into())
                     };
@@ -86,6 +116,9 @@ macro_rules! glucose_opt_set {
 
             #[doc=$doc]
             pub fn [<set_opt_$name>](&mut self, value: $type) -> Result<(), SolverError> {
+                if !$crate::solver::OptValue::is_valid(value) {
+                    return Err(SolverError::InvalidArgument("option value must be finite"));
+                }
                 let code = unsafe {
                      bindings::[<glucose_set_opt_$ffi_name>](self.inner.as_ptr(),value.into())
                     };
@@ -93,6 +126,7 @@ macro_rules! glucose_opt_set {
                 if code!=0{
                     GlucoseSolver::error_msg(code)?;
                 }
+                self.options.$name = Some(value);
                 Ok(())
             }
         }
@@ -106,7 +140,12 @@ macro_rules! glucose_opt_g_set {
     ($name:ident,$ffi_name:ident,$type:ty,$doc:expr) => {
         paste::paste! {
             #[doc=$doc]
+            #[deprecated(note = "global options race with solvers built on other threads; use `GlucoseSolver::with_options`")]
             pub fn [<set_global_opt_$name>](value: $type) -> Result<(), SolverError> {
+                if !$crate::solver::OptValue::is_valid(value) {
+                    return Err(SolverError::InvalidArgument("option value must be finite"));
+                }
+                let mut globals = GlucoseSolver::globals();
                 let code = unsafe {
                      bindings::[<glucose_set_global_opt_$ffi_name>](value.into())
                     };
@@ -114,12 +153,136 @@ macro_rules! glucose_opt_g_set {
                 if code!=0{
                     GlucoseSolver::error_msg(code)?;
                 }
+                globals.$name = value;
                 Ok(())
             }
         }
     };
 }
 
+macro_rules! glucose_options {
+    (
+        instance { $($name:ident: $type:ty, $doc:literal;)* }
+        global { $($g_name:ident: $g_type:ty = $default:expr, $g_doc:literal;)* }
+    ) => {
+        /// Options of one `GlucoseSolver`, applied by [`GlucoseSolver::with_options`].
+        ///
+        /// `None` keeps Glucose's default. Valid ranges are those of the matching
+        /// `set_opt_*` methods.
+        /// # Example
+        /// ```rust
+        /// use satgalaxy::solver::{GlucoseOptions, GlucoseSolver};
+        /// let options = GlucoseOptions {
+        ///     luby_restart: Some(true),
+        ///     use_elim: Some(false),
+        ///     ..Default::default()
+        /// };
+        /// let solver = GlucoseSolver::with_options(&options).unwrap();
+        /// ```
+        #[derive(Debug, Clone, Copy, PartialEq, Default)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct GlucoseOptions {
+            $(#[doc = $doc] pub $name: Option<$type>,)*
+            $(#[doc = $g_doc] pub $g_name: Option<$g_type>,)*
+        }
+
+        /// The options Glucose only reads from its globals, when a solver is built.
+        #[derive(Debug, Clone, Copy)]
+        struct GlobalOptions {
+            $($g_name: $g_type,)*
+        }
+
+        static GLOBAL_OPTIONS: Mutex<GlobalOptions> = Mutex::new(GlobalOptions {
+            $($g_name: $default,)*
+        });
+
+        impl GlobalOptions {
+            /// `self` with the global-only options of `options` applied.
+            fn with(mut self, options: &GlucoseOptions) -> Self {
+                $(if let Some(value) = options.$g_name {
+                    self.$g_name = value;
+                })*
+                self
+            }
+
+            /// Write every value into Glucose's globals.
+            fn install(&self) -> Result<(), SolverError> {
+                paste::paste! {
+                    $(
+                        if !$crate::solver::OptValue::is_valid(self.$g_name) {
+                            return Err(SolverError::InvalidArgument("option value must be finite"));
+                        }
+                        let code = unsafe {
+                            bindings::[<glucose_set_global_opt_$g_name>](self.$g_name.into())
+                        };
+                        if code != 0 {
+                            GlucoseSolver::error_msg(code)?;
+                        }
+                    )*
+                }
+                Ok(())
+            }
+        }
+
+        impl GlucoseSolver {
+            fn apply_options(&mut self, options: &GlucoseOptions) -> Result<(), SolverError> {
+                paste::paste! {
+                    $(if let Some(value) = options.$name {
+                        self.[<set_opt_$name>](value)?;
+                    })*
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+glucose_options! {
+    instance {
+        k: f64, "The constant used to force restart, in (0, 1).";
+        r: f64, "The constant used to block restart, in (0, 5).";
+        size_lbd_queue: i32, "The size of moving average for LBD (restarts), at least 10.";
+        size_trail_queue: i32, "The size of moving average for trail (block restarts), at least 10.";
+        first_reduce_db: i32, "The number of conflicts before first reduce DB.";
+        inc_reduce_db: i32, "Increment for reduce DB.";
+        lb_lbd_frozen_clause: i32, "Protect clauses if LBD decreases below this.";
+        chanseok_limit: i32, "Chanseok: permanent clauses with LBD<=limit, above 1.";
+        lb_size_minimzing_clause: i32, "Min size required to minimize clause, at least 3.";
+        lb_lbd_minimzing_clause: i32, "Min LBD required to minimize clause, at least 3.";
+        lcm: bool, "Use inprocessing vivif (ijcai17).";
+        lcm_update_lbd: bool, "Update LBD when doing LCM.";
+        var_decay: f64, "Variable activity decay factor, in (0, 1).";
+        max_var_decay: f64, "Max variable activity decay factor, in (0, 1).";
+        clause_decay: f64, "Clause activity decay factor, in (0, 1).";
+        random_var_freq: f64, "Frequency for random variable selection, in [0, 1].";
+        random_seed: f64, "Seed for random variable selection, positive.";
+        ccmin_mode: i32, "Conflict clause minimization (0=none,1=basic,2=deep).";
+        phase_saving: i32, "Phase saving (0=none,1=basic,2=deep).";
+        rnd_init_act: bool, "Randomize initial activity.";
+        garbage_frac: f64, "Memory waste allowed before GC, positive.";
+        use_asymm: bool, "Shrink clauses by asymmetric branching.";
+        use_rcheck: bool, "Check if clause is already implied.";
+        use_elim: bool, "Perform variable elimination.";
+        grow: i32, "Allow clause growth in elimination.";
+        clause_lim: i32, "Max resolvent length for elimination (-1=no limit).";
+        subsumption_lim: i32, "Max clause size for subsumption (-1=no limit).";
+        simp_garbage_frac: f64, "Memory waste allowed during simplification, positive.";
+        verbosity: i32, "Verbosity level (0=silent,1=some,2=more).";
+    }
+    global {
+        spec_inc_reduce_db: i32 = 1000, "Special increment for reduce DB.";
+        chanseok_hack: bool = false, "Use Chanseok Oh strategy for LBD.";
+        glu_reduction: bool = true, "Glucose reduction strategy.";
+        luby_restart: bool = false, "Use Luby restart sequence.";
+        restart_inc: f64 = 2.0, "Restart interval increase factor, at least 1.";
+        luby_restart_factor: i32 = 100, "Luby restart factor, positive.";
+        randomize_phase_on_restarts: i32 = 0, "Randomization level on restarts (0-3).";
+        fixed_randomize_phase_on_restarts: bool = false, "Fix first 7 levels at random phase.";
+        adapt: bool = true, "Adapt strategies after 100000 conflicts.";
+        forceunsat: bool = true, "Force phase for UNSAT.";
+    }
+}
+
 macro_rules! ffi_bind {
     (
         $(#[$doc:meta])*
@@ -153,11 +316,14 @@ macro_rules! ffi_bind {
 
 impl GlucoseSolver {
     fn error_msg(code: i32) -> Result<(), SolverError> {
-        unsafe {
-            let msg: *const ::std::os::raw::c_char = bindings::glucose_error_msg(code);
-            let msg = std::ffi::CStr::from_ptr(msg);
-            return Err(SolverError(msg.to_str().unwrap()));
-        }
+        unsafe { Err(SolverError::ffi(code, bindings::glucose_error_msg(code))) }
+    }
+    /// Lock Glucose's global options. Solvers read them when built, so construction
+    /// and every global write hold this lock.
+    fn globals() -> MutexGuard<'static, GlobalOptions> {
+        GLOBAL_OPTIONS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
     }
     fn error(&mut self) -> Result<(), SolverError> {
         unsafe {
@@ -295,30 +461,147 @@ impl GlucoseSolver {
     );
 
     pub fn new() -> Self {
+        let _globals = GlucoseSolver::globals();
         unsafe {
             GlucoseSolver {
                 inner: NonNull::new(bindings::glucose_new_solver()).unwrap(),
+                frozen: BTreeSet::new(),
+                unsat_assumptions: None,
+                core_shrunk: false,
+                history: Some(vec![]),
+                eliminated: false,
+                options: GlucoseOptions::default(),
             }
         }
     }
+
+    /// Create a solver with `options`, leaving other solvers unaffected.
+    ///
+    /// Some options (e.g. `luby_restart` or `adapt`) only exist as C++ globals that
+    /// Glucose reads when a solver is built. They are written, the solver is built and
+    /// they are restored while holding a process-wide lock, so this is safe to call from
+    /// several threads. The remaining options are set on the new instance.
+    pub fn with_options(options: &GlucoseOptions) -> Result<Self, SolverError> {
+        let globals = GlucoseSolver::globals();
+        let installed = globals.with(options).install();
+        let solver = installed.map(|()| unsafe {
+            GlucoseSolver {
+                inner: NonNull::new(bindings::glucose_new_solver()).unwrap(),
+                frozen: BTreeSet::new(),
+                unsat_assumptions: None,
+                core_shrunk: false,
+                history: Some(vec![]),
+                eliminated: false,
+                options: *options,
+            }
+        });
+        globals.install()?;
+        drop(globals);
+        let mut solver = solver?;
+        solver.apply_options(options)?;
+        Ok(solver)
+    }
     ffi_bind! {
         /// Add a new variable to the solver.
         glucose_new_var() -> i32;
         as new_var
     }
 
-    /// Add a clause to the solver.
+    /// Protect `var` from variable elimination, or release it again.
+    ///
+    /// Freeze the variables that later clauses or assumptions will mention. The core
+    /// does not export Glucose's `setFrozen`, so elimination is skipped entirely while
+    /// any variable is frozen. Eliminated variables still get values in the model:
+    /// Glucose's `extendModel` reconstructs them after every SAT answer.
+    pub fn set_frozen(&mut self, var: i32, frozen: bool) {
+        if frozen {
+            self.frozen.insert(var.abs());
+        } else {
+            self.frozen.remove(&var.abs());
+        }
+    }
+
+    /// Whether `var` is protected from variable elimination.
+    pub fn is_frozen(&self, var: i32) -> bool {
+        self.frozen.contains(&var.abs())
+    }
+
+    /// The largest variable index Glucose accepts, since literals are encoded as `2 * var + sign`.
+    pub const MAX_VAR: i32 = i32::MAX / 2;
+
+    /// Add a clause to the solver, without validating its literals.
+    ///
+    /// This is the unchecked fast path: a 0 literal ends the clause early at the C level.
+    /// `SatSolver::push_clause` rejects 0 and variables above `MAX_VAR` first.
+    ///
+    /// Eliminated variables must not appear in clauses or assumptions added after a
+    /// solve. Unless `use_elim` is off, the solver keeps a copy of its clauses, and the
+    /// first clause or assumption after an eliminating solve rebuilds the instance from
+    /// that copy, dropping the learned clauses, and turns elimination off for good.
+    /// Turn `use_elim` off before adding clauses when solving incrementally to avoid
+    /// both the copy and the rebuild.
     pub fn add_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        self.track_clause(clause)?;
         unsafe {
             bindings::glucose_add_clause(self.inner.as_ptr(), clause.as_ptr(), clause.len());
         }
         self.error()?;
         Ok(())
     }
-    ffi_bind! {
-        /// Add the empty clause to the solver.
-        glucose_add_empty_clause() -> i32;
-        as add_empty_clause
+    /// Add the empty clause to the solver.
+    pub fn add_empty_clause(&mut self) -> Result<i32, SolverError> {
+        self.track_clause(&[])?;
+        let ret = unsafe { bindings::glucose_add_empty_clause(self.inner.as_ptr()) };
+        self.error()?;
+        Ok(ret)
+    }
+
+    /// Whether solving may simplify, i.e. elimination is enabled and nothing is frozen.
+    fn simp(&self) -> bool {
+        self.frozen.is_empty() && self.history.is_some()
+    }
+
+    /// Keep `clause` for `restore_eliminated`, first bringing back the variables an
+    /// earlier solve eliminated, since `clause` may mention them.
+    fn track_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        if self.eliminated {
+            self.restore_eliminated()?;
+        }
+        if self.options.use_elim == Some(false) {
+            self.history = None;
+        }
+        if let Some(history) = &mut self.history {
+            // A 0 literal ends the clause at the C level.
+            let end = clause
+                .iter()
+                .position(|&lit| lit == 0)
+                .unwrap_or(clause.len());
+            history.push(clause[..end].to_vec());
+        }
+        Ok(())
+    }
+
+    /// Replace the instance by a fresh one holding the clauses added so far, without
+    /// the eliminations of earlier solves, and stop eliminating.
+    fn restore_eliminated(&mut self) -> Result<(), SolverError> {
+        self.eliminated = false;
+        let Some(clauses) = self.history.take() else {
+            return Ok(());
+        };
+        let vars = self.nvars()?;
+        let mut fresh = GlucoseSolver::with_options(&self.options)?;
+        // `fresh` takes the old instance and destroys it.
+        std::mem::swap(&mut self.inner, &mut fresh.inner);
+        while self.nvars()? < vars {
+            self.new_var()?;
+        }
+        for clause in &clauses {
+            unsafe {
+                bindings::glucose_add_clause(self.inner.as_ptr(), clause.as_ptr(), clause.len());
+            }
+            self.error()?;
+        }
+        Ok(())
     }
 
     ffi_bind! {
@@ -445,19 +728,46 @@ impl GlucoseSolver {
 
     ffi_bind! {
         /// Check if the solver is okay.
-        glucose_okay() -> i32;
+        glucose_okay() -> bool => |res| res != 0;
         as okay
     }
+
+    /// The final conflict clause of the last solve: the negation of the assumptions that
+    /// made it UNSAT.
+    ///
+    /// `satgalaxy-core` does not export the solver's own `conflict` vector, so this is
+    /// the negated `unsat_core`. The first call after a solve shrinks the assumptions by
+    /// deletion, re-solving once per assumption; later calls return the cached core.
+    /// Those solves replace the model and status of the last solve, so read the model
+    /// before asking for the conflict. Returns an error if the last solve was not UNSAT.
+    pub fn conflict(&mut self) -> Result<Vec<i32>, SolverError> {
+        Ok(self.unsat_core()?.into_iter().map(|lit| -lit).collect())
+    }
+
+    /// Remember `assumptions` for `unsat_core` if the solve under them returned UNSAT.
+    fn record_unsat(&mut self, status: RawStatus, assumptions: &[i32]) {
+        self.unsat_assumptions = (status == RawStatus::Unsatisfiable).then(|| assumptions.to_vec());
+        self.core_shrunk = false;
+    }
 }
 
 impl SatSolver for GlucoseSolver {
     fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        check_clause(clause, Self::MAX_VAR)?;
         GlucoseSolver::add_clause(self, clause)?;
         Ok(())
     }
     fn solve_sat(&mut self) -> Result<RawStatus, SolverError> {
-        self.eliminate(true)?;
-        self.solve_limited(&[], true, false)
+        let simp = self.simp();
+        self.eliminated |= simp;
+        // `eliminate` returns false once simplification has refuted the formula.
+        let status = if simp && self.eliminate(true)? == 0 {
+            RawStatus::Unsatisfiable
+        } else {
+            self.solve_limited(&[], simp, false)?
+        };
+        self.record_unsat(status, &[]);
+        Ok(status)
     }
 
     fn model(&mut self) -> Result<Vec<i32>, SolverError> {
@@ -469,6 +779,74 @@ impl SatSolver for GlucoseSolver {
         }
         Ok(model)
     }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        if self.eliminated && !assumptions.is_empty() {
+            self.restore_eliminated()?;
+        }
+        let simp = self.simp();
+        self.eliminated |= simp;
+        let status = self.solve_limited(assumptions, simp, false)?;
+        self.record_unsat(status, assumptions);
+        Ok(status)
+    }
+
+    fn statistics(&mut self) -> Result<SolverStats, SolverError> {
+        Ok(SolverStats {
+            learned_clauses: Some(self.nlearnts()? as u64),
+            clauses: Some(self.nclauses()? as u64),
+            ..SolverStats::default()
+        })
+    }
+
+    fn unsat_core(&mut self) -> Result<Vec<i32>, SolverError> {
+        let core = match self.unsat_assumptions.take() {
+            Some(core) if self.core_shrunk => core,
+            Some(assumptions) => shrink_core(self, assumptions)?,
+            None => return Err(SolverError::InvalidState("Last solve was not UNSAT")),
+        };
+        self.unsat_assumptions = Some(core.clone());
+        self.core_shrunk = true;
+        Ok(core)
+    }
+
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        match self.unsat_assumptions {
+            Some(_) => self.conflict().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn failed_assumptions(&mut self) -> Result<Vec<i32>, SolverError> {
+        match self.unsat_assumptions {
+            Some(_) => self.unsat_core(),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn model_bitset(&mut self) -> Result<ModelBitset, SolverError> {
+        let vars = self.nvars()?;
+        ModelBitset::from_fn(vars as usize, |var| self.model_value(var))
+    }
+
+    fn is_ok(&mut self) -> Result<bool, SolverError> {
+        self.okay()
+    }
+
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        self.nvars()
+    }
+
+    fn reset(&mut self) -> Result<(), SolverError> {
+        let mut fresh = GlucoseSolver::with_options(&self.options)?;
+        // `fresh` takes the old instance and destroys it.
+        std::mem::swap(&mut self.inner, &mut fresh.inner);
+        self.frozen.clear();
+        self.unsat_assumptions = None;
+        self.history = Some(vec![]);
+        self.eliminated = false;
+        Ok(())
+    }
 }
 impl Drop for GlucoseSolver {
     fn drop(&mut self) {
@@ -494,6 +872,30 @@ mod tests {
         ));
     }
     #[test]
+    fn eliminated_and_frozen() {
+        let clauses = vec![vec![1, 2], vec![-2, 3], vec![-3, 4]];
+        let mut solver = GlucoseSolver::new();
+        for clause in &clauses {
+            solver.push_clause(clause).unwrap();
+        }
+        let SatStatus::Satisfiable(model) = solver.solve_model().unwrap() else {
+            panic!("expected SAT");
+        };
+        crate::verify::verify_model(&clauses, &model).unwrap();
+        assert_eq!(solver.full_model().unwrap().len(), 4);
+
+        let mut solver = GlucoseSolver::new();
+        solver.set_frozen(2, true);
+        assert!(solver.is_frozen(-2));
+        for clause in &clauses {
+            solver.push_clause(clause).unwrap();
+        }
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        solver.push_clause(&[-1]).unwrap();
+        solver.push_clause(&[-4]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+    }
+    #[test]
     fn sat() {
         let mut solver = GlucoseSolver::new();
         solver.push_clause(&vec![1, 2]).unwrap();
@@ -502,4 +904,97 @@ mod tests {
             matches!(solver.solve_model().unwrap(),SatStatus::Satisfiable(x) if x.eq(&vec![2]))
         );
     }
+    #[test]
+    fn unsat_by_elimination() {
+        let mut solver = GlucoseSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[1, -2]).unwrap();
+        solver.push_clause(&[-1, 2]).unwrap();
+        solver.push_clause(&[-1, -2]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+        assert!(!solver.okay().unwrap());
+    }
+    #[test]
+    #[allow(deprecated)]
+    fn non_finite_option() {
+        let mut solver = GlucoseSolver::new();
+        assert!(solver.set_opt_var_decay(f64::NAN).is_err());
+        assert!(GlucoseSolver::set_global_opt_var_decay(f64::INFINITY).is_err());
+        solver.set_opt_var_decay(0.9).unwrap();
+    }
+    #[test]
+    fn with_options() {
+        let options = GlucoseOptions {
+            luby_restart: Some(true),
+            use_elim: Some(false),
+            ..Default::default()
+        };
+        let mut solver = GlucoseSolver::with_options(&options).unwrap();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-1]).unwrap();
+        assert_eq!(
+            solver.solve_model().unwrap(),
+            SatStatus::Satisfiable(vec![2])
+        );
+        assert!(!GlucoseSolver::globals().luby_restart);
+
+        let invalid = GlucoseOptions {
+            restart_inc: Some(f64::NAN),
+            ..Default::default()
+        };
+        assert!(GlucoseSolver::with_options(&invalid).is_err());
+        let invalid = GlucoseOptions {
+            var_decay: Some(2.0),
+            ..Default::default()
+        };
+        assert!(GlucoseSolver::with_options(&invalid).is_err());
+    }
+    #[test]
+    fn conflict() {
+        let mut solver = GlucoseSolver::new();
+        solver.push_clause(&[-1, -2]).unwrap();
+        assert!(solver.conflict().is_err());
+        assert_eq!(
+            solver.solve_with_assumptions(&[1, 3, 2]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        let mut conflict = solver.conflict().unwrap();
+        conflict.sort();
+        assert_eq!(conflict, vec![-2, -1]);
+        assert_eq!(solver.failed_assumptions().unwrap().len(), 2);
+        let mut last = solver.last_conflict().unwrap().unwrap();
+        last.sort();
+        assert_eq!(last, conflict);
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        assert_eq!(solver.last_conflict().unwrap(), None);
+    }
+    #[test]
+    fn is_ok_after_contradictory_units() {
+        let mut solver = GlucoseSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        assert!(solver.is_ok().unwrap());
+        solver.push_clause(&[1]).unwrap();
+        solver.push_clause(&[-1]).unwrap();
+        assert!(!solver.is_ok().unwrap());
+    }
+    #[test]
+    fn incremental_after_elimination() {
+        let mut solver = GlucoseSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[2, 3]).unwrap();
+        assert_eq!(solver.count_models(10).unwrap().exact, Some(5));
+
+        let mut solver = GlucoseSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-2, 3]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        assert_eq!(
+            solver.solve_with_assumptions(&[-1, -3]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        solver.push_clause(&[-3]).unwrap();
+        assert!(
+            matches!(solver.solve_model().unwrap(),SatStatus::Satisfiable(x) if x.eq(&vec![1]))
+        );
+    }
 }
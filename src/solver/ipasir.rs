@@ -0,0 +1,260 @@
+//! The `ipasir` module loads third-party solvers through the IPASIR interface.
+//!
+//! This module is enabled when the `ipasir` feature is activated, on Unix platforms.
+//!
+//! # Overview
+//! The `IpasirSolver` struct `dlopen`s a shared library implementing
+//! [IPASIR](https://github.com/biotomas/ipasir), such as Kissat or CryptoMiniSat built as
+//! a shared object, and wraps it in the `SatSolver` trait.
+//!
+//! # Usage
+//! To use the `ipasir` module, ensure the `ipasir` feature is enabled in your `Cargo.toml`:
+//! ```toml
+//! [dependencies]
+//! satgalaxy = { version = "x.y.z", features = ["ipasir"] }
+//! ```
+use std::{
+    ffi::{c_char, c_int, c_void, CStr, CString},
+    fmt,
+    os::unix::ffi::OsStrExt,
+    path::Path,
+    ptr::NonNull,
+};
+
+use crate::errors::SolverError;
+
+use super::{check_clause, ffi_util::Callback, RawStatus, SatSolver};
+
+const RTLD_NOW: c_int = 2;
+
+#[cfg_attr(target_os = "linux", link(name = "dl"))]
+extern "C" {
+    fn dlopen(filename: *const c_char, flags: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    fn dlclose(handle: *mut c_void) -> c_int;
+}
+
+type Terminate = unsafe extern "C" fn(data: *mut c_void) -> c_int;
+
+/// The IPASIR entry points of a loaded library.
+#[derive(Debug, Clone, Copy)]
+struct Api {
+    signature: unsafe extern "C" fn() -> *const c_char,
+    init: unsafe extern "C" fn() -> *mut c_void,
+    release: unsafe extern "C" fn(*mut c_void),
+    add: unsafe extern "C" fn(*mut c_void, i32),
+    assume: unsafe extern "C" fn(*mut c_void, i32),
+    solve: unsafe extern "C" fn(*mut c_void) -> c_int,
+    val: unsafe extern "C" fn(*mut c_void, i32) -> i32,
+    failed: unsafe extern "C" fn(*mut c_void, i32) -> c_int,
+    set_terminate: Option<unsafe extern "C" fn(*mut c_void, *mut c_void, Option<Terminate>)>,
+}
+
+/// Look up `name` in `handle` as a function pointer of type `F`.
+unsafe fn symbol<F: Copy>(handle: *mut c_void, name: &CStr) -> Option<F> {
+    let ptr = dlsym(handle, name.as_ptr());
+    (!ptr.is_null()).then(|| std::mem::transmute_copy(&ptr))
+}
+
+/// A user callback polled through `ipasir_set_terminate`.
+type TerminateCallback = Box<dyn FnMut() -> bool + Send>;
+
+unsafe extern "C" fn terminate_requested(data: *mut c_void) -> c_int {
+    let callback = &mut *(data as *mut Callback<TerminateCallback>);
+    callback.call(|callback| callback()).unwrap_or(true) as c_int
+}
+
+/// `IpasirSolver` wraps a solver loaded from an IPASIR shared library.
+/// # Example
+/// ```rust,no_run
+/// use satgalaxy::solver::{IpasirSolver, RawStatus, SatSolver};
+/// let mut solver = IpasirSolver::open("/usr/local/lib/libkissat.so").unwrap();
+/// println!("loaded {}", solver.signature());
+/// solver.push_clause(&[1, 2]).unwrap();
+/// solver.push_clause(&[-1]).unwrap();
+/// assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+/// assert_eq!(solver.model().unwrap(), vec![2]);
+/// ```
+pub struct IpasirSolver {
+    library: NonNull<c_void>,
+    api: Api,
+    inner: NonNull<c_void>,
+    max_var: i32,
+    /// Assumptions of the last solve, for `last_conflict`.
+    last_assumptions: Vec<i32>,
+    status: RawStatus,
+    /// Installed with `set_terminate`; boxed so its address stays put. A panic in it
+    /// stops the solve and is resumed once the solver returns.
+    terminate: Option<Box<Callback<TerminateCallback>>>,
+}
+
+// SAFETY: `inner` is owned by this value alone, and `library` stays loaded until it
+// is dropped. IPASIR requires solvers to keep no thread-local state, so a solver may be
+// used from whichever thread owns it. The terminate callback handed to it is `Send`
+// and boxed in `terminate`, which outlives its registration.
+unsafe impl Send for IpasirSolver {}
+
+impl fmt::Debug for IpasirSolver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IpasirSolver")
+            .field("signature", &self.signature())
+            .field("max_var", &self.max_var)
+            .field("status", &self.status)
+            .finish_non_exhaustive()
+    }
+}
+
+impl IpasirSolver {
+    /// Load the IPASIR library at `path` and create a solver from it.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, SolverError> {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes())
+            .map_err(|_| SolverError::InvalidArgument("library path contains a NUL byte"))?;
+        unsafe {
+            let library = NonNull::new(dlopen(path.as_ptr(), RTLD_NOW)).ok_or_else(|| {
+                SolverError::Library(format!("failed to load {}", path.to_string_lossy()))
+            })?;
+            let handle = library.as_ptr();
+            let api = (|| {
+                Some(Api {
+                    signature: symbol(handle, c"ipasir_signature")?,
+                    init: symbol(handle, c"ipasir_init")?,
+                    release: symbol(handle, c"ipasir_release")?,
+                    add: symbol(handle, c"ipasir_add")?,
+                    assume: symbol(handle, c"ipasir_assume")?,
+                    solve: symbol(handle, c"ipasir_solve")?,
+                    val: symbol(handle, c"ipasir_val")?,
+                    failed: symbol(handle, c"ipasir_failed")?,
+                    set_terminate: symbol(handle, c"ipasir_set_terminate"),
+                })
+            })();
+            let Some(api) = api else {
+                dlclose(handle);
+                return Err(SolverError::Library(
+                    "IPASIR library is missing a required symbol".into(),
+                ));
+            };
+            let Some(inner) = NonNull::new((api.init)()) else {
+                dlclose(handle);
+                return Err(SolverError::Library("ipasir_init returned null".into()));
+            };
+            Ok(Self {
+                library,
+                api,
+                inner,
+                max_var: 0,
+                last_assumptions: vec![],
+                status: RawStatus::Unknown,
+                terminate: None,
+            })
+        }
+    }
+
+    /// The name and version reported by `ipasir_signature`.
+    pub fn signature(&self) -> String {
+        unsafe { CStr::from_ptr((self.api.signature)()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// The value of `lit` in the last model: `lit` if it is true, `-lit` if false.
+    pub fn val(&mut self, lit: i32) -> i32 {
+        unsafe { (self.api.val)(self.inner.as_ptr(), lit) }
+    }
+
+    /// Whether assumption `lit` was used to prove the last UNSAT result.
+    pub fn failed(&mut self, lit: i32) -> bool {
+        unsafe { (self.api.failed)(self.inner.as_ptr(), lit) != 0 }
+    }
+}
+
+impl SatSolver for IpasirSolver {
+    fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        check_clause(clause, i32::MAX)?;
+        for &lit in clause {
+            self.max_var = self.max_var.max(lit.abs());
+            unsafe { (self.api.add)(self.inner.as_ptr(), lit) };
+        }
+        unsafe { (self.api.add)(self.inner.as_ptr(), 0) };
+        Ok(())
+    }
+
+    fn solve_sat(&mut self) -> Result<RawStatus, SolverError> {
+        self.solve_with_assumptions(&[])
+    }
+
+    fn model(&mut self) -> Result<Vec<i32>, SolverError> {
+        if self.status != RawStatus::Satisfiable {
+            return Err(SolverError::InvalidState("Last solve was not SAT"));
+        }
+        Ok((1..=self.max_var)
+            .filter(|&var| self.val(var) > 0)
+            .collect())
+    }
+
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        Ok(self.max_var)
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        for &lit in assumptions {
+            unsafe { (self.api.assume)(self.inner.as_ptr(), lit) };
+        }
+        self.status = unsafe { (self.api.solve)(self.inner.as_ptr()) }.into();
+        if let Some(panic) = self
+            .terminate
+            .as_mut()
+            .and_then(|callback| callback.take_panic())
+        {
+            std::panic::resume_unwind(panic);
+        }
+        self.last_assumptions = assumptions.to_vec();
+        Ok(self.status)
+    }
+
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        if self.status != RawStatus::Unsatisfiable {
+            return Ok(None);
+        }
+        let assumptions = self.last_assumptions.clone();
+        Ok(Some(
+            assumptions
+                .into_iter()
+                .filter(|&lit| self.failed(lit))
+                .map(|lit| -lit)
+                .collect(),
+        ))
+    }
+
+    fn set_terminate(&mut self, callback: TerminateCallback) -> Result<(), SolverError> {
+        let set_terminate = self
+            .api
+            .set_terminate
+            .ok_or(SolverError::UnsupportedOperation("termination callbacks"))?;
+        let mut callback = Box::new(Callback::new(callback));
+        let data = &mut *callback as *mut Callback<TerminateCallback> as *mut c_void;
+        unsafe { set_terminate(self.inner.as_ptr(), data, Some(terminate_requested)) };
+        self.terminate = Some(callback);
+        Ok(())
+    }
+}
+
+impl Drop for IpasirSolver {
+    fn drop(&mut self) {
+        unsafe {
+            (self.api.release)(self.inner.as_ptr());
+            dlclose(self.library.as_ptr());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_library() {
+        let err = IpasirSolver::open("/nonexistent/libipasir.so").unwrap_err();
+        assert!(matches!(err, SolverError::Library(_)));
+        assert!(IpasirSolver::open("bad\0path").is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! The `maxsat` module provides weighted MaxSAT solving on top of any [`SatSolver`].
+//!
+//! # Overview
+//! [`MaxSatSolver`] implements the core-guided WPM1 algorithm (the weighted extension
+//! of Fu–Malik): soft clauses are guarded by selector assumptions, and every unsat core
+//! returned by the backend is relaxed with fresh variables under an exactly-one
+//! constraint, until the soft clauses that remain active are satisfiable.
+//!
+//! Backends that report precise failed assumptions (`CaDiCaLSolver`, `PicoSATSolver`)
+//! converge much faster, since the others report every assumption as the core.
+use crate::errors::SolverError;
+
+use super::{RawStatus, SatSolver};
+
+/// The result of [`MaxSatSolver::solve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MaxSatStatus {
+    /// An optimal model and the total weight of the soft clauses it violates.
+    Optimal {
+        cost: u64,
+        model: Vec<i32>,
+    },
+    /// The hard clauses are unsatisfiable.
+    Unsatisfiable,
+    Unknown,
+}
+
+/// A weighted MaxSAT solver driving a [`SatSolver`] backend.
+///
+/// With the `parser` feature, it is also an `AsWcnf` sink, so WCNF files can be loaded
+/// directly with `read_wcnf_from_file`.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CaDiCaLSolver, MaxSatSolver, MaxSatStatus};
+/// let mut solver = MaxSatSolver::new(CaDiCaLSolver::new());
+/// solver.add_hard_clause(&[1, 2]).unwrap();
+/// solver.add_soft_clause(3, &[-1]);
+/// solver.add_soft_clause(1, &[-2]);
+/// let MaxSatStatus::Optimal { cost, model } = solver.solve().unwrap() else {
+///     panic!("hard clauses are satisfiable");
+/// };
+/// assert_eq!((cost, model), (1, vec![2]));
+/// ```
+pub struct MaxSatSolver<S: SatSolver> {
+    solver: S,
+    soft_clauses: Vec<(u64, Vec<i32>)>,
+    /// The largest variable of the formula; larger ones are auxiliary.
+    num_vars: i32,
+}
+
+impl<S: SatSolver> MaxSatSolver<S> {
+    pub fn new(solver: S) -> Self {
+        Self {
+            solver,
+            soft_clauses: vec![],
+            num_vars: 0,
+        }
+    }
+
+    fn track_vars(&mut self, clause: &[i32]) {
+        let max = clause
+            .iter()
+            .map(|lit| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        self.num_vars = self.num_vars.max(max as i32);
+    }
+
+    /// Add a clause that every solution must satisfy.
+    pub fn add_hard_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        self.track_vars(clause);
+        self.solver.push_clause(clause)
+    }
+
+    /// Add a clause whose violation costs `weight`. Zero-weight clauses are ignored.
+    pub fn add_soft_clause(&mut self, weight: u64, clause: &[i32]) {
+        self.track_vars(clause);
+        if weight > 0 {
+            self.soft_clauses.push((weight, clause.to_vec()));
+        }
+    }
+
+    /// The underlying SAT solver.
+    pub fn inner(&mut self) -> &mut S {
+        &mut self.solver
+    }
+
+    /// Find a model minimizing the total weight of violated soft clauses.
+    ///
+    /// The model only contains variables of the added clauses. The relaxation clauses
+    /// stay in the backend, so `solve` is meant to be called once.
+    pub fn solve(&mut self) -> Result<MaxSatStatus, SolverError> {
+        let mut next_var = self.solver.max_var()?.max(self.num_vars);
+        let mut fresh = || {
+            next_var += 1;
+            next_var
+        };
+        // Without this check, backends lacking precise cores would relax forever.
+        match self.solver.solve_with_assumptions(&[])? {
+            RawStatus::Unsatisfiable => return Ok(MaxSatStatus::Unsatisfiable),
+            RawStatus::Unknown => return Ok(MaxSatStatus::Unknown),
+            RawStatus::Satisfiable => {}
+        }
+        let mut soft: Vec<(u64, Vec<i32>)> = self.soft_clauses.clone();
+        let mut cost = 0;
+        loop {
+            let mut selectors = Vec::with_capacity(soft.len());
+            for (_, clause) in &soft {
+                let selector = fresh();
+                let mut guarded = clause.clone();
+                guarded.push(-selector);
+                self.solver.push_clause(&guarded)?;
+                selectors.push(selector);
+            }
+            match self.solver.solve_with_assumptions(&selectors)? {
+                RawStatus::Satisfiable => {
+                    let num_vars = self.num_vars;
+                    let model = self.solver.model()?;
+                    return Ok(MaxSatStatus::Optimal {
+                        cost,
+                        model: model
+                            .into_iter()
+                            .filter(|lit| lit.abs() <= num_vars)
+                            .collect(),
+                    });
+                }
+                RawStatus::Unknown => return Ok(MaxSatStatus::Unknown),
+                RawStatus::Unsatisfiable => {}
+            }
+            let core: Vec<usize> = match self.solver.last_conflict()? {
+                Some(conflict) => (0..soft.len())
+                    .filter(|&i| conflict.contains(&-selectors[i]))
+                    .collect(),
+                // No precise core from the backend: every assumption is involved.
+                None => (0..soft.len()).collect(),
+            };
+            if core.is_empty() {
+                return Ok(MaxSatStatus::Unsatisfiable);
+            }
+            let min_weight = core.iter().map(|&i| soft[i].0).min().unwrap_or(0);
+            cost += min_weight;
+            let mut relax = Vec::with_capacity(core.len());
+            for &i in &core {
+                let (weight, clause) = &mut soft[i];
+                let mut relaxed = clause.clone();
+                let var = fresh();
+                relaxed.push(var);
+                relax.push(var);
+                if *weight > min_weight {
+                    *weight -= min_weight;
+                    soft.push((min_weight, relaxed));
+                } else {
+                    *clause = relaxed;
+                }
+            }
+            // Exactly one clause of the core is relaxed.
+            self.solver.push_clause(&relax)?;
+            for (i, &a) in relax.iter().enumerate() {
+                for &b in &relax[i + 1..] {
+                    self.solver.push_clause(&[-a, -b])?;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "parser")]
+impl<S: SatSolver> crate::parser::AsWcnf for MaxSatSolver<S> {
+    fn push_hard_clause(&mut self, clause: Vec<i32>) -> Result<(), crate::errors::ParserError> {
+        self.add_hard_clause(&clause)?;
+        Ok(())
+    }
+    fn push_soft_clause(
+        &mut self,
+        weight: u64,
+        clause: Vec<i32>,
+    ) -> Result<(), crate::errors::ParserError> {
+        self.add_soft_clause(weight, &clause);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "cadical")]
+    use super::*;
+    #[test]
+    #[cfg(feature = "cadical")]
+    fn weighted() {
+        use crate::solver::CaDiCaLSolver;
+        let mut solver = MaxSatSolver::new(CaDiCaLSolver::new());
+        solver.add_hard_clause(&[1, 2, 3]).unwrap();
+        solver.add_soft_clause(4, &[-1]);
+        solver.add_soft_clause(2, &[-2]);
+        solver.add_soft_clause(3, &[-3]);
+        solver.add_soft_clause(1, &[1]);
+        assert!(matches!(
+            solver.solve().unwrap(),
+            MaxSatStatus::Optimal { cost: 3, model } if model == vec![2]
+        ));
+
+        let mut solver = MaxSatSolver::new(CaDiCaLSolver::new());
+        solver.add_hard_clause(&[1]).unwrap();
+        solver.add_hard_clause(&[-1]).unwrap();
+        solver.add_soft_clause(1, &[2]);
+        assert_eq!(solver.solve().unwrap(), MaxSatStatus::Unsatisfiable);
+    }
+}
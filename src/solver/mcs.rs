@@ -0,0 +1,196 @@
+use crate::errors::SolverError;
+
+use super::{ModelBitset, RawStatus, SatSolver};
+
+/// A partition of the soft clauses into a maximal satisfiable subset and its complement,
+/// a minimal correction set. Both hold indices into the soft clauses.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct McsPartition {
+    pub mss: Vec<usize>,
+    pub mcs: Vec<usize>,
+}
+
+/// An iterator over the MSS/MCS partitions of a set of soft clauses.
+///
+/// The clauses already in the solver are hard. Each soft clause is guarded by a selector
+/// variable allocated above the solver's variables, an MSS is grown from a model one soft
+/// clause at a time, and the clause `∨ selectors(MCS)` blocks it before the next
+/// partition, so the solver is left with the guarded and blocking clauses added. Works
+/// with every backend that supports assumptions.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CaDiCaLSolver, McsEnumerator, SatSolver};
+/// let mut solver = CaDiCaLSolver::new();
+/// solver.push_clause(&[-1, -2]).unwrap();
+/// let mut mcses: Vec<Vec<usize>> = McsEnumerator::new(&mut solver)
+///     .assumptions(&[1, 2, 3])
+///     .map(|partition| partition.unwrap().mcs)
+///     .collect();
+/// mcses.sort();
+/// assert_eq!(mcses, vec![vec![0], vec![1]]);
+/// ```
+pub struct McsEnumerator<'a, S: SatSolver + ?Sized> {
+    solver: &'a mut S,
+    soft: Vec<Vec<i32>>,
+    selectors: Vec<i32>,
+    done: bool,
+}
+
+impl<'a, S: SatSolver + ?Sized> McsEnumerator<'a, S> {
+    pub fn new(solver: &'a mut S) -> Self {
+        Self {
+            solver,
+            soft: vec![],
+            selectors: vec![],
+            done: false,
+        }
+    }
+
+    /// Add a soft clause; its index is the number of soft clauses added before it.
+    pub fn soft_clause(mut self, clause: &[i32]) -> Self {
+        self.soft.push(clause.to_vec());
+        self
+    }
+
+    /// Add each literal as a soft unit clause, to partition an assumption set.
+    pub fn assumptions(mut self, lits: &[i32]) -> Self {
+        self.soft.extend(lits.iter().map(|&lit| vec![lit]));
+        self
+    }
+
+    /// Push every soft clause guarded by a fresh selector.
+    fn load(&mut self) -> Result<(), SolverError> {
+        let soft_max = self
+            .soft
+            .iter()
+            .flatten()
+            .map(|lit| lit.abs())
+            .max()
+            .unwrap_or(0);
+        let base = self.solver.max_var().unwrap_or(0).max(soft_max);
+        for (i, clause) in self.soft.iter().enumerate() {
+            let selector = base + 1 + i as i32;
+            let mut guarded = clause.clone();
+            guarded.push(-selector);
+            self.solver.push_clause(&guarded)?;
+            self.selectors.push(selector);
+        }
+        Ok(())
+    }
+
+    fn solve(&mut self, soft: &[usize]) -> Result<Option<ModelBitset>, SolverError> {
+        let assumptions: Vec<i32> = soft.iter().map(|&i| self.selectors[i]).collect();
+        match self.solver.solve_with_assumptions(&assumptions)? {
+            RawStatus::Satisfiable => Ok(Some(self.solver.model_bitset()?)),
+            RawStatus::Unsatisfiable => Ok(None),
+            RawStatus::Unknown => Err(SolverError::Terminated),
+        }
+    }
+
+    /// The soft clauses satisfied by `model`.
+    fn satisfied(&self, model: &ModelBitset) -> Vec<usize> {
+        (0..self.soft.len())
+            .filter(|&i| {
+                self.soft[i]
+                    .iter()
+                    .any(|&lit| model.bit(lit.abs()) == (lit > 0))
+            })
+            .collect()
+    }
+
+    fn next_partition(&mut self) -> Result<Option<McsPartition>, SolverError> {
+        if self.selectors.len() < self.soft.len() {
+            self.load()?;
+        }
+        let Some(model) = self.solve(&[])? else {
+            return Ok(None);
+        };
+        let mut mss = self.satisfied(&model);
+        for i in 0..self.soft.len() {
+            if mss.contains(&i) {
+                continue;
+            }
+            let mut candidate = mss.clone();
+            candidate.push(i);
+            if let Some(model) = self.solve(&candidate)? {
+                mss = self.satisfied(&model);
+            }
+        }
+        let mcs: Vec<usize> = (0..self.soft.len()).filter(|i| !mss.contains(i)).collect();
+        if mcs.is_empty() {
+            // Every soft clause is satisfiable together, so this is the only partition.
+            self.done = true;
+        } else {
+            let block: Vec<i32> = mcs.iter().map(|&i| self.selectors[i]).collect();
+            self.solver.push_clause(&block)?;
+        }
+        Ok(Some(McsPartition { mss, mcs }))
+    }
+}
+
+impl<S: SatSolver + ?Sized> Iterator for McsEnumerator<'_, S> {
+    type Item = Result<McsPartition, SolverError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.next_partition() {
+            Ok(Some(partition)) => Some(Ok(partition)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "picosat"))]
+mod tests {
+    use super::*;
+    use crate::solver::PicoSATSolver;
+
+    #[test]
+    fn partitions() {
+        let mut solver = PicoSATSolver::new();
+        SatSolver::push_clause(&mut solver, &[1, 2]).unwrap();
+        let mut partitions: Vec<McsPartition> = McsEnumerator::new(&mut solver)
+            .soft_clause(&[-1])
+            .soft_clause(&[-2])
+            .soft_clause(&[3])
+            .map(Result::unwrap)
+            .collect();
+        partitions.sort_by_key(|partition| partition.mcs.clone());
+        assert_eq!(
+            partitions,
+            vec![
+                McsPartition {
+                    mss: vec![1, 2],
+                    mcs: vec![0]
+                },
+                McsPartition {
+                    mss: vec![0, 2],
+                    mcs: vec![1]
+                },
+            ]
+        );
+
+        let mut solver = PicoSATSolver::new();
+        let all: Vec<McsPartition> = McsEnumerator::new(&mut solver)
+            .assumptions(&[1, 2])
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(
+            all,
+            vec![McsPartition {
+                mss: vec![0, 1],
+                mcs: vec![]
+            }]
+        );
+    }
+}
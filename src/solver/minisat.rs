@@ -22,8 +22,8 @@ mod bindings {
 }
 use crate::errors::SolverError;
 
-use super::{RawStatus, SatSolver};
-use std::{ffi::c_int, ptr::NonNull};
+use super::{check_clause, shrink_core, ModelBitset, RawStatus, SatSolver, SolverStats};
+use std::{collections::BTreeSet, ffi::c_int, ptr::NonNull};
 
 /// `MinisatSolver` is a wrapper for the [MiniSat](https://github.com/niklasso/minisat) SimpSolver.
 /// It also allows creating a `Minisat_StdSimpSolver` instance for more low-level operations.
@@ -54,14 +54,33 @@ use std::{ffi::c_int, ptr::NonNull};
 ///  [dependencies]
 ///  satgalaxy = { version = "x.y.z", features = ["minisat"] }
 ///
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct MinisatSolver {
     /// The inner pointer to the Minisat solver instance.
     /// This is a raw pointer to the C++ object, and it should not be used directly.
     /// Use the methods provided by `MinisatSolver` instead.
     inner: NonNull<bindings::MiniSATSolver>,
+    /// Whether `solve_sat` runs variable elimination before solving.
+    use_simp: bool,
+    /// Variables that must survive simplification, see `set_frozen`.
+    frozen: BTreeSet<i32>,
+    /// Assumptions of the last solve if it returned UNSAT, empty after `solve_sat`.
+    unsat_assumptions: Option<Vec<i32>>,
+    /// Whether `unsat_core` has already shrunk `unsat_assumptions` to a minimal core.
+    core_shrunk: bool,
+    /// The clauses added so far, kept while solving may still eliminate variables;
+    /// `None` once elimination is off for good, see `set_use_simp`.
+    history: Option<Vec<Vec<i32>>>,
+    /// Whether a solve has eliminated variables since the instance was built.
+    eliminated: bool,
+    /// The options set on this instance, applied again by `reset`.
+    options: MinisatOptions,
 }
 
+// SAFETY: `inner` is owned by this value alone and MiniSat keeps no thread-local
+// state, so the instance may be used from whichever thread owns the solver. MiniSat
+// takes no callbacks, and the other fields are plain Rust data.
+unsafe impl Send for MinisatSolver {}
 impl Default for MinisatSolver {
     fn default() -> Self {
         Self::new()
@@ -75,12 +94,15 @@ macro_rules! minisat_opt_set {
         paste::paste! {
             #[doc=$doc]
             pub fn [<set_global_opt_$name>](value: $type) -> Result<(), SolverError> {
+                if !$crate::solver::OptValue::is_valid(value) {
+                    return Err(SolverError::InvalidArgument("option value must be finite"));
+                }
                 let code = unsafe {
                      bindings::[<minisat_set_global_opt_$ffi_name>](value.into())
                     };
 
                 if code!=0{
-                    return Err(SolverError(Self::error_msg(code)));
+                    return Err(Self::error_msg(code));
                 }
                 Ok(())
             }
@@ -88,26 +110,85 @@ macro_rules! minisat_opt_set {
         paste::paste! {
             #[doc=$doc]
             pub fn [<set_opt_$name>](&mut self, value: $type) -> Result<(), SolverError> {
+                if !$crate::solver::OptValue::is_valid(value) {
+                    return Err(SolverError::InvalidArgument("option value must be finite"));
+                }
                 let code = unsafe {
                      bindings::[<minisat_set_opt_$ffi_name>](self.inner.as_ptr(), value.into())
                     };
 
                 if code!=0{
-                    return Err(SolverError(Self::error_msg(code)));
+                    return Err(Self::error_msg(code));
                 }
+                self.options.$name = Some(value);
                 Ok(())
             }
         }
     };
 }
 
-impl MinisatSolver {
-    fn error_msg(code: i32) -> &'static str {
-        unsafe {
-            let msg = bindings::minisat_error_msg(code);
-            let msg = std::ffi::CStr::from_ptr(msg);
-            msg.to_str().unwrap()
+macro_rules! minisat_options {
+    ($($name:ident: $type:ty, $doc:literal;)*) => {
+        /// Options of one `MinisatSolver`, applied by [`MinisatSolver::with_options`].
+        ///
+        /// `None` keeps MiniSat's default. Valid ranges are those of the matching
+        /// `set_opt_*` methods.
+        /// # Example
+        /// ```rust
+        /// use satgalaxy::solver::{MinisatOptions, MinisatSolver};
+        /// let options = MinisatOptions {
+        ///     luby_restart: Some(false),
+        ///     random_seed: Some(7.0),
+        ///     ..Default::default()
+        /// };
+        /// let solver = MinisatSolver::with_options(&options).unwrap();
+        /// ```
+        #[derive(Debug, Clone, Copy, PartialEq, Default)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub struct MinisatOptions {
+            $(#[doc = $doc] pub $name: Option<$type>,)*
+        }
+
+        impl MinisatSolver {
+            /// Set every option of `options` on this instance.
+            pub fn apply_options(&mut self, options: &MinisatOptions) -> Result<(), SolverError> {
+                paste::paste! {
+                    $(if let Some(value) = options.$name {
+                        self.[<set_opt_$name>](value)?;
+                    })*
+                }
+                Ok(())
+            }
         }
+    };
+}
+
+minisat_options! {
+    var_decay: f64, "The variable activity decay factor, in (0, 1).";
+    clause_decay: f64, "The clause activity decay factor, in (0, 1).";
+    random_var_freq: f64, "The frequency of random decisions, in [0, 1].";
+    random_seed: f64, "Used by the random variable selection, positive.";
+    ccmin_mode: i32, "Conflict clause minimization (0=none, 1=basic, 2=deep).";
+    phase_saving: i32, "Phase saving (0=none, 1=limited, 2=full).";
+    rnd_init_act: bool, "Randomize the initial activity.";
+    luby_restart: bool, "Use the Luby restart sequence.";
+    restart_first: i32, "The base restart interval, positive.";
+    restart_inc: f64, "Restart interval increase factor, at least 1.";
+    garbage_frac: f64, "Wasted memory allowed before garbage collection, positive.";
+    min_learnts_lim: i32, "Minimum learnt clause limit, at least 0.";
+    use_asymm: bool, "Shrink clauses by asymmetric branching.";
+    use_rcheck: bool, "Check if a clause is already implied (costly).";
+    use_elim: bool, "Perform variable elimination.";
+    grow: i32, "Allowed clause growth of an elimination step, at least 0.";
+    clause_lim: i32, "Max resolvent length for elimination (-1=no limit).";
+    subsumption_lim: i32, "Max clause size for subsumption (-1=no limit).";
+    simp_garbage_frac: f64, "Wasted memory allowed during simplification, positive.";
+    verbosity: i32, "Verbosity level (0=silent, 1=some, 2=more).";
+}
+
+impl MinisatSolver {
+    fn error_msg(code: i32) -> SolverError {
+        unsafe { SolverError::ffi(code, bindings::minisat_error_msg(code)) }
     }
     minisat_opt_set!(
         var_decay,
@@ -190,6 +271,96 @@ impl MinisatSolver {
         unsafe {
             MinisatSolver {
                 inner: NonNull::new(bindings::minisat_new_solver()).unwrap(),
+                use_simp: true,
+                frozen: BTreeSet::new(),
+                unsat_assumptions: None,
+                core_shrunk: false,
+                history: Some(vec![]),
+                eliminated: false,
+                options: MinisatOptions::default(),
+            }
+        }
+    }
+    /// Create a solver with `options`, leaving other solvers unaffected.
+    ///
+    /// Every option is set on the new instance through the `set_opt_*` methods, unlike
+    /// the `set_global_opt_*` functions, which change the defaults of every solver
+    /// created afterwards in the process.
+    pub fn with_options(options: &MinisatOptions) -> Result<Self, SolverError> {
+        let mut solver = Self::new();
+        solver.apply_options(options)?;
+        Ok(solver)
+    }
+    /// Enable or disable variable elimination in `solve_sat` (enabled by default).
+    ///
+    /// Elimination pays off for one-shot solving, but eliminated variables must not
+    /// appear in clauses or assumptions added afterwards. While it is enabled, the solver
+    /// keeps a copy of its clauses, and the first clause or assumption after an
+    /// eliminating solve rebuilds the instance from that copy, dropping the learned
+    /// clauses, and turns elimination off for good. Turn it off before adding clauses
+    /// when alternating `push_clause` and `solve_sat` to avoid both the copy and the
+    /// rebuild.
+    pub fn set_use_simp(&mut self, use_simp: bool) {
+        self.use_simp = use_simp;
+    }
+    /// Protect `var` from variable elimination, or release it again.
+    ///
+    /// Freeze the variables that later clauses or assumptions will mention. The core
+    /// does not export MiniSat's `setFrozen`, so elimination is skipped entirely while
+    /// any variable is frozen. Eliminated variables still get values in the model:
+    /// MiniSat's `extendModel` reconstructs them after every SAT answer.
+    pub fn set_frozen(&mut self, var: i32, frozen: bool) {
+        if frozen {
+            self.frozen.insert(var.abs());
+        } else {
+            self.frozen.remove(&var.abs());
+        }
+    }
+    /// Whether `var` is protected from variable elimination.
+    pub fn is_frozen(&self, var: i32) -> bool {
+        self.frozen.contains(&var.abs())
+    }
+    /// Whether solving may simplify, i.e. elimination is enabled and nothing is frozen.
+    fn simp(&self) -> bool {
+        self.use_simp && self.frozen.is_empty() && self.history.is_some()
+    }
+    /// Keep `clause` for `restore_eliminated`, first bringing back the variables an
+    /// earlier solve eliminated, since `clause` may mention them.
+    fn track_clause(&mut self, clause: &[i32]) {
+        if self.eliminated {
+            self.restore_eliminated();
+        }
+        if !self.use_simp || self.options.use_elim == Some(false) {
+            self.history = None;
+        }
+        if let Some(history) = &mut self.history {
+            // A 0 literal ends the clause at the C level.
+            let end = clause
+                .iter()
+                .position(|&lit| lit == 0)
+                .unwrap_or(clause.len());
+            history.push(clause[..end].to_vec());
+        }
+    }
+    /// Replace the instance by a fresh one holding the clauses added so far, without
+    /// the eliminations of earlier solves, and stop eliminating.
+    fn restore_eliminated(&mut self) {
+        self.eliminated = false;
+        let Some(clauses) = self.history.take() else {
+            return;
+        };
+        let vars = self.vars();
+        let mut fresh = MinisatSolver::new();
+        // This instance accepted the options already.
+        let _ = fresh.apply_options(&self.options);
+        // `fresh` takes the old instance and destroys it.
+        std::mem::swap(&mut self.inner, &mut fresh.inner);
+        while self.vars() < vars {
+            self.new_var();
+        }
+        for clause in &clauses {
+            unsafe {
+                bindings::minisat_add_clause(self.inner.as_ptr(), clause.as_ptr(), clause.len());
             }
         }
     }
@@ -207,14 +378,22 @@ impl MinisatSolver {
             bindings::minisat_release_var(self.inner.as_ptr(), var as c_int);
         }
     }
-    /// Add a clause to the solver.
+    /// The largest variable index MiniSat accepts, since literals are encoded as `2 * var + sign`.
+    pub const MAX_VAR: i32 = i32::MAX / 2;
+
+    /// Add a clause to the solver, without validating its literals.
+    ///
+    /// This is the unchecked fast path: a 0 literal ends the clause early at the C level.
+    /// `SatSolver::push_clause` rejects 0 and variables above `MAX_VAR` first.
     pub fn add_clause(&mut self, clause: &[i32]) {
+        self.track_clause(clause);
         unsafe {
             bindings::minisat_add_clause(self.inner.as_ptr(), clause.as_ptr(), clause.len());
         }
     }
     /// Add an empty clause to the solver. (unsat)
     pub fn add_empty_clause(&mut self) {
+        self.track_clause(&[]);
         unsafe {
             bindings::minisat_add_empty_clause(self.inner.as_ptr());
         }
@@ -223,6 +402,16 @@ impl MinisatSolver {
     pub fn value(&mut self, var: i32) -> bool {
         unsafe { bindings::minisat_value(self.inner.as_ptr(), var as c_int) != 0 }
     }
+    /// The value of `var` in the current assignment, or `None` if it is unassigned.
+    ///
+    /// Outside of a solve this is the root level, so `Some` means `var` is fixed.
+    pub fn root_value(&mut self, var: i32) -> Option<bool> {
+        match unsafe { bindings::minisat_value(self.inner.as_ptr(), var as c_int) } {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        }
+    }
     // The model assignments for the variables
     pub fn model_value(&mut self, var: i32) -> bool {
         unsafe { bindings::minisat_model_value(self.inner.as_ptr(), var as c_int) != 0 }
@@ -282,20 +471,46 @@ impl MinisatSolver {
         unsafe { bindings::minisat_nlearnts(self.inner.as_ptr()) as usize }
     }
 
+    /// Whether the solver is still consistent (no conflict derived at the top level).
     pub fn okay(&mut self) -> bool {
         unsafe { bindings::minisat_okay(self.inner.as_ptr()) == 1 }
     }
+
+    /// The final conflict clause of the last solve: the negation of the assumptions that
+    /// made it UNSAT.
+    ///
+    /// `satgalaxy-core` does not export the solver's own `conflict` vector, so this is
+    /// the negated `unsat_core`. The first call after a solve shrinks the assumptions by
+    /// deletion, re-solving once per assumption; later calls return the cached core.
+    /// Those solves replace the model and status of the last solve, so read the model
+    /// before asking for the conflict. Returns an error if the last solve was not UNSAT.
+    pub fn conflict(&mut self) -> Result<Vec<i32>, SolverError> {
+        Ok(self.unsat_core()?.into_iter().map(|lit| -lit).collect())
+    }
+
+    /// Remember `assumptions` for `unsat_core` if the solve under them returned UNSAT.
+    fn record_unsat(&mut self, status: RawStatus, assumptions: &[i32]) {
+        self.unsat_assumptions = (status == RawStatus::Unsatisfiable).then(|| assumptions.to_vec());
+        self.core_shrunk = false;
+    }
 }
 
 impl SatSolver for MinisatSolver {
     fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        check_clause(clause, Self::MAX_VAR)?;
         MinisatSolver::add_clause(self, clause);
         Ok(())
     }
 
     fn solve_sat(&mut self) -> Result<RawStatus, SolverError> {
-        self.eliminate(false);
-        Ok(self.solve_limited(&[], true, false))
+        let simp = self.simp();
+        if simp {
+            self.eliminate(false);
+            self.eliminated = true;
+        }
+        let status = self.solve_limited(&[], simp, false);
+        self.record_unsat(status, &[]);
+        Ok(status)
     }
 
     fn model(&mut self) -> Result<Vec<i32>, SolverError> {
@@ -303,6 +518,75 @@ impl SatSolver for MinisatSolver {
             .filter(|lit| self.model_value(*lit))
             .collect())
     }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        if self.eliminated && !assumptions.is_empty() {
+            self.restore_eliminated();
+        }
+        let simp = self.simp();
+        let status = self.solve_limited(assumptions, simp, false);
+        self.eliminated |= simp;
+        self.record_unsat(status, assumptions);
+        Ok(status)
+    }
+
+    fn statistics(&mut self) -> Result<SolverStats, SolverError> {
+        Ok(SolverStats {
+            learned_clauses: Some(self.learnts() as u64),
+            clauses: Some(self.clauses() as u64),
+            ..SolverStats::default()
+        })
+    }
+
+    fn unsat_core(&mut self) -> Result<Vec<i32>, SolverError> {
+        let core = match self.unsat_assumptions.take() {
+            Some(core) if self.core_shrunk => core,
+            Some(assumptions) => shrink_core(self, assumptions)?,
+            None => return Err(SolverError::InvalidState("Last solve was not UNSAT")),
+        };
+        self.unsat_assumptions = Some(core.clone());
+        self.core_shrunk = true;
+        Ok(core)
+    }
+
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        match self.unsat_assumptions {
+            Some(_) => self.conflict().map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn failed_assumptions(&mut self) -> Result<Vec<i32>, SolverError> {
+        match self.unsat_assumptions {
+            Some(_) => self.unsat_core(),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn model_bitset(&mut self) -> Result<ModelBitset, SolverError> {
+        let vars = self.vars();
+        ModelBitset::from_fn(vars as usize, |var| Ok(self.model_value(var)))
+    }
+
+    fn is_ok(&mut self) -> Result<bool, SolverError> {
+        Ok(self.okay())
+    }
+
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        Ok(self.vars())
+    }
+
+    fn reset(&mut self) -> Result<(), SolverError> {
+        let mut fresh = MinisatSolver::new();
+        fresh.apply_options(&self.options)?;
+        // `fresh` takes the old instance and destroys it.
+        std::mem::swap(&mut self.inner, &mut fresh.inner);
+        self.frozen.clear();
+        self.unsat_assumptions = None;
+        self.history = Some(vec![]);
+        self.eliminated = false;
+        Ok(())
+    }
 }
 impl Drop for MinisatSolver {
     fn drop(&mut self) {
@@ -328,6 +612,50 @@ mod tests {
         ));
     }
     #[test]
+    fn with_options() {
+        let options = MinisatOptions {
+            use_elim: Some(false),
+            ccmin_mode: Some(1),
+            ..Default::default()
+        };
+        let mut solver = MinisatSolver::with_options(&options).unwrap();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-1]).unwrap();
+        assert_eq!(
+            solver.solve_model().unwrap(),
+            SatStatus::Satisfiable(vec![2])
+        );
+        let invalid = MinisatOptions {
+            ccmin_mode: Some(3),
+            ..Default::default()
+        };
+        assert!(MinisatSolver::with_options(&invalid).is_err());
+    }
+    #[test]
+    fn eliminated_and_frozen() {
+        let clauses = vec![vec![1, 2], vec![-2, 3], vec![-3, 4]];
+        let mut solver = MinisatSolver::new();
+        for clause in &clauses {
+            solver.push_clause(clause).unwrap();
+        }
+        let SatStatus::Satisfiable(model) = solver.solve_model().unwrap() else {
+            panic!("expected SAT");
+        };
+        crate::verify::verify_model(&clauses, &model).unwrap();
+        assert_eq!(solver.full_model().unwrap().len(), 4);
+
+        let mut solver = MinisatSolver::new();
+        solver.set_frozen(2, true);
+        assert!(solver.is_frozen(-2));
+        for clause in &clauses {
+            solver.push_clause(clause).unwrap();
+        }
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        solver.push_clause(&[-1]).unwrap();
+        solver.push_clause(&[-4]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+    }
+    #[test]
     fn sat() {
         let mut solver = MinisatSolver::new();
         solver.push_clause(&vec![1, 2]).unwrap();
@@ -336,4 +664,72 @@ mod tests {
             matches!(solver.solve_model().unwrap(),SatStatus::Satisfiable(x) if x.eq(&vec![2]))
         );
     }
+    #[test]
+    fn unsat_core() {
+        let mut solver = MinisatSolver::new();
+        solver.set_use_simp(false);
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-1, 3]).unwrap();
+        assert!(solver.unsat_core().is_err());
+        assert_eq!(
+            solver.solve_with_assumptions(&[4, -2, 5, -3]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        let mut core = solver.unsat_core().unwrap();
+        core.sort();
+        assert_eq!(core, vec![-3, -2]);
+        let mut conflict = solver.conflict().unwrap();
+        conflict.sort();
+        assert_eq!(conflict, vec![2, 3]);
+        let mut failed = solver.failed_assumptions().unwrap();
+        failed.sort();
+        assert_eq!(failed, vec![-3, -2]);
+        let mut conflict = solver.last_conflict().unwrap().unwrap();
+        conflict.sort();
+        assert_eq!(conflict, vec![2, 3]);
+        solver.push_clause(&[-2]).unwrap();
+        solver.push_clause(&[-3]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+        assert_eq!(solver.last_conflict().unwrap(), Some(vec![]));
+    }
+    #[test]
+    fn non_finite_option() {
+        let mut solver = MinisatSolver::new();
+        assert!(solver.set_opt_var_decay(f64::NAN).is_err());
+        solver.set_opt_var_decay(0.9).unwrap();
+    }
+    #[test]
+    fn incremental_without_simp() {
+        let mut solver = MinisatSolver::new();
+        solver.set_use_simp(false);
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[2, 3]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        solver.push_clause(&[-2]).unwrap();
+        assert!(
+            matches!(solver.solve_model().unwrap(),SatStatus::Satisfiable(x) if x.eq(&vec![1, 3]))
+        );
+        solver.push_clause(&[-1]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+    }
+    #[test]
+    fn incremental_after_elimination() {
+        let mut solver = MinisatSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[2, 3]).unwrap();
+        assert_eq!(solver.count_models(10).unwrap().exact, Some(5));
+
+        let mut solver = MinisatSolver::new();
+        solver.push_clause(&[1, 2]).unwrap();
+        solver.push_clause(&[-2, 3]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        assert_eq!(
+            solver.solve_with_assumptions(&[-1, -3]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        solver.push_clause(&[-3]).unwrap();
+        assert!(
+            matches!(solver.solve_model().unwrap(),SatStatus::Satisfiable(x) if x.eq(&vec![1]))
+        );
+    }
 }
@@ -1,22 +1,113 @@
 #[cfg(feature = "cadical")]
 pub mod cadical;
 #[cfg(feature = "cadical")]
-pub use cadical::CaDiCaLSolver;
+pub use cadical::{CaDiCaLSolver, Fixedness, TerminationHandle};
+#[cfg(feature = "cadical")]
+mod cadical_options;
+#[cfg(feature = "cadical")]
+pub use cadical_options::{CadicalOptions, OptionInfo, CADICAL_OPTIONS};
+mod config;
+pub use config::{Preset, SolverConfig};
+#[cfg(feature = "cadical")]
+mod cube;
+#[cfg(any(
+    feature = "picosat",
+    feature = "cadical",
+    all(feature = "ipasir", unix)
+))]
+mod ffi_util;
+#[cfg(feature = "cadical")]
+pub use cube::{CubeAndConquer, CubeStatus};
+#[cfg(feature = "async")]
+mod future;
+#[cfg(feature = "async")]
+pub use future::{AsyncSolve, SolveFuture, SolveOutput};
 #[cfg(feature = "glucose")]
 pub mod glucose;
 #[cfg(feature = "glucose")]
-pub use glucose::GlucoseSolver;
+pub use glucose::{GlucoseOptions, GlucoseSolver};
+pub mod maxsat;
+pub use maxsat::{MaxSatSolver, MaxSatStatus};
+#[cfg(all(feature = "ipasir", unix))]
+pub mod ipasir;
+#[cfg(all(feature = "ipasir", unix))]
+pub use ipasir::IpasirSolver;
 #[cfg(feature = "minisat")]
 pub mod minisat;
+mod models;
+pub use models::ModelIterator;
+mod counting;
+pub use counting::{ApproxCount, ApproxCounter};
+mod mcs;
+pub use mcs::{McsEnumerator, McsPartition};
+mod mus;
 #[cfg(feature = "minisat")]
-pub use minisat::MinisatSolver;
+pub use minisat::{MinisatOptions, MinisatSolver};
+pub use mus::{DeletionMus, GroupMus};
 #[cfg(feature = "picosat")]
 pub mod picosat;
+mod portfolio;
+pub use portfolio::PortfolioSolver;
+mod scoped;
+pub use scoped::{IncrementalSolver, Scoped};
+mod shared;
+pub use shared::SharedSolver;
+mod tracked;
+pub use tracked::{TrackedClause, TrackedSolver};
+#[cfg(feature = "parser")]
+mod optimize;
+#[cfg(feature = "parser")]
+pub use optimize::{OptimizeStatus, Optimizer};
+#[cfg(all(
+    feature = "parser",
+    any(
+        feature = "cadical",
+        feature = "glucose",
+        feature = "minisat",
+        feature = "picosat"
+    )
+))]
+mod qbf;
+#[cfg(all(
+    feature = "parser",
+    any(
+        feature = "cadical",
+        feature = "glucose",
+        feature = "minisat",
+        feature = "picosat"
+    )
+))]
+pub use qbf::{solve_2qbf, QbfStatus};
+mod types;
 #[cfg(feature = "picosat")]
-pub use picosat::PicoSATSolver;
+pub use picosat::{MusConfig, PicoSATSolver, TraceFormat};
+pub use types::{Lit, Var};
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
 
 use crate::errors::SolverError;
 
+/// Option values that can be validated before they reach the C layer.
+#[cfg(any(feature = "glucose", feature = "minisat"))]
+pub(crate) trait OptValue: Copy {
+    /// Whether the value is safe to pass on; floats must be finite.
+    fn is_valid(self) -> bool {
+        true
+    }
+}
+#[cfg(any(feature = "glucose", feature = "minisat"))]
+impl OptValue for i32 {}
+#[cfg(any(feature = "glucose", feature = "minisat"))]
+impl OptValue for bool {}
+#[cfg(any(feature = "glucose", feature = "minisat"))]
+impl OptValue for f64 {
+    fn is_valid(self) -> bool {
+        self.is_finite()
+    }
+}
+
 #[macro_export]
 macro_rules! create_solver {
     (cadical) => {
@@ -27,8 +118,8 @@ macro_rules! create_solver {
     };
 }
 
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RawStatus {
     Satisfiable,
     Unsatisfiable,
@@ -51,6 +142,7 @@ impl From<i32> for RawStatus {
     }
 }
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SatStatus {
     Satisfiable(Vec<i32>),
     Unsatisfiable,
@@ -63,9 +155,230 @@ impl Default for SatStatus {
     }
 }
 
+/// A model packed into a bitset, one bit per variable.
+///
+/// Bit `var - 1` holds the truth value of variable `var`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelBitset {
+    /// The packed truth values.
+    pub words: Vec<u64>,
+    /// The number of variables covered by `words`.
+    pub vars: usize,
+}
+
+impl ModelBitset {
+    /// Build a bitset over `1..=vars` from a per-variable value query.
+    pub fn from_fn<F>(vars: usize, mut value: F) -> Result<Self, SolverError>
+    where
+        F: FnMut(i32) -> Result<bool, SolverError>,
+    {
+        let mut words = vec![0u64; vars.div_ceil(64)];
+        for var in 1..=vars {
+            if value(var as i32)? {
+                words[(var - 1) / 64] |= 1 << ((var - 1) % 64);
+            }
+        }
+        Ok(Self { words, vars })
+    }
+
+    /// The truth value of `var`. Variables outside `1..=vars` are false.
+    pub fn bit(&self, var: i32) -> bool {
+        if var <= 0 || var as usize > self.vars {
+            return false;
+        }
+        let idx = var as usize - 1;
+        self.words[idx / 64] >> (idx % 64) & 1 == 1
+    }
+
+    /// Split into the packed words and the variable count.
+    pub fn into_parts(self) -> (Vec<u64>, usize) {
+        (self.words, self.vars)
+    }
+}
+
+/// The result of [`SatSolver::solve_benchmarked`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveOutcome {
+    /// The status and model, as returned by `solve_model`.
+    pub status: SatStatus,
+    /// Wall-clock time spent in `solve_model`.
+    pub elapsed: Duration,
+    /// Conflicts spent by the solve, if the backend reports them.
+    pub conflicts: Option<u64>,
+}
+
+/// Search counters returned by [`SatSolver::statistics`].
+///
+/// A field is `None` when the backend does not export that counter through
+/// `satgalaxy-core`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverStats {
+    pub conflicts: Option<u64>,
+    pub decisions: Option<u64>,
+    pub propagations: Option<u64>,
+    pub restarts: Option<u64>,
+    /// Learned clauses currently kept.
+    pub learned_clauses: Option<u64>,
+    /// Original (irredundant) clauses currently kept.
+    pub clauses: Option<u64>,
+    /// Peak memory allocated by the solver, in bytes.
+    pub memory_bytes: Option<u64>,
+    /// Time the solver reports having spent.
+    pub time: Option<Duration>,
+}
+
+/// Limits for each later solve, see [`SatSolver::set_budget`].
+///
+/// `None` leaves a resource unlimited. Counts are per solve call, not cumulative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Budget {
+    pub conflicts: Option<u64>,
+    pub decisions: Option<u64>,
+    pub propagations: Option<u64>,
+    /// Peak memory allocated by the solver, in MiB.
+    pub memory_mb: Option<u64>,
+}
+
+impl Budget {
+    /// Whether no resource is limited.
+    pub fn is_unlimited(&self) -> bool {
+        *self == Budget::default()
+    }
+}
+
+/// The limit of a [`Budget`] that stopped a solve, see [`SatSolver::exhausted_budget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BudgetLimit {
+    Conflicts,
+    Decisions,
+    Propagations,
+    Memory,
+}
+
+/// The result of [`SatSolver::count_models`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ModelCount {
+    /// The exact number of models, or `None` if counting stopped at the cap.
+    pub exact: Option<u64>,
+    /// Whether more than `cap` models exist.
+    pub hit_cap: bool,
+}
+
+/// Check that every literal of `clause` is nonzero and its variable is at most `max_var`.
+///
+/// The C backends read clauses as zero-terminated, so a 0 inside a clause would cut it
+/// short, and oversized variables overflow their literal encoding.
+#[cfg(any(
+    feature = "cadical",
+    feature = "glucose",
+    feature = "minisat",
+    feature = "picosat",
+    feature = "ipasir"
+))]
+pub(crate) fn check_clause(clause: &[i32], max_var: i32) -> Result<(), SolverError> {
+    match clause
+        .iter()
+        .find(|lit| **lit == 0 || lit.unsigned_abs() > max_var as u32)
+    {
+        Some(&lit) => Err(SolverError::InvalidLiteral(lit)),
+        None => Ok(()),
+    }
+}
+
+/// Shrink an unsatisfiable assumption set by deletion until it is minimal.
+pub(crate) fn shrink_core<S: SatSolver + ?Sized>(
+    solver: &mut S,
+    mut core: Vec<i32>,
+) -> Result<Vec<i32>, SolverError> {
+    let mut i = 0;
+    while i < core.len() {
+        let mut candidate = core.clone();
+        candidate.remove(i);
+        if solver.solve_with_assumptions(&candidate)? == RawStatus::Unsatisfiable {
+            core = candidate;
+        } else {
+            i += 1;
+        }
+    }
+    Ok(core)
+}
+
+/// The result of [`SatSolver::decide`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Decision {
+    /// The assumptions are feasible; holds the model.
+    Sat(Vec<i32>),
+    /// The assumptions are infeasible; holds the failed assumptions that conflict.
+    Unsat(Vec<i32>),
+}
+
+/// XORs with at most this many literals are expanded directly into `2^(n-1)` clauses.
+const XOR_DIRECT_LEN: usize = 4;
+
+/// Encode `lits[0] ^ lits[1] ^ ... == rhs` into CNF, allocating auxiliary variables
+/// above `max_var` for XORs longer than [`XOR_DIRECT_LEN`].
+fn xor_clauses(lits: &[i32], rhs: bool, mut max_var: i32) -> Vec<Vec<i32>> {
+    let mut clauses = Vec::new();
+    let mut rest = lits.to_vec();
+    while rest.len() > XOR_DIRECT_LEN {
+        max_var += 1;
+        let mut chunk: Vec<i32> = rest.drain(..XOR_DIRECT_LEN - 1).collect();
+        chunk.push(max_var);
+        xor_expand(&chunk, false, &mut clauses);
+        rest.push(max_var);
+    }
+    xor_expand(&rest, rhs, &mut clauses);
+    clauses
+}
+
+/// Forbid every assignment of `lits` whose parity differs from `rhs`.
+fn xor_expand(lits: &[i32], rhs: bool, clauses: &mut Vec<Vec<i32>>) {
+    for mask in 0u32..1 << lits.len() {
+        if (mask.count_ones() % 2 == 1) == rhs {
+            continue;
+        }
+        let clause = lits
+            .iter()
+            .enumerate()
+            .map(|(i, &lit)| if mask >> i & 1 == 1 { -lit } else { lit })
+            .collect();
+        clauses.push(clause);
+    }
+}
+
 pub trait SatSolver {
     fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError>;
 
+    /// Add a clause of typed literals or variables, e.g. `[x.positive(), y.negative()]`.
+    fn push_lits<I>(&mut self, clause: I) -> Result<(), SolverError>
+    where
+        Self: Sized,
+        I: IntoIterator,
+        I::Item: Into<Lit>,
+    {
+        let clause: Vec<i32> = clause.into_iter().map(|lit| lit.into().into()).collect();
+        self.push_clause(&clause)
+    }
+
+    /// Create a solver loaded with `clauses`.
+    fn from_clauses(clauses: &[Vec<i32>]) -> Result<Self, SolverError>
+    where
+        Self: Sized + Default,
+    {
+        let mut solver = Self::default();
+        for clause in clauses {
+            solver.push_clause(clause)?;
+        }
+        Ok(solver)
+    }
+
     fn solve_model(&mut self) -> Result<SatStatus, SolverError> {
         let status = self.solve_sat()?;
         return match status {
@@ -75,10 +388,653 @@ pub trait SatSolver {
         };
     }
     fn solve_sat(&mut self) -> Result<RawStatus, SolverError>;
+
+    /// The model of the last satisfiable solve, as the variables assigned true in
+    /// ascending order.
+    ///
+    /// Every backend uses this representation; false and unassigned variables are
+    /// omitted. Use `full_model` for a literal per variable.
     fn model(&mut self) -> Result<Vec<i32>, SolverError>;
+
+    /// The variables assigned true, the same as `model` under an explicit name.
+    fn model_positive(&mut self) -> Result<Vec<i32>, SolverError> {
+        self.model()
+    }
+
+    /// The model of the last satisfiable solve as one literal per variable
+    /// `1..=max_var`: `var` if it is true, `-var` otherwise.
+    ///
+    /// Variables the solver left unassigned are reported as false, like in `model`.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::solver::{CaDiCaLSolver, SatSolver};
+    /// let mut solver = CaDiCaLSolver::new();
+    /// solver.push_clause(&[-1, 2]).unwrap();
+    /// solver.push_clause(&[-2]).unwrap();
+    /// solver.push_clause(&[3]).unwrap();
+    /// solver.solve_sat().unwrap();
+    /// assert_eq!(solver.full_model().unwrap(), vec![-1, -2, 3]);
+    /// ```
+    fn full_model(&mut self) -> Result<Vec<i32>, SolverError> {
+        let max_var = self.max_var()?;
+        let bits = self.model_bitset()?;
+        Ok((1..=max_var)
+            .map(|var| if bits.bit(var) { var } else { -var })
+            .collect())
+    }
+
+    /// Whether the solver is still consistent, i.e. no conflict has been derived yet.
+    ///
+    /// Returns `false` once the clauses added so far are known to be unsatisfiable
+    /// (e.g. contradictory units), which is cheap to check before a full solve.
+    /// The default returns `true`; `GlucoseSolver` and `MinisatSolver` map it to
+    /// `okay`, and `PicoSATSolver` to `!is_inconsistent`.
+    fn is_ok(&mut self) -> Result<bool, SolverError> {
+        Ok(true)
+    }
+
+    /// The largest variable index known to the solver.
+    ///
+    /// Used to allocate fresh variables, e.g. by `add_xor`. The default returns an error;
+    /// the bundled backends override it.
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        Err(SolverError::UnsupportedOperation("variable counts"))
+    }
+
+    /// Drop every clause, variable and assumption, so that the solver can be reused for
+    /// an unrelated formula.
+    ///
+    /// The configuration survives: options, presets, budgets and termination callbacks
+    /// stay in effect. Per-variable state such as phases or frozen variables does not.
+    ///
+    /// # Availability
+    /// * `CaDiCaLSolver`, `GlucoseSolver`, `MinisatSolver`, `PicoSATSolver` - none of
+    ///   them can drop clauses in place, so the backend instance is replaced by a new one
+    ///   and the options set so far are applied to it again.
+    /// * Other solvers - returns an error.
+    fn reset(&mut self) -> Result<(), SolverError> {
+        Err(SolverError::UnsupportedOperation("resetting"))
+    }
+
+    /// Add the constraint that the XOR of `lits` equals `rhs`.
+    ///
+    /// XORs of up to four literals are expanded directly. Longer ones are split into
+    /// a chain of short XORs linked by fresh auxiliary variables allocated above
+    /// `max_var`, so models of the solver will also assign those variables.
+    fn add_xor(&mut self, lits: &[i32], rhs: bool) -> Result<(), SolverError> {
+        let max_var = if lits.len() > XOR_DIRECT_LEN {
+            let max_lit = lits.iter().map(|lit| lit.unsigned_abs()).max().unwrap_or(0);
+            self.max_var()?.max(max_lit as i32)
+        } else {
+            0
+        };
+        for clause in xor_clauses(lits, rhs, max_var) {
+            self.push_clause(&clause)?;
+        }
+        Ok(())
+    }
+
+    /// Install a callback polled during search; returning `true` stops the solve, which
+    /// then returns `RawStatus::Unknown`.
+    ///
+    /// # Availability
+    /// * `CaDiCaLSolver` - polled by CaDiCaL's terminator hook. For cancelling from
+    ///   another thread, `CaDiCaLSolver::termination_handle` is simpler.
+    /// * Other backends - returns an error; `satgalaxy-core` exports no interrupt hook.
+    fn set_terminate(
+        &mut self,
+        _callback: Box<dyn FnMut() -> bool + Send>,
+    ) -> Result<(), SolverError> {
+        Err(SolverError::UnsupportedOperation("termination callbacks"))
+    }
+
+    /// Solve, giving up with `RawStatus::Unknown` once `timeout` has elapsed.
+    ///
+    /// # Availability
+    /// * `CaDiCaLSolver` - checked by CaDiCaL's terminator hook.
+    /// * `PicoSATSolver` - solves in decision-limited slices and checks the clock between
+    ///   them, so the timeout may be overshot by one slice.
+    /// * `MinisatSolver`, `GlucoseSolver` - returns an error; `satgalaxy-core` exports
+    ///   neither their interrupt nor their budget API.
+    fn solve_with_timeout(&mut self, _timeout: Duration) -> Result<RawStatus, SolverError> {
+        Err(SolverError::UnsupportedOperation("timeouts"))
+    }
+
+    /// Limit every later solve by `budget`. A solve that exhausts it returns
+    /// `RawStatus::Unknown`, and `exhausted_budget` names the limit that was hit.
+    ///
+    /// # Availability
+    /// * `CaDiCaLSolver` - conflicts and decisions, through CaDiCaL's `limit`.
+    /// * `PicoSATSolver` - decisions and propagations; memory is checked between
+    ///   decision-limited slices, so the limit may be overshot by one slice.
+    /// * `MinisatSolver`, `GlucoseSolver` - only an unlimited budget; `satgalaxy-core`
+    ///   does not export their budget API.
+    ///
+    /// A budget with a limit the backend cannot enforce is rejected as a whole.
+    fn set_budget(&mut self, budget: Budget) -> Result<(), SolverError> {
+        if budget.is_unlimited() {
+            Ok(())
+        } else {
+            Err(SolverError::UnsupportedOperation("budgets"))
+        }
+    }
+
+    /// The limit that stopped the last solve, or `None` if it was not stopped by its
+    /// budget.
+    fn exhausted_budget(&mut self) -> Result<Option<BudgetLimit>, SolverError> {
+        Ok(None)
+    }
+
+    /// Prefer `lit` when the solver decides on its variable.
+    ///
+    /// A phase is only a hint: it steers decisions without constraining the formula.
+    ///
+    /// # Availability
+    /// * `PicoSATSolver` - `set_default_phase_lit`.
+    /// * `CaDiCaLSolver`, `MinisatSolver`, `GlucoseSolver` - returns an error;
+    ///   `satgalaxy-core` exports neither CaDiCaL's `phase` nor MiniSat's `setPolarity`.
+    fn set_phase(&mut self, _lit: i32) -> Result<(), SolverError> {
+        Err(SolverError::UnsupportedOperation("phase hints"))
+    }
+
+    /// Drop the phase set by `set_phase` for the variable of `lit`.
+    fn unset_phase(&mut self, _lit: i32) -> Result<(), SolverError> {
+        Err(SolverError::UnsupportedOperation("phase hints"))
+    }
+
+    /// Set the phase of every literal of `model`, so that the next solve starts its
+    /// search from that assignment.
+    ///
+    /// Pass a signed assignment such as `full_model`; `model` only lists the true
+    /// variables and leaves the phases of the false ones untouched.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::solver::{PicoSATSolver, SatSolver, SatStatus};
+    /// let mut solver = PicoSATSolver::new();
+    /// solver.push_clause(&[1, 2, 3]).unwrap();
+    /// solver.warm_start_from_model(&[-1, 2, -3]).unwrap();
+    /// assert_eq!(solver.solve_model().unwrap(), SatStatus::Satisfiable(vec![2]));
+    /// ```
+    fn warm_start_from_model(&mut self, model: &[i32]) -> Result<(), SolverError> {
+        if let Some(&lit) = model.iter().find(|&&lit| lit == 0) {
+            return Err(SolverError::InvalidLiteral(lit));
+        }
+        for &lit in model {
+            self.set_phase(lit)?;
+        }
+        Ok(())
+    }
+
+    /// Add `clause` guarded by a fresh selector variable `s`, i.e. `clause ∨ ¬s`, and
+    /// return `s`.
+    ///
+    /// The clause only constrains solves in which `s` holds: assume `s` (e.g. with
+    /// `solve_with_assumptions`) to enforce it for one solve, or use `activate` and
+    /// `retract` to fix it on or off for good. The selector is allocated above `max_var`.
+    fn add_retractable_clause(&mut self, clause: &[i32]) -> Result<i32, SolverError> {
+        let max_lit = clause
+            .iter()
+            .map(|lit| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        let selector = self.max_var()?.max(max_lit as i32) + 1;
+        let mut guarded = clause.to_vec();
+        guarded.push(-selector);
+        self.push_clause(&guarded)?;
+        Ok(selector)
+    }
+
+    /// Permanently enforce the clause guarded by `selector`.
+    fn activate(&mut self, selector: i32) -> Result<(), SolverError> {
+        self.push_clause(&[selector])
+    }
+
+    /// Permanently drop the clause guarded by `selector`.
+    fn retract(&mut self, selector: i32) -> Result<(), SolverError> {
+        self.push_clause(&[-selector])
+    }
+
+    /// Solve under `assumptions`, which only hold for this call.
+    ///
+    /// The default returns an error; the bundled backends override it.
+    fn solve_with_assumptions(&mut self, _assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        Err(SolverError::UnsupportedOperation("assumptions"))
+    }
+
+    /// The assumptions of the last `solve_with_assumptions` call that caused UNSAT.
+    ///
+    /// Derived from `last_conflict`, so it is empty after a non-UNSAT result or when the
+    /// formula is UNSAT on its own. `MinisatSolver` and `GlucoseSolver` return their
+    /// `unsat_core` instead.
+    fn failed_assumptions(&mut self) -> Result<Vec<i32>, SolverError> {
+        Ok(self
+            .last_conflict()?
+            .map(|conflict| conflict.into_iter().map(|lit| -lit).collect())
+            .unwrap_or_default())
+    }
+
+    /// An unsat core over the assumptions of the last `solve_with_assumptions` call.
+    ///
+    /// Returns the assumptions that, together with the formula, are unsatisfiable.
+    /// `CaDiCaLSolver` and `PicoSATSolver` report their failed assumptions, while
+    /// `MinisatSolver` and `GlucoseSolver` shrink the assumptions by deletion, re-solving
+    /// once per assumption, which yields a minimal core but replaces the model of the last
+    /// solve. Returns an error if the last solve was not UNSAT under assumptions.
+    fn unsat_core(&mut self) -> Result<Vec<i32>, SolverError> {
+        match self.last_conflict()? {
+            Some(conflict) => Ok(conflict.into_iter().map(|lit| -lit).collect()),
+            None => Err(SolverError::InvalidState(
+                "Last solve was not UNSAT under assumptions",
+            )),
+        }
+    }
+
+    /// Solve under `assumptions` and return the model or the conflicting assumptions.
+    ///
+    /// On UNSAT the core is taken from `last_conflict`; backends that do not expose it
+    /// report every assumption. An `Unknown` result is returned as an error.
+    /// # Example
+    /// Checking which options of a small configuration conflict:
+    /// ```rust
+    /// use satgalaxy::solver::{CaDiCaLSolver, Decision, SatSolver};
+    /// let (gpu, laptop, big_psu) = (1, 2, 3);
+    /// let mut solver = CaDiCaLSolver::new();
+    /// solver.push_clause(&[-gpu, big_psu]).unwrap(); // a GPU needs a big PSU
+    /// solver.push_clause(&[-laptop, -big_psu]).unwrap(); // a laptop has no room for one
+    ///
+    /// assert!(matches!(solver.decide(vec![gpu]).unwrap(), Decision::Sat(_)));
+    /// let Decision::Unsat(mut core) = solver.decide(vec![gpu, laptop]).unwrap() else {
+    ///     panic!("a GPU laptop is infeasible");
+    /// };
+    /// core.sort();
+    /// assert_eq!(core, vec![gpu, laptop]);
+    /// ```
+    fn decide(&mut self, assumptions: Vec<i32>) -> Result<Decision, SolverError> {
+        match self.solve_with_assumptions(&assumptions)? {
+            RawStatus::Satisfiable => self.model().map(Decision::Sat),
+            RawStatus::Unsatisfiable => {
+                let core = match self.last_conflict()? {
+                    Some(conflict) => conflict.into_iter().map(|lit| -lit).collect(),
+                    None => assumptions,
+                };
+                Ok(Decision::Unsat(core))
+            }
+            RawStatus::Unknown => Err(SolverError::Terminated),
+        }
+    }
+
+    /// Run `solve_model` and report its wall-clock time alongside the status.
+    ///
+    /// `conflicts` is taken from `statistics`, so it is `None` for the bundled backends,
+    /// since `satgalaxy-core` does not export their conflict counters.
+    fn solve_benchmarked(&mut self) -> Result<SolveOutcome, SolverError> {
+        let start = Instant::now();
+        let status = self.solve_model()?;
+        Ok(SolveOutcome {
+            status,
+            elapsed: start.elapsed(),
+            conflicts: self.statistics()?.conflicts,
+        })
+    }
+
+    /// Structured search counters, for collecting metrics without parsing printed stats.
+    ///
+    /// # Availability
+    /// * `CaDiCaLSolver` - `clauses` (irredundant clauses).
+    /// * `MinisatSolver`, `GlucoseSolver` - `learned_clauses` and `clauses`.
+    /// * `PicoSATSolver` - `decisions`, `propagations`, `clauses` (original clauses),
+    ///   `memory_bytes` and `time`.
+    ///
+    /// The default reports nothing.
+    fn statistics(&mut self) -> Result<SolverStats, SolverError> {
+        Ok(SolverStats::default())
+    }
+
+    /// The final conflict clause of the last solve, expressed over the assumptions.
+    ///
+    /// Returns `Some(clause)` after an UNSAT result, where `clause` contains the negation
+    /// of each assumption involved in the conflict (empty if the formula is UNSAT on its
+    /// own), and `None` after any other result or when the backend does not expose it.
+    ///
+    /// # Availability
+    /// * `CaDiCaLSolver` - derived from `failed` over the assumptions of the last `solve`.
+    /// * `PicoSATSolver` - derived from `failed_assumptions`.
+    /// * `MinisatSolver`, `GlucoseSolver` - their `conflict`, a minimal core found by
+    ///   re-solving once per assumption the first time it is asked for after a solve.
+    ///   Those solves replace the model of the last solve.
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        Ok(None)
+    }
+
+    /// The model of the last satisfiable solve packed into a [`ModelBitset`].
+    ///
+    /// The default is derived from `model` over `1..=max_var`, since `model` only lists
+    /// the true variables; the bundled backends override it to query each variable
+    /// directly without materializing the literal vector.
+    fn model_bitset(&mut self) -> Result<ModelBitset, SolverError> {
+        let vars = self.max_var()?.max(0) as usize;
+        let model: std::collections::HashSet<i32> = self.model()?.into_iter().collect();
+        ModelBitset::from_fn(vars, |var| Ok(model.contains(&var)))
+    }
+
+    /// Enumerate the models of the formula, see [`ModelIterator`].
+    fn models(&mut self) -> ModelIterator<'_, Self>
+    where
+        Self: Sized,
+    {
+        ModelIterator::new(self)
+    }
+
+    /// Count the assignments of `vars` that extend to a model, stopping once more than
+    /// `cap` are found.
+    ///
+    /// Like `count_models`, but models that only differ outside `vars` count once, and
+    /// the blocking clauses only mention `vars`. See [`ApproxCounter`] for formulas with
+    /// too many models to enumerate.
+    fn count_projected_models(
+        &mut self,
+        vars: &[i32],
+        cap: u64,
+    ) -> Result<ModelCount, SolverError> {
+        let mut count = 0u64;
+        for model in ModelIterator::new(self).project(vars) {
+            model?;
+            if count == cap {
+                return Ok(ModelCount {
+                    exact: None,
+                    hit_cap: true,
+                });
+            }
+            count += 1;
+        }
+        Ok(ModelCount {
+            exact: Some(count),
+            hit_cap: false,
+        })
+    }
+
+    /// Count the models of the formula, stopping once more than `cap` are found.
+    ///
+    /// Each model found is excluded with a blocking clause over the variables of its
+    /// `model_bitset`, so the solver is left with those clauses added. If more than
+    /// `cap` models exist, the result has `exact: None` and `hit_cap: true`
+    /// instead of a truncated count.
+    fn count_models(&mut self, cap: u64) -> Result<ModelCount, SolverError> {
+        let mut count = 0u64;
+        loop {
+            match self.solve_sat()? {
+                RawStatus::Satisfiable if count == cap => {
+                    return Ok(ModelCount {
+                        exact: None,
+                        hit_cap: true,
+                    });
+                }
+                RawStatus::Satisfiable => {
+                    count += 1;
+                    let bits = self.model_bitset()?;
+                    let block: Vec<i32> = (1..=bits.vars as i32)
+                        .map(|var| if bits.bit(var) { -var } else { var })
+                        .collect();
+                    self.push_clause(&block)?;
+                }
+                RawStatus::Unsatisfiable => {
+                    return Ok(ModelCount {
+                        exact: Some(count),
+                        hit_cap: false,
+                    });
+                }
+                RawStatus::Unknown => {
+                    return Err(SolverError::Terminated);
+                }
+            }
+        }
+    }
+}
+
+impl<S: SatSolver + ?Sized> SatSolver for Box<S> {
+    fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        (**self).push_clause(clause)
+    }
+    fn solve_model(&mut self) -> Result<SatStatus, SolverError> {
+        (**self).solve_model()
+    }
+    fn solve_sat(&mut self) -> Result<RawStatus, SolverError> {
+        (**self).solve_sat()
+    }
+    fn model(&mut self) -> Result<Vec<i32>, SolverError> {
+        (**self).model()
+    }
+    fn model_positive(&mut self) -> Result<Vec<i32>, SolverError> {
+        (**self).model_positive()
+    }
+    fn full_model(&mut self) -> Result<Vec<i32>, SolverError> {
+        (**self).full_model()
+    }
+    fn is_ok(&mut self) -> Result<bool, SolverError> {
+        (**self).is_ok()
+    }
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        (**self).max_var()
+    }
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        (**self).solve_with_assumptions(assumptions)
+    }
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        (**self).last_conflict()
+    }
+    fn unsat_core(&mut self) -> Result<Vec<i32>, SolverError> {
+        (**self).unsat_core()
+    }
+    fn failed_assumptions(&mut self) -> Result<Vec<i32>, SolverError> {
+        (**self).failed_assumptions()
+    }
+    fn statistics(&mut self) -> Result<SolverStats, SolverError> {
+        (**self).statistics()
+    }
+    fn set_terminate(
+        &mut self,
+        callback: Box<dyn FnMut() -> bool + Send>,
+    ) -> Result<(), SolverError> {
+        (**self).set_terminate(callback)
+    }
+    fn solve_with_timeout(&mut self, timeout: Duration) -> Result<RawStatus, SolverError> {
+        (**self).solve_with_timeout(timeout)
+    }
+    fn set_budget(&mut self, budget: Budget) -> Result<(), SolverError> {
+        (**self).set_budget(budget)
+    }
+    fn set_phase(&mut self, lit: i32) -> Result<(), SolverError> {
+        (**self).set_phase(lit)
+    }
+    fn unset_phase(&mut self, lit: i32) -> Result<(), SolverError> {
+        (**self).unset_phase(lit)
+    }
+    fn exhausted_budget(&mut self) -> Result<Option<BudgetLimit>, SolverError> {
+        (**self).exhausted_budget()
+    }
+    fn model_bitset(&mut self) -> Result<ModelBitset, SolverError> {
+        (**self).model_bitset()
+    }
+    fn reset(&mut self) -> Result<(), SolverError> {
+        (**self).reset()
+    }
+}
+
+/// A SAT backend chosen at runtime, e.g. from a CLI flag or a config file.
+///
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{Backend, SatSolver, SatStatus};
+/// let backend: Backend = "cadical".parse().unwrap();
+/// let mut solver = backend.build().unwrap();
+/// solver.push_clause(&[1, 2]).unwrap();
+/// solver.push_clause(&[-1]).unwrap();
+/// assert_eq!(solver.solve_model().unwrap(), SatStatus::Satisfiable(vec![2]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    CaDiCaL,
+    Glucose,
+    Minisat,
+    PicoSAT,
+}
+
+impl Backend {
+    /// All backends, in order of preference.
+    pub const ALL: [Backend; 4] = [
+        Backend::CaDiCaL,
+        Backend::Glucose,
+        Backend::Minisat,
+        Backend::PicoSAT,
+    ];
+
+    /// Whether the feature of this backend is enabled.
+    pub fn is_enabled(self) -> bool {
+        match self {
+            Backend::CaDiCaL => cfg!(feature = "cadical"),
+            Backend::Glucose => cfg!(feature = "glucose"),
+            Backend::Minisat => cfg!(feature = "minisat"),
+            Backend::PicoSAT => cfg!(feature = "picosat"),
+        }
+    }
+
+    /// Create a new solver of this backend with its default configuration, ready for
+    /// incremental use, see [`SolverConfig::build`].
+    ///
+    /// Returns an error if the feature of the backend is not enabled.
+    pub fn build(self) -> Result<Box<dyn SatSolver + Send>, SolverError> {
+        SolverConfig::default().build(self)
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Backend::CaDiCaL => "cadical",
+            Backend::Glucose => "glucose",
+            Backend::Minisat => "minisat",
+            Backend::PicoSAT => "picosat",
+        })
+    }
+}
+
+impl FromStr for Backend {
+    type Err = SolverError;
+
+    /// Parse a backend name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Backend::ALL
+            .into_iter()
+            .find(|backend| backend.to_string().eq_ignore_ascii_case(s))
+            .ok_or(SolverError::InvalidArgument("unknown solver backend"))
+    }
+}
+
+/// Clause-database cleanup between incremental solves.
+///
+/// # Availability
+/// * `PicoSATSolver` - both operations are native.
+/// * `CaDiCaLSolver` - `simplify` runs CaDiCaL's preprocessing; `remove_learned` enables
+///   the `reduce` option with `percentage`, clamped to 10..=100, as its target. The
+///   options stay set, so every later reduction during search uses that target and
+///   nothing is removed immediately.
+/// * `MinisatSolver`, `GlucoseSolver` - not implemented; `satgalaxy-core` does not export
+///   their clause-database operations.
+pub trait Inprocessing {
+    /// Simplify the formula at the top level.
+    fn simplify(&mut self) -> Result<(), SolverError>;
+    /// Remove `percentage` percent of the learned clauses (100 removes all).
+    ///
+    /// Backends without an immediate cleanup take this as a hint for their future
+    /// reductions, see the availability list above.
+    fn remove_learned(&mut self, percentage: u32) -> Result<(), SolverError>;
+}
+
+/// The backend used by [`quick_solve`]: CaDiCaL if enabled, then Glucose, MiniSat, PicoSAT.
+#[cfg(feature = "cadical")]
+type DefaultSolver = CaDiCaLSolver;
+#[cfg(all(not(feature = "cadical"), feature = "glucose"))]
+type DefaultSolver = GlucoseSolver;
+#[cfg(all(
+    not(any(feature = "cadical", feature = "glucose")),
+    feature = "minisat"
+))]
+type DefaultSolver = MinisatSolver;
+#[cfg(all(
+    not(any(feature = "cadical", feature = "glucose", feature = "minisat")),
+    feature = "picosat"
+))]
+type DefaultSolver = PicoSATSolver;
+
+/// Solve `clauses` with the default backend and return the status with the model.
+///
+/// The backend is CaDiCaL when the `cadical` feature is enabled, otherwise the first
+/// enabled of Glucose, MiniSat and PicoSAT.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{quick_solve, SatStatus};
+/// assert_eq!(quick_solve(&[vec![1, 2], vec![-1]]).unwrap(), SatStatus::Satisfiable(vec![2]));
+/// ```
+#[cfg(any(
+    feature = "cadical",
+    feature = "glucose",
+    feature = "minisat",
+    feature = "picosat"
+))]
+pub fn quick_solve(clauses: &[Vec<i32>]) -> Result<SatStatus, SolverError> {
+    DefaultSolver::from_clauses(clauses)?.solve_model()
+}
+
+/// How [`solve_file`] reads and solves a file.
+#[cfg(feature = "parser")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SolveOptions {
+    pub parser: crate::parser::ParserOptions,
+    pub config: SolverConfig,
+    /// Give up with `SatStatus::Unknown` after this long, see
+    /// [`SatSolver::solve_with_timeout`]. Backends without timeout support (MiniSat and
+    /// Glucose) ignore it and solve to completion.
+    pub timeout: Option<Duration>,
+}
+
+/// Read the DIMACS CNF file at `path` into a new solver of `backend` and solve it.
+///
+/// Compressed files are detected as by
+/// [`read_dimacs_from_file`](crate::parser::read_dimacs_from_file). The default options
+/// parse in `ParseMode::Inferred`, apply no preset and set no timeout.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{solve_file, Backend, SatStatus, SolveOptions};
+/// let path = std::env::temp_dir().join("satgalaxy_solve_file.cnf");
+/// std::fs::write(&path, "p cnf 2 2\n1 2 0\n-1 0\n").unwrap();
+/// let status = solve_file(&path, Backend::CaDiCaL, SolveOptions::default()).unwrap();
+/// assert_eq!(status, SatStatus::Satisfiable(vec![2]));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+#[cfg(feature = "parser")]
+pub fn solve_file<P: AsRef<std::path::Path>>(
+    path: P,
+    backend: Backend,
+    options: SolveOptions,
+) -> Result<SatStatus, crate::errors::ParserError> {
+    let mut solver = options.config.build(backend)?;
+    crate::parser::read_dimacs_from_file(path, options.parser, &mut solver)?;
+    let status = match options
+        .timeout
+        .map(|timeout| solver.solve_with_timeout(timeout))
+    {
+        None | Some(Err(SolverError::UnsupportedOperation(_))) => solver.solve_sat()?,
+        Some(status) => status?,
+    };
+    Ok(match status {
+        RawStatus::Satisfiable => SatStatus::Satisfiable(solver.model()?),
+        RawStatus::Unsatisfiable => SatStatus::Unsatisfiable,
+        RawStatus::Unknown => SatStatus::Unknown,
+    })
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MusStatus {
     Satisfiable,
     Unsatisfiable(Vec<usize>),
@@ -96,3 +1052,207 @@ pub trait MusSolver {
 
     fn solve_mus(&mut self) -> Result<MusStatus, SolverError>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn model_bitset() {
+        let bits = ModelBitset::from_fn(65, |var| Ok(var % 2 == 1)).unwrap();
+        assert_eq!(bits.words.len(), 2);
+        assert!(bits.bit(1));
+        assert!(!bits.bit(2));
+        assert!(bits.bit(65));
+        assert!(!bits.bit(0));
+        assert!(!bits.bit(66));
+    }
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json() {
+        let status = SatStatus::Satisfiable(vec![1, -2]);
+        let json = serde_json::to_string(&status).unwrap();
+        assert_eq!(json, r#"{"Satisfiable":[1,-2]}"#);
+        assert_eq!(serde_json::from_str::<SatStatus>(&json).unwrap(), status);
+        let stats = SolverStats {
+            decisions: Some(3),
+            ..SolverStats::default()
+        };
+        let json = serde_json::to_string(&stats).unwrap();
+        assert_eq!(serde_json::from_str::<SolverStats>(&json).unwrap(), stats);
+        assert_eq!(serde_json::to_string(&Lit::new(-4).unwrap()).unwrap(), "-4");
+        assert!(serde_json::from_str::<Lit>("0").is_err());
+    }
+    #[cfg(any(
+        feature = "cadical",
+        feature = "glucose",
+        feature = "minisat",
+        feature = "picosat",
+        feature = "ipasir"
+    ))]
+    #[test]
+    fn check_clause() {
+        assert!(super::check_clause(&[1, -2, 3], 3).is_ok());
+        assert_eq!(
+            super::check_clause(&[1, 0, 2], 3),
+            Err(SolverError::InvalidLiteral(0))
+        );
+        assert_eq!(
+            super::check_clause(&[-4], 3),
+            Err(SolverError::InvalidLiteral(-4))
+        );
+        assert!(super::check_clause(&[i32::MIN], i32::MAX).is_err());
+        for backend in Backend::ALL {
+            if let Ok(mut solver) = backend.build() {
+                assert!(solver.push_clause(&[1, 0, 2]).is_err());
+                assert!(solver.push_clause(&[i32::MIN]).is_err());
+            }
+        }
+    }
+    #[test]
+    fn backend() {
+        assert_eq!("CaDiCaL".parse::<Backend>().unwrap(), Backend::CaDiCaL);
+        assert_eq!("picosat".parse::<Backend>().unwrap(), Backend::PicoSAT);
+        assert!("kissat".parse::<Backend>().is_err());
+        for backend in Backend::ALL {
+            assert_eq!(backend.to_string().parse::<Backend>().unwrap(), backend);
+            let Ok(mut solver) = backend.build() else {
+                assert!(!backend.is_enabled());
+                continue;
+            };
+            solver.push_clause(&[1, 2]).unwrap();
+            solver.push_clause(&[-1]).unwrap();
+            assert_eq!(
+                solver.solve_model().unwrap(),
+                SatStatus::Satisfiable(vec![2])
+            );
+            assert_eq!(solver.model_positive().unwrap(), vec![2]);
+            assert_eq!(solver.full_model().unwrap(), vec![-1, 2]);
+        }
+    }
+    #[test]
+    fn reset() {
+        for backend in Backend::ALL {
+            let Ok(mut solver) = SolverConfig::preset(Preset::Plain).build(backend) else {
+                continue;
+            };
+            solver.push_clause(&[3]).unwrap();
+            solver.push_clause(&[-3]).unwrap();
+            assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+            solver.reset().unwrap();
+            solver.push_clause(&[1, 2]).unwrap();
+            solver.push_clause(&[-1]).unwrap();
+            assert_eq!(
+                solver.solve_model().unwrap(),
+                SatStatus::Satisfiable(vec![2])
+            );
+        }
+    }
+    /// The `SatSolver` contract every backend must meet, checked through static
+    /// dispatch so that each backend is also compiled against the trait on its own.
+    fn conformance<S: SatSolver>(mut solver: S) {
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        assert_eq!(solver.model().unwrap(), Vec::<i32>::new());
+        let clauses = [vec![1, 2], vec![-1, 3], vec![-2, -3, 4]];
+        for clause in &clauses {
+            solver.push_clause(clause).unwrap();
+        }
+        assert!(solver.push_clause(&[5, 0]).is_err());
+        assert_eq!(solver.max_var().unwrap(), 4);
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        let model = solver.full_model().unwrap();
+        assert_eq!(model.len(), 4);
+        assert!(clauses
+            .iter()
+            .all(|clause| clause.iter().any(|lit| model.contains(lit))));
+
+        assert_eq!(
+            solver.solve_with_assumptions(&[-3, -4]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        let failed = solver.failed_assumptions().unwrap();
+        assert!(!failed.is_empty());
+        assert!(failed.iter().all(|lit| [-3, -4].contains(lit)));
+        // Assumptions only hold for one call.
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        assert_eq!(
+            solver.solve_with_assumptions(&[3]).unwrap(),
+            RawStatus::Satisfiable
+        );
+        assert!(solver.full_model().unwrap().contains(&3));
+        solver.statistics().unwrap();
+
+        solver.push_clause(&[-3]).unwrap();
+        solver.push_clause(&[-2]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+        assert_eq!(solver.solve_model().unwrap(), SatStatus::Unsatisfiable);
+    }
+    #[cfg(feature = "cadical")]
+    #[test]
+    fn cadical_conformance() {
+        conformance(CaDiCaLSolver::new());
+    }
+    #[cfg(feature = "glucose")]
+    #[test]
+    fn glucose_conformance() {
+        conformance(GlucoseSolver::new());
+    }
+    #[cfg(feature = "minisat")]
+    #[test]
+    fn minisat_conformance() {
+        conformance(MinisatSolver::new());
+    }
+    #[cfg(all(feature = "parser", feature = "minisat"))]
+    #[test]
+    fn solve_file_timeout_fallback() {
+        let path = std::env::temp_dir().join("satgalaxy_solve_file_timeout.cnf");
+        std::fs::write(&path, "p cnf 2 2\n1 2 0\n-1 0\n").unwrap();
+        let options = SolveOptions {
+            timeout: Some(Duration::from_secs(1)),
+            ..SolveOptions::default()
+        };
+        let status = solve_file(&path, Backend::Minisat, options).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(status, SatStatus::Satisfiable(vec![2]));
+    }
+    #[cfg(feature = "picosat")]
+    #[test]
+    fn picosat_conformance() {
+        conformance(PicoSATSolver::new());
+    }
+    #[test]
+    fn boxed_conformance() {
+        for backend in Backend::ALL {
+            if let Ok(solver) = backend.build() {
+                conformance(solver);
+            }
+        }
+    }
+    #[test]
+    fn xor_clauses() {
+        for n in 1..=7i32 {
+            for rhs in [false, true] {
+                let lits: Vec<i32> = (1..=n).map(|v| if v % 2 == 0 { -v } else { v }).collect();
+                let clauses = super::xor_clauses(&lits, rhs, n);
+                let vars = clauses
+                    .iter()
+                    .flatten()
+                    .map(|l| l.abs())
+                    .max()
+                    .unwrap_or(n)
+                    .max(n);
+                let mut extensions = vec![0; 1 << n];
+                for mask in 0u32..1 << vars {
+                    let value = |lit: i32| (mask >> (lit.abs() - 1) & 1 == 1) == (lit > 0);
+                    if clauses.iter().all(|c| c.iter().any(|&l| value(l))) {
+                        let parity = lits.iter().filter(|&&l| value(l)).count() % 2 == 1;
+                        assert_eq!(parity, rhs);
+                        extensions[(mask & ((1 << n) - 1)) as usize] += 1;
+                    }
+                }
+                let expected = extensions.iter().filter(|&&e| e == 1).count();
+                assert_eq!(expected, 1 << (n - 1));
+                assert!(extensions.iter().all(|&e| e <= 1));
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use crate::errors::SolverError;
+
+use super::{RawStatus, SatSolver};
+
+/// An iterator over the models of a [`SatSolver`] (AllSAT).
+///
+/// Each model found is excluded with a blocking clause before the next solve, so the
+/// solver is left with those clauses added. Models are reported like
+/// `SatSolver::model`, as the variables assigned true; with a projection, only the
+/// projected variables are reported and blocked, so each distinct assignment of them is
+/// yielded once.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CaDiCaLSolver, ModelIterator, SatSolver};
+/// let mut solver = CaDiCaLSolver::new();
+/// solver.push_clause(&[1, 2]).unwrap();
+/// solver.push_clause(&[3]).unwrap();
+/// let mut models: Vec<Vec<i32>> = ModelIterator::new(&mut solver)
+///     .project(&[1, 2])
+///     .map(Result::unwrap)
+///     .collect();
+/// models.sort();
+/// assert_eq!(models, vec![vec![1], vec![1, 2], vec![2]]);
+/// ```
+pub struct ModelIterator<'a, S: SatSolver + ?Sized> {
+    solver: &'a mut S,
+    projection: Option<Vec<i32>>,
+    limit: Option<usize>,
+    found: usize,
+    done: bool,
+}
+
+impl<'a, S: SatSolver + ?Sized> ModelIterator<'a, S> {
+    pub fn new(solver: &'a mut S) -> Self {
+        Self {
+            solver,
+            projection: None,
+            limit: None,
+            found: 0,
+            done: false,
+        }
+    }
+
+    /// Only report and block the given variables.
+    pub fn project(mut self, vars: &[i32]) -> Self {
+        self.projection = Some(vars.iter().map(|var| var.abs()).collect());
+        self
+    }
+
+    /// Stop after `limit` models.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    fn next_model(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        match self.solver.solve_sat()? {
+            RawStatus::Satisfiable => {}
+            RawStatus::Unsatisfiable => return Ok(None),
+            RawStatus::Unknown => return Err(SolverError::Terminated),
+        }
+        let bits = self.solver.model_bitset()?;
+        let vars: Vec<i32> = match &self.projection {
+            Some(vars) => vars.clone(),
+            None => (1..=bits.vars as i32).collect(),
+        };
+        let block: Vec<i32> = vars
+            .iter()
+            .map(|&var| if bits.bit(var) { -var } else { var })
+            .collect();
+        self.solver.push_clause(&block)?;
+        Ok(Some(
+            vars.into_iter().filter(|&var| bits.bit(var)).collect(),
+        ))
+    }
+}
+
+impl<S: SatSolver + ?Sized> Iterator for ModelIterator<'_, S> {
+    type Item = Result<Vec<i32>, SolverError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.limit.is_some_and(|limit| self.found >= limit) {
+            return None;
+        }
+        match self.next_model() {
+            Ok(Some(model)) => {
+                self.found += 1;
+                Some(Ok(model))
+            }
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "picosat")]
+    use super::*;
+    #[test]
+    #[cfg(feature = "picosat")]
+    fn limit_and_projection() {
+        use crate::solver::PicoSATSolver;
+        let mut solver = PicoSATSolver::new();
+        SatSolver::push_clause(&mut solver, &[1, 2, 3]).unwrap();
+        let models = ModelIterator::new(&mut solver).limit(3).count();
+        assert_eq!(models, 3);
+        let rest: Vec<_> = ModelIterator::new(&mut solver)
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(rest.len(), 4);
+
+        let mut solver = PicoSATSolver::new();
+        SatSolver::push_clause(&mut solver, &[1, 2, 3]).unwrap();
+        let projected: Vec<_> = ModelIterator::new(&mut solver)
+            .project(&[1])
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(projected.len(), 2);
+    }
+}
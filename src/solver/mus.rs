@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use crate::errors::SolverError;
+
+use super::{MusSolver, MusStatus, RawStatus, SatSolver};
+
+/// Deletion-based MUS extraction over any [`SatSolver`] with assumptions.
+///
+/// Each clause is guarded by a selector variable `s` (pushed as `clause ∨ ¬s`), and
+/// clauses are tested one at a time: a clause whose removal leaves the rest unsatisfiable
+/// is dropped, otherwise it belongs to the MUS. After every UNSAT answer the candidate set
+/// is cut down to the failed assumptions, so backends with precise cores (`CaDiCaLSolver`,
+/// `PicoSATSolver`) skip most of the tests.
+///
+/// Selectors are allocated above every variable pushed before the first `solve_mus`;
+/// clauses pushed later must not use them.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CaDiCaLSolver, DeletionMus, MusSolver, MusStatus};
+/// let mut mus = DeletionMus::new(CaDiCaLSolver::new());
+/// mus.push_clause(&[1]).unwrap();
+/// mus.push_clause(&[2]).unwrap();
+/// mus.push_clause(&[-1]).unwrap();
+/// assert_eq!(mus.solve_mus().unwrap(), MusStatus::Unsatisfiable(vec![0, 2]));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DeletionMus<S> {
+    inner: S,
+    clauses: Vec<Vec<i32>>,
+    /// The selector of each clause loaded into `inner`.
+    selectors: Vec<i32>,
+    max_var: i32,
+}
+
+impl<S: SatSolver> DeletionMus<S> {
+    /// Extract MUSes with `inner`, which should not hold any clauses yet.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            clauses: vec![],
+            selectors: vec![],
+            max_var: 0,
+        }
+    }
+
+    /// The wrapped solver.
+    pub fn inner(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Push the clauses that have no selector yet into `inner`.
+    fn load(&mut self) -> Result<(), SolverError> {
+        for i in self.selectors.len()..self.clauses.len() {
+            self.max_var += 1;
+            let mut guarded = self.clauses[i].clone();
+            guarded.push(-self.max_var);
+            self.inner.push_clause(&guarded)?;
+            self.selectors.push(self.max_var);
+        }
+        Ok(())
+    }
+}
+
+/// Deletion-based MUS extraction over the clause sets guarded by `selectors`.
+///
+/// The result holds indices into `selectors`.
+fn deletion_mus<S: SatSolver + ?Sized>(
+    solver: &mut S,
+    selectors: &[i32],
+) -> Result<MusStatus, SolverError> {
+    let solve = |solver: &mut S, sets: &[usize]| {
+        let assumptions: Vec<i32> = sets.iter().map(|&i| selectors[i]).collect();
+        solver.solve_with_assumptions(&assumptions)
+    };
+    // Keep the sets of `sets` whose selectors failed in the last solve.
+    let refine = |solver: &mut S, sets: Vec<usize>| -> Vec<usize> {
+        let failed: HashSet<i32> = solver
+            .failed_assumptions()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        if failed.is_empty() {
+            return sets;
+        }
+        sets.into_iter()
+            .filter(|&i| failed.contains(&selectors[i]))
+            .collect()
+    };
+    let all: Vec<usize> = (0..selectors.len()).collect();
+    let mut pending = match solve(solver, &all)? {
+        RawStatus::Satisfiable => return Ok(MusStatus::Satisfiable),
+        RawStatus::Unknown => return Ok(MusStatus::Unknown),
+        RawStatus::Unsatisfiable => refine(solver, all),
+    };
+    let mut necessary = vec![];
+    while let Some(set) = pending.pop() {
+        let candidate: Vec<usize> = necessary.iter().chain(&pending).copied().collect();
+        match solve(solver, &candidate)? {
+            RawStatus::Satisfiable => necessary.push(set),
+            RawStatus::Unknown => return Ok(MusStatus::Unknown),
+            RawStatus::Unsatisfiable => {
+                // Necessary sets are in every core of the candidate sets.
+                let core: HashSet<usize> = refine(solver, candidate).into_iter().collect();
+                pending.retain(|i| core.contains(i));
+            }
+        }
+    }
+    necessary.sort_unstable();
+    Ok(MusStatus::Unsatisfiable(necessary))
+}
+
+impl<S: SatSolver> MusSolver for DeletionMus<S> {
+    fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        let max = clause
+            .iter()
+            .map(|lit| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0) as i32;
+        if let Some(&first) = self.selectors.first() {
+            if max >= first {
+                return Err(SolverError::InvalidArgument(
+                    "variable is used as a clause selector",
+                ));
+            }
+        } else {
+            self.max_var = self.max_var.max(max);
+        }
+        self.clauses.push(clause.to_vec());
+        Ok(())
+    }
+
+    fn solve_mus(&mut self) -> Result<MusStatus, SolverError> {
+        self.load()?;
+        deletion_mus(&mut self.inner, &self.selectors)
+    }
+}
+
+/// Group-MUS extraction: a minimal set of named clause groups that is unsatisfiable
+/// together with the hard clauses.
+///
+/// All clauses of a group share one selector variable, so groups are kept or dropped as
+/// a whole, which is the usual formulation for debugging specifications where each
+/// requirement expands to several clauses. `solve_mus` reports group indices, in order
+/// of first use, and `mus_groups` the group names. Selectors are allocated above
+/// every variable seen so far and rejected in later clauses.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CaDiCaLSolver, GroupMus, MusSolver};
+/// let mut mus: GroupMus<_> = GroupMus::new(CaDiCaLSolver::new());
+/// mus.push_clause(&[-1, -3]).unwrap();
+/// mus.add_clause("a implies b", &[-1, 2]).unwrap();
+/// mus.add_clause("a", &[1]).unwrap();
+/// mus.add_clause("a and c", &[1]).unwrap();
+/// mus.add_clause("a and c", &[3]).unwrap();
+/// assert_eq!(mus.mus_groups().unwrap(), vec!["a and c"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GroupMus<S, G = String> {
+    inner: S,
+    groups: Vec<G>,
+    /// The selector of each group.
+    selectors: Vec<i32>,
+    index: HashMap<G, usize>,
+    max_var: i32,
+}
+
+impl<S: SatSolver, G: Clone + Eq + Hash> GroupMus<S, G> {
+    /// Extract group-MUSes with `inner`, which should not hold any clauses yet.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            groups: vec![],
+            selectors: vec![],
+            index: HashMap::new(),
+            max_var: 0,
+        }
+    }
+
+    /// The wrapped solver.
+    pub fn inner(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Add `clause` to the group named `group`, creating the group on first use.
+    ///
+    /// Returns the index of the group.
+    pub fn add_clause(
+        &mut self,
+        group: impl Into<G>,
+        clause: &[i32],
+    ) -> Result<usize, SolverError> {
+        self.check(clause)?;
+        let group = group.into();
+        let index = match self.index.get(&group) {
+            Some(&index) => index,
+            None => {
+                let inner_max = self.inner.max_var().unwrap_or(0);
+                self.max_var = self.max_var.max(inner_max).max(Self::max_lit(clause)) + 1;
+                self.selectors.push(self.max_var);
+                self.groups.push(group.clone());
+                self.index.insert(group, self.groups.len() - 1);
+                self.groups.len() - 1
+            }
+        };
+        let mut guarded = clause.to_vec();
+        guarded.push(-self.selectors[index]);
+        self.inner.push_clause(&guarded)?;
+        Ok(index)
+    }
+
+    /// The name of the group with index `index`.
+    pub fn group(&self, index: usize) -> Option<&G> {
+        self.groups.get(index)
+    }
+
+    /// The names of the groups of a group-MUS; empty if the groups are satisfiable.
+    pub fn mus_groups(&mut self) -> Result<Vec<&G>, SolverError> {
+        match self.solve_mus()? {
+            MusStatus::Unsatisfiable(mus) => Ok(mus.iter().map(|&i| &self.groups[i]).collect()),
+            MusStatus::Satisfiable => Ok(vec![]),
+            MusStatus::Unknown => Err(SolverError::Terminated),
+        }
+    }
+
+    fn max_lit(clause: &[i32]) -> i32 {
+        clause
+            .iter()
+            .map(|lit| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0) as i32
+    }
+
+    fn check(&self, clause: &[i32]) -> Result<(), SolverError> {
+        if clause.iter().any(|lit| self.selectors.contains(&lit.abs())) {
+            return Err(SolverError::InvalidArgument(
+                "variable is used as a group selector",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<S: SatSolver, G: Clone + Eq + Hash> MusSolver for GroupMus<S, G> {
+    /// Add a hard clause, which belongs to no group and is never part of a MUS.
+    fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        self.check(clause)?;
+        self.max_var = self.max_var.max(Self::max_lit(clause));
+        self.inner.push_clause(clause)
+    }
+
+    fn solve_mus(&mut self) -> Result<MusStatus, SolverError> {
+        deletion_mus(&mut self.inner, &self.selectors)
+    }
+}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+    use crate::solver::CaDiCaLSolver;
+
+    #[test]
+    fn deletion_mus() {
+        let mut mus = DeletionMus::new(CaDiCaLSolver::new());
+        for clause in [
+            vec![1, 2],
+            vec![-1],
+            vec![3],
+            vec![-2],
+            vec![-3, 4],
+            vec![4, 5],
+        ] {
+            mus.push_clause(&clause).unwrap();
+        }
+        assert_eq!(
+            mus.solve_mus().unwrap(),
+            MusStatus::Unsatisfiable(vec![0, 1, 3])
+        );
+        assert!(mus.push_clause(&[7]).is_err());
+
+        let mut sat = DeletionMus::new(CaDiCaLSolver::new());
+        sat.push_clause(&[1, 2]).unwrap();
+        assert_eq!(sat.solve_mus().unwrap(), MusStatus::Satisfiable);
+    }
+
+    #[test]
+    fn group_mus() {
+        let mut mus: GroupMus<_, &str> = GroupMus::new(CaDiCaLSolver::new());
+        MusSolver::push_clause(&mut mus, &[-1, -2]).unwrap();
+        assert_eq!(mus.add_clause("x", &[1, 3]).unwrap(), 0);
+        assert_eq!(mus.add_clause("y", &[5]).unwrap(), 1);
+        assert_eq!(mus.add_clause("x", &[-3]).unwrap(), 0);
+        assert_eq!(mus.add_clause("z", &[2]).unwrap(), 2);
+        assert_eq!(mus.group(2), Some(&"z"));
+        // The selector of "x" is 4, above the variables of its first clause.
+        assert!(mus.add_clause("w", &[-4]).is_err());
+        assert_eq!(
+            mus.solve_mus().unwrap(),
+            MusStatus::Unsatisfiable(vec![0, 2])
+        );
+        assert_eq!(mus.mus_groups().unwrap(), vec![&"x", &"z"]);
+    }
+}
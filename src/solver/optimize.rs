@@ -0,0 +1,259 @@
+use crate::encoder::cardinality::{at_most_k, CardEncoding};
+use crate::encoder::pb::{pb_at_most, PbEncoding};
+use crate::encoder::VarManager;
+use crate::errors::{ParserError, SolverError};
+
+use super::{RawStatus, SatSolver};
+
+/// The result of [`Optimizer::solve_maxsat`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OptimizeStatus {
+    /// A model of minimal cost.
+    Optimal { cost: u64, model: Vec<i32> },
+    /// The search stopped before optimality was proven; the best model found so far.
+    Feasible { cost: u64, model: Vec<i32> },
+    /// The hard clauses are unsatisfiable.
+    Unsatisfiable,
+    /// The search stopped before finding any model.
+    Unknown,
+}
+
+/// Minimizes a linear objective over the models of a [`SatSolver`] by model-improving
+/// search.
+///
+/// The objective is a list of `(weight, lit)` terms, and a model costs the total weight
+/// of its true literals. Each model found is followed by the constraint
+/// `cost ≤ best - 1`, encoded as a cardinality constraint when all weights are equal and
+/// as a pseudo-Boolean one otherwise, until the solver proves no cheaper model exists.
+/// Every improvement is reported to the callback of `solve_maxsat_with`, so an
+/// interrupted search still yields an upper bound.
+///
+/// The bound constraints are guarded by activation literals and disabled afterwards, so
+/// the solver keeps its original models and can be reused.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CaDiCaLSolver, OptimizeStatus, Optimizer};
+/// let mut optimizer = Optimizer::new(CaDiCaLSolver::new());
+/// optimizer.add_hard_clause(&[1, 2]).unwrap();
+/// optimizer.add_hard_clause(&[-1, 3]).unwrap();
+/// optimizer.set_objective(&[(2, 1), (3, 2), (2, 3)]);
+/// let mut bounds = vec![];
+/// let status = optimizer.solve_maxsat_with(|cost| bounds.push(cost)).unwrap();
+/// assert_eq!(status, OptimizeStatus::Optimal { cost: 3, model: vec![2] });
+/// assert_eq!(bounds.last(), Some(&3));
+/// ```
+pub struct Optimizer<S: SatSolver> {
+    solver: S,
+    objective: Vec<(u64, i32)>,
+    encoding: PbEncoding,
+    /// The largest variable of the formula; larger ones are auxiliary.
+    num_vars: i32,
+}
+
+impl<S: SatSolver> Optimizer<S> {
+    /// Optimize over the models of `solver`, including the clauses it already holds.
+    pub fn new(mut solver: S) -> Self {
+        let num_vars = solver.max_var().unwrap_or(0);
+        Self {
+            solver,
+            objective: vec![],
+            encoding: PbEncoding::default(),
+            num_vars,
+        }
+    }
+
+    fn track_vars(&mut self, lits: impl IntoIterator<Item = i32>) {
+        let max = lits
+            .into_iter()
+            .map(|lit| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        self.num_vars = self.num_vars.max(max as i32);
+    }
+
+    /// Add a clause that every model must satisfy.
+    pub fn add_hard_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        self.track_vars(clause.iter().copied());
+        self.solver.push_clause(clause)
+    }
+
+    /// Replace the objective with `terms` of `(weight, lit)`.
+    pub fn set_objective(&mut self, terms: &[(u64, i32)]) {
+        self.track_vars(terms.iter().map(|&(_, lit)| lit));
+        self.objective = terms
+            .iter()
+            .copied()
+            .filter(|&(weight, _)| weight > 0)
+            .collect();
+    }
+
+    /// Use `encoding` for the bound constraints of weighted objectives.
+    pub fn set_encoding(&mut self, encoding: PbEncoding) {
+        self.encoding = encoding;
+    }
+
+    /// The underlying SAT solver.
+    pub fn inner(&mut self) -> &mut S {
+        &mut self.solver
+    }
+
+    /// The cost of `model` under the objective.
+    pub fn cost(&self, model: &[i32]) -> u64 {
+        self.objective
+            .iter()
+            .filter(|&&(_, lit)| {
+                if lit > 0 {
+                    model.contains(&lit)
+                } else {
+                    !model.contains(&-lit)
+                }
+            })
+            .map(|&(weight, _)| weight)
+            .sum()
+    }
+
+    /// Find a model of minimal cost.
+    pub fn solve_maxsat(&mut self) -> Result<OptimizeStatus, SolverError> {
+        self.solve_maxsat_with(|_| {})
+    }
+
+    /// Find a model of minimal cost, passing the cost of every improved model to
+    /// `on_bound`.
+    ///
+    /// Models list the true variables of the formula, like `SatSolver::model`.
+    pub fn solve_maxsat_with(
+        &mut self,
+        mut on_bound: impl FnMut(u64),
+    ) -> Result<OptimizeStatus, SolverError> {
+        if self
+            .objective
+            .iter()
+            .any(|&(weight, _)| weight > i64::MAX as u64)
+        {
+            return Err(SolverError::InvalidArgument(
+                "objective weight exceeds i64::MAX",
+            ));
+        }
+        let mut vars = VarManager::new(self.solver.max_var().unwrap_or(0).max(self.num_vars));
+        let mut activations = vec![];
+        let mut best: Option<(u64, Vec<i32>)> = None;
+        let status = loop {
+            // Only the latest bound is assumed, since it implies the earlier ones.
+            let assumptions: Vec<i32> = activations.last().copied().into_iter().collect();
+            match self.solver.solve_with_assumptions(&assumptions)? {
+                RawStatus::Satisfiable => {}
+                RawStatus::Unsatisfiable => {
+                    break match best.take() {
+                        Some((cost, model)) => OptimizeStatus::Optimal { cost, model },
+                        None => OptimizeStatus::Unsatisfiable,
+                    }
+                }
+                RawStatus::Unknown => {
+                    break match best.take() {
+                        Some((cost, model)) => OptimizeStatus::Feasible { cost, model },
+                        None => OptimizeStatus::Unknown,
+                    }
+                }
+            }
+            let num_vars = self.num_vars;
+            let model: Vec<i32> = self
+                .solver
+                .model()?
+                .into_iter()
+                .filter(|lit| lit.abs() <= num_vars)
+                .collect();
+            let cost = self.cost(&model);
+            on_bound(cost);
+            if cost == 0 {
+                break OptimizeStatus::Optimal { cost, model };
+            }
+            best = Some((cost, model));
+            let activation = vars.fresh();
+            self.strengthen(cost - 1, activation, &mut vars)?;
+            activations.push(activation);
+        };
+        // Disable the bounds, so later solves see the original formula.
+        for activation in activations {
+            self.solver.push_clause(&[-activation])?;
+        }
+        Ok(status)
+    }
+
+    /// Require `cost ≤ bound` while `activation` is assumed.
+    fn strengthen(
+        &mut self,
+        bound: u64,
+        activation: i32,
+        vars: &mut VarManager,
+    ) -> Result<(), SolverError> {
+        let mut clauses: Vec<Vec<i32>> = vec![];
+        let weight = self.objective[0].0;
+        let encoded = if self.objective.iter().all(|&(w, _)| w == weight) {
+            let lits: Vec<i32> = self.objective.iter().map(|&(_, lit)| lit).collect();
+            let k = (bound / weight) as usize;
+            at_most_k(&lits, k, CardEncoding::Totalizer, vars, &mut clauses)
+        } else {
+            let terms: Vec<(i64, i32)> = self
+                .objective
+                .iter()
+                .map(|&(w, lit)| (w as i64, lit))
+                .collect();
+            let bound = bound.min(i64::MAX as u64) as i64;
+            pb_at_most(&terms, bound, self.encoding, vars, &mut clauses)
+        };
+        encoded.map_err(|err| match err {
+            ParserError::SolverError(err) => err,
+            ParserError::InvalidLiteral(lit) => SolverError::InvalidLiteral(lit),
+            _ => SolverError::InvalidArgument("objective cannot be encoded"),
+        })?;
+        for mut clause in clauses {
+            clause.push(-activation);
+            self.solver.push_clause(&clause)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+    use crate::solver::CaDiCaLSolver;
+
+    #[test]
+    fn minimize() {
+        let mut optimizer = Optimizer::new(CaDiCaLSolver::new());
+        optimizer.add_hard_clause(&[1, 2, 3]).unwrap();
+        optimizer.add_hard_clause(&[-3, 4]).unwrap();
+        optimizer.set_objective(&[(4, 1), (2, 2), (1, 3), (2, 4)]);
+        let mut bounds = vec![];
+        let status = optimizer
+            .solve_maxsat_with(|cost| bounds.push(cost))
+            .unwrap();
+        assert_eq!(
+            status,
+            OptimizeStatus::Optimal {
+                cost: 2,
+                model: vec![2]
+            }
+        );
+        assert!(bounds.windows(2).all(|pair| pair[0] > pair[1]));
+        assert_eq!(bounds.last(), Some(&2));
+
+        // The bound `cost ≤ 1` was disabled.
+        assert_eq!(
+            optimizer.inner().solve_with_assumptions(&[1]).unwrap(),
+            RawStatus::Satisfiable
+        );
+        optimizer.set_objective(&[(1, 1), (1, 2), (1, 3), (1, 4)]);
+        assert!(matches!(
+            optimizer.solve_maxsat().unwrap(),
+            OptimizeStatus::Optimal { cost: 1, .. }
+        ));
+
+        let mut unsat = Optimizer::new(CaDiCaLSolver::new());
+        unsat.add_hard_clause(&[1]).unwrap();
+        unsat.add_hard_clause(&[-1]).unwrap();
+        assert_eq!(unsat.solve_maxsat().unwrap(), OptimizeStatus::Unsatisfiable);
+    }
+}
@@ -21,11 +21,23 @@ mod binding {
     include!("../../bindings/picosat_bindings.rs");
 }
 
-use std::{collections::HashSet, fmt::Display, os::raw, ptr::NonNull};
+use std::{
+    collections::HashSet,
+    ffi::CString,
+    io::Write,
+    os::raw,
+    path::Path,
+    ptr::NonNull,
+    time::{Duration, Instant},
+};
 
 use crate::{errors::SolverError, solver::RawStatus};
 
-use super::{MusSolver, MusStatus, SatSolver};
+use super::{
+    check_clause,
+    ffi_util::{zero_terminated, Callback},
+    Budget, BudgetLimit, Inprocessing, ModelBitset, MusSolver, MusStatus, SatSolver, SolverStats,
+};
 
 macro_rules! ffi_bind {
     (
@@ -58,19 +70,84 @@ macro_rules! ffi_bind {
     };
 }
 
-fn ptr_to_vec<T: Display + PartialEq + std::cmp::PartialEq<i32>>(ptr: *const T) -> Vec<T> {
-    let mut vec = Vec::new();
-    let mut curr = ptr;
-    let mut v = unsafe { curr.read() };
+extern "C" {
+    fn fopen(path: *const raw::c_char, mode: *const raw::c_char) -> *mut binding::FILE;
+    fn tmpfile() -> *mut binding::FILE;
+    fn rewind(file: *mut binding::FILE);
+    fn fread(ptr: *mut raw::c_void, size: usize, n: usize, file: *mut binding::FILE) -> usize;
+    fn fclose(file: *mut binding::FILE) -> raw::c_int;
+}
 
-    while !curr.is_null() && v != 0 {
-        unsafe {
-            vec.push(v);
-            curr = curr.offset(1);
-            v = curr.read();
+/// The `picosat_s_mus_assumptions` callback calling the closure in `state`.
+unsafe extern "C" fn mus_progress<F: FnMut(&[i32])>(state: *mut raw::c_void, mus: *const i32) {
+    let callback = &mut *(state as *mut Callback<F>);
+    callback.call(|progress| progress(zero_terminated(mus)));
+}
+
+/// The `picosat_s_humus` callback calling the closure in `state`.
+unsafe extern "C" fn humus_progress<F: FnMut(usize, usize)>(
+    state: *mut raw::c_void,
+    nmcs: i32,
+    nhumus: i32,
+) {
+    let callback = &mut *(state as *mut Callback<F>);
+    callback.call(|progress| progress(nmcs as usize, nhumus as usize));
+}
+
+/// The proof and core formats written by [`PicoSATSolver::write_trace`].
+///
+/// CaDiCaL's DRAT/LRAT tracers are not exported by `satgalaxy-core`, so proofs are
+/// currently only available from PicoSAT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// The original clauses of the clausal core, in DIMACS format.
+    ClausalCore,
+    /// PicoSAT's compact TraceCheck format.
+    Compact,
+    /// PicoSAT's extended TraceCheck format.
+    Extended,
+    /// The learned core clauses as a RUP proof.
+    Rup,
+}
+
+/// Parameters of the MUS extraction performed by [`PicoSATSolver`]'s `solve_mus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MusConfig {
+    /// Maximum number of trace-based core extraction rounds.
+    pub max_rounds: i32,
+    /// Number of rounds always run before stopping early on little progress.
+    pub min_rounds: i32,
+    /// Number of consecutive rounds with little progress tolerated before stopping.
+    pub max_non_red_rounds: i32,
+    /// Wall-clock budget for the whole extraction.
+    ///
+    /// The solver runs in slices of decisions with the clock checked in between, so
+    /// the budget is overrun by at most one slice. When it expires, `solve_mus` returns the current over-approximation
+    /// of the MUS as `MusStatus::Unsatisfiable`, or `MusStatus::Unknown` if no
+    /// extraction round has proved the formula unsatisfiable yet.
+    pub timeout: Option<Duration>,
+}
+
+impl Default for MusConfig {
+    fn default() -> Self {
+        Self {
+            max_rounds: 100,
+            min_rounds: 3,
+            max_non_red_rounds: 3,
+            timeout: None,
         }
     }
-    vec
+}
+
+/// The solver-wide options of a [`PicoSATSolver`], recorded so that `reset` can apply
+/// them to the new instance.
+#[derive(Debug, Clone, Copy, Default)]
+struct Settings {
+    measure_all_calls: bool,
+    verbosity: Option<i32>,
+    plain: Option<i32>,
+    global_default_phase: Option<i32>,
+    seed: Option<u32>,
 }
 
 /// `PicoSATSolver` is a wrapper for the [PicoSAT](https://github.com/arminbiere/picosat) Solver .
@@ -96,39 +173,130 @@ fn ptr_to_vec<T: Display + PartialEq + std::cmp::PartialEq<i32>>(ptr: *const T)
 ///     },
 /// }
 /// ```
+///  # Memory
+///  Clauses added with `SatSolver::push_clause` go straight to PicoSAT; no copy is kept
+///  on the Rust side. Only the clauses of `MusSolver::push_clause`, which the MUS
+///  extraction adds again in every round, are stored, and, while trace generation is
+///  enabled, the clauses `extract_core_problem` maps core indices back to.
 ///  # Usage
 ///  To use the `PicoSATSolver`, ensure the `picosat` feature is enabled in your `Cargo.toml`:
 ///  ```toml
 ///  [dependencies]
 ///  satgalaxy = { version = "x.y.z", features = ["picosat"] }
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct PicoSATSolver {
     inner: NonNull<binding::PicoSATSolver>,
+    /// Clauses added with `MusSolver::push_clause`, which are not given to PicoSAT until
+    /// `solve_mus`.
     clauses: Vec<Vec<i32>>,
+    /// The largest variable of `clauses`.
     vars: i32,
+    mus_config: MusConfig,
+    /// Clauses added while trace generation is enabled.
+    traced_clauses: Option<Vec<Vec<i32>>>,
+    /// Limits applied to every solve, see `set_budget`.
+    budget: Budget,
+    /// The limit that stopped the last solve.
+    exhausted: Option<BudgetLimit>,
+    /// The options set through `set_plain`, `set_seed` and the like, applied again by
+    /// `reset`.
+    settings: Settings,
+    /// The length of the groups added by `add_all_different`.
+    ado_len: Option<usize>,
+    /// The variables of the groups added by `add_all_different`.
+    ado_vars: HashSet<i32>,
 }
+// SAFETY: `inner` is owned by this value alone and PicoSAT keeps all of its state in
+// the instance, none of it thread-local. The progress callbacks given to PicoSAT only
+// live for the call that installs them, on the calling thread, and the other fields
+// are plain Rust data.
+unsafe impl Send for PicoSATSolver {}
 impl Default for PicoSATSolver {
     fn default() -> Self {
         Self::new()
     }
 }
 impl PicoSATSolver {
+    /// The largest variable index PicoSAT accepts, since literals are encoded as `2 * var + sign`.
+    pub const MAX_VAR: i32 = i32::MAX / 2;
+
     pub fn new() -> Self {
         unsafe {
             PicoSATSolver {
                 inner: NonNull::new(binding::picosat_s_init()).unwrap(),
                 clauses: Vec::new(),
                 vars: 0,
+                mus_config: MusConfig::default(),
+                traced_clauses: None,
+                budget: Budget::default(),
+                exhausted: None,
+                settings: Settings::default(),
+                ado_len: None,
+                ado_vars: HashSet::new(),
+            }
+        }
+    }
+    /// Solve under `assumptions` within the budget set by `set_budget`.
+    fn sat_within_budget(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        // Decisions per `sat` call between two memory checks.
+        const SLICE: u64 = 10_000;
+        let budget = self.budget;
+        self.exhausted = None;
+        let propagations = self.propagations()?;
+        if let Some(limit) = budget.propagations {
+            self.set_propagation_limit(propagations.saturating_add(limit))?;
+        }
+        let mut decisions_left = budget.decisions;
+        let status = loop {
+            // PicoSAT drops the assumptions after every `sat` call.
+            for &lit in assumptions {
+                self.assume(lit)?;
+            }
+            let slice = match budget.memory_mb {
+                Some(_) => decisions_left.map_or(SLICE, |left| left.min(SLICE)),
+                None => decisions_left.unwrap_or(u64::MAX),
+            };
+            let limit = match (budget.memory_mb, decisions_left) {
+                (None, None) => -1,
+                _ => i32::try_from(slice).unwrap_or(i32::MAX),
+            };
+            let decisions = self.decisions()?;
+            let status = self.sat(limit)?;
+            if status != RawStatus::Unknown {
+                break status;
+            }
+            let used = self.decisions()? - decisions;
+            decisions_left = decisions_left.map(|left| left.saturating_sub(used));
+            let propagated = self.propagations()? - propagations;
+            let memory = self.max_bytes_allocated()? as u64;
+            self.exhausted = if budget.propagations.is_some_and(|limit| propagated >= limit) {
+                Some(BudgetLimit::Propagations)
+            } else if decisions_left == Some(0) {
+                Some(BudgetLimit::Decisions)
+            } else if budget.memory_mb.is_some_and(|limit| memory >= limit << 20) {
+                Some(BudgetLimit::Memory)
+            } else {
+                None
+            };
+            if self.exhausted.is_some() || limit < 0 {
+                break status;
             }
+        };
+        if budget.propagations.is_some() {
+            self.set_propagation_limit(u64::MAX)?;
         }
+        Ok(status)
+    }
+    /// Set the parameters used by `solve_mus`.
+    pub fn set_mus_config(&mut self, config: MusConfig) {
+        self.mus_config = config;
     }
+
     fn error(&self) -> Result<(), SolverError> {
         unsafe {
             let code = binding::picosat_s_error(self.inner.as_ptr());
             if code != 0 {
-                let msg = binding::picosat_s_errmsg(code);
-                let msg = std::ffi::CStr::from_ptr(msg);
-                return Err(SolverError(msg.to_str().unwrap()));
+                return Err(SolverError::ffi(code, binding::picosat_s_errmsg(code)));
             }
         }
         Ok(())
@@ -165,15 +333,17 @@ impl PicoSATSolver {
     //     as set_output
     // }
 
-    ffi_bind! {
-        /// Measure all time spent in all calls in the solver
-        ///
-        /// By default only the time spent in 'picosat_s_sat' is measured.
-        ///
-        /// # Note
-        /// Enabling this may significantly increase time for adding large CNFs due to getrusage calls.
-        picosat_s_measure_all_calls () -> ();
-        as measure_all_calls
+    /// Measure all time spent in all calls in the solver
+    ///
+    /// By default only the time spent in 'picosat_s_sat' is measured.
+    ///
+    /// # Note
+    /// Enabling this may significantly increase time for adding large CNFs due to getrusage calls.
+    pub fn measure_all_calls(&mut self) -> Result<(), SolverError> {
+        unsafe { binding::picosat_s_measure_all_calls(self.inner.as_ptr()) };
+        self.error()?;
+        self.settings.measure_all_calls = true;
+        Ok(())
     }
 
     ffi_bind! {
@@ -185,40 +355,46 @@ impl PicoSATSolver {
         as set_prefix
     }
 
-    ffi_bind! {
-        /// Sets the verbosity level for the solver
-        ///
-        /// # Arguments
-        /// * `new_verbosity_level` - Verbosity level (1+ for detailed progress reports)
-        ///
-        /// # Note
-        /// Reports are printed to the output file set by `set_output`, prefixed by `set_prefix`
-        picosat_s_set_verbosity (new_verbosity_level: i32) -> ();
-        as set_verbosity
+    /// Sets the verbosity level for the solver
+    ///
+    /// # Arguments
+    /// * `new_verbosity_level` - Verbosity level (1+ for detailed progress reports)
+    ///
+    /// # Note
+    /// Reports are printed to the output file set by `set_output`, prefixed by `set_prefix`
+    pub fn set_verbosity(&mut self, new_verbosity_level: i32) -> Result<(), SolverError> {
+        unsafe { binding::picosat_s_set_verbosity(self.inner.as_ptr(), new_verbosity_level) };
+        self.error()?;
+        self.settings.verbosity = Some(new_verbosity_level);
+        Ok(())
     }
 
-    ffi_bind! {
-        /// Disables or enables preprocessing
-        ///
-        /// # Arguments
-        /// * `new_plain_value` - Non-zero to disable preprocessing (plain solving), zero to enable
-        ///
-        /// # Note
-        /// Currently affects only failed literal probing.
-        picosat_s_set_plain (new_plain_value: i32) -> ();
-        as set_plain
+    /// Disables or enables preprocessing
+    ///
+    /// # Arguments
+    /// * `new_plain_value` - Non-zero to disable preprocessing (plain solving), zero to enable
+    ///
+    /// # Note
+    /// Currently affects only failed literal probing.
+    pub fn set_plain(&mut self, new_plain_value: i32) -> Result<(), SolverError> {
+        unsafe { binding::picosat_s_set_plain(self.inner.as_ptr(), new_plain_value) };
+        self.error()?;
+        self.settings.plain = Some(new_plain_value);
+        Ok(())
     }
 
-    ffi_bind! {
-        /// Sets the default initial phase for decision variables
-        ///
-        /// # Arguments
-        /// * `phase` - Phase value: 0 (false), 1 (true), 2 (Jeroslow-Wang, default), 3 (random)
-        ///
-        /// # Note
-        /// After first assignment, variables reuse their previous value for decisions.
-        picosat_s_set_global_default_phase (phase: i32) -> ();
-        as set_global_default_phase
+    /// Sets the default initial phase for decision variables
+    ///
+    /// # Arguments
+    /// * `phase` - Phase value: 0 (false), 1 (true), 2 (Jeroslow-Wang, default), 3 (random)
+    ///
+    /// # Note
+    /// After first assignment, variables reuse their previous value for decisions.
+    pub fn set_global_default_phase(&mut self, phase: i32) -> Result<(), SolverError> {
+        unsafe { binding::picosat_s_set_global_default_phase(self.inner.as_ptr(), phase) };
+        self.error()?;
+        self.settings.global_default_phase = Some(phase);
+        Ok(())
     }
 
     ffi_bind! {
@@ -284,30 +460,38 @@ impl PicoSATSolver {
         as set_less_important_lit
     }
 
-    ffi_bind! {
-        /// Sets the seed for the random number generator
-        ///
-        /// # Arguments
-        /// * `random_number_generator_seed` - Seed value for the random number generator
-        ///
-        /// # Note
-        /// Useful for benchmarking different parameter sets, less effective for industrial examples.
-        picosat_s_set_seed (random_number_generator_seed: u32) -> ();
-        as set_seed
+    /// Sets the seed for the random number generator
+    ///
+    /// # Arguments
+    /// * `random_number_generator_seed` - Seed value for the random number generator
+    ///
+    /// # Note
+    /// Useful for benchmarking different parameter sets, less effective for industrial examples.
+    pub fn set_seed(&mut self, random_number_generator_seed: u32) -> Result<(), SolverError> {
+        unsafe { binding::picosat_s_set_seed(self.inner.as_ptr(), random_number_generator_seed) };
+        self.error()?;
+        self.settings.seed = Some(random_number_generator_seed);
+        Ok(())
     }
 
-    ffi_bind! {
-        /// Enables proof trace generation
-        ///
-        ///
-        /// # Note
-        /// - Not needed for `set_incremental_rup_file`
-        /// - Trace generation may not be included if compiled with full optimization
-        ///
-        /// # Returns
-        /// `true` if trace generation is supported, `false` otherwise
-        picosat_s_enable_trace_generation () -> bool => |res| res != 0;
-        as enable_trace_generation
+    /// Enables proof trace generation
+    ///
+    /// While enabled, clauses added through `SatSolver::push_clause` are also kept so
+    /// that `extract_core_problem` can map the clausal core back to clauses.
+    ///
+    /// # Note
+    /// - Not needed for `set_incremental_rup_file`
+    /// - Trace generation may not be included if compiled with full optimization
+    ///
+    /// # Returns
+    /// `true` if trace generation is supported, `false` otherwise
+    pub fn enable_trace_generation(&mut self) -> Result<bool, SolverError> {
+        let res = unsafe { binding::picosat_s_enable_trace_generation(self.inner.as_ptr()) };
+        self.error()?;
+        if res != 0 && self.traced_clauses.is_none() {
+            self.traced_clauses = Some(Vec::new());
+        }
+        Ok(res != 0)
     }
 
     // ffi_bind! {
@@ -530,11 +714,58 @@ impl PicoSATSolver {
         /// * `lit` - Literal to add to the ADC
         ///
         /// # Note
-        /// Only one global ADC is supported
+        /// Only one global ADC is supported. PicoSAT aborts on groups of different
+        /// lengths or sharing a variable; prefer `add_all_different`, which checks
+        /// both. Do not mix the two, since groups added here are not checked.
         picosat_s_add_ado_lit (lit: i32) -> ();
         as add_ado_lit
     }
 
+    /// Requires `group`, read as a bit vector of literal values, to differ from every
+    /// other group added so far.
+    ///
+    /// PicoSAT supports a single, global all-different constraint (ADC): every group
+    /// joins it and it cannot be removed. All groups must therefore have the same
+    /// length and use disjoint variables, each at most once. A group breaking these
+    /// rules is rejected with `SolverError::InvalidArgument`, and nothing is added.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::solver::{PicoSATSolver, RawStatus, SatSolver};
+    /// let mut solver = PicoSATSolver::new();
+    /// solver.add_all_different(&[1, 2]).unwrap();
+    /// solver.add_all_different(&[3, 4]).unwrap();
+    /// assert!(solver.add_all_different(&[5]).is_err());
+    /// // Make both groups equal.
+    /// for clause in [[1, -3], [-1, 3], [2, -4], [-2, 4]] {
+    ///     solver.push_clause(&clause).unwrap();
+    /// }
+    /// assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+    /// ```
+    pub fn add_all_different(&mut self, group: &[i32]) -> Result<(), SolverError> {
+        check_clause(group, Self::MAX_VAR)?;
+        if group.is_empty() {
+            return Err(SolverError::InvalidArgument("all-different group is empty"));
+        }
+        if self.ado_len.is_some_and(|len| len != group.len()) {
+            return Err(SolverError::InvalidArgument(
+                "all-different groups must have the same length",
+            ));
+        }
+        let vars: HashSet<i32> = group.iter().map(|lit| lit.abs()).collect();
+        if vars.len() != group.len() || !vars.is_disjoint(&self.ado_vars) {
+            return Err(SolverError::InvalidArgument(
+                "a variable may appear only once in all all-different groups",
+            ));
+        }
+        for &lit in group {
+            self.add_ado_lit(lit)?;
+        }
+        self.add_ado_lit(0)?;
+        self.ado_len = Some(group.len());
+        self.ado_vars.extend(vars);
+        Ok(())
+    }
+
     ffi_bind! {
         /// Runs the main SAT solving routine
         ///
@@ -665,9 +896,7 @@ impl PicoSATSolver {
         ///
         /// # Returns
         ///
-        picosat_s_failed_assumptions () -> Vec<i32> => |res| {
-            ptr_to_vec(res)
-        };
+        picosat_s_failed_assumptions () -> Vec<i32> => |res| zero_terminated(res).to_vec();
         as failed_assumptions
     }
 
@@ -690,9 +919,7 @@ impl PicoSATSolver {
             state: *mut raw::c_void,
             callback: Option<unsafe extern "C" fn(*mut raw::c_void, *const i32)>,
             fix: i32
-        ) -> Vec<i32> =>|res|ptr_to_vec(res);
-
-
+        ) -> Vec<i32> => |res| zero_terminated(res).to_vec();
         as mus_assumptions
     }
 
@@ -707,8 +934,9 @@ impl PicoSATSolver {
         /// - Reassumes all assumptions before returning
         ///
         /// # Returns
-        /// Pointer to zero-terminated array of consistent assumptions
-        picosat_s_maximal_satisfiable_subset_of_assumptions () -> *const i32;
+        /// The consistent assumptions
+        picosat_s_maximal_satisfiable_subset_of_assumptions () -> Vec<i32>
+            => |res| zero_terminated(res).to_vec();
         as maximal_satisfiable_subset_of_assumptions
     }
 
@@ -723,8 +951,9 @@ impl PicoSATSolver {
         /// - Requires assumptions set via `assume`
         ///
         /// # Returns
-        /// Pointer to zero-terminated array or NULL if none remain
-        picosat_s_next_maximal_satisfiable_subset_of_assumptions () -> *const i32;
+        /// The next maximal satisfiable subset, or `None` if none remain
+        picosat_s_next_maximal_satisfiable_subset_of_assumptions () -> Option<Vec<i32>>
+            => |res| (!res.is_null()).then(|| zero_terminated(res).to_vec());
         as next_maximal_satisfiable_subset_of_assumptions
     }
 
@@ -738,8 +967,9 @@ impl PicoSATSolver {
         /// Each assumed literal appears once in the result
         ///
         /// # Returns
-        /// Pointer to zero-terminated array of minimal correcting assumptions
-        picosat_s_next_minimal_correcting_subset_of_assumptions () -> *const i32;
+        /// The next minimal correcting subset, or `None` if none remain
+        picosat_s_next_minimal_correcting_subset_of_assumptions () -> Option<Vec<i32>>
+            => |res| (!res.is_null()).then(|| zero_terminated(res).to_vec());
         as next_minimal_correcting_subset_of_assumptions
     }
 
@@ -760,10 +990,61 @@ impl PicoSATSolver {
         picosat_s_humus (
             callback: Option<unsafe extern "C" fn(*mut raw::c_void, i32, i32)>,
             state: *mut raw::c_void
-        ) -> Vec<i32> =>|res|ptr_to_vec(res);
+        ) -> Vec<i32> => |res| zero_terminated(res).to_vec();
         as humus
     }
 
+    /// Like `mus_assumptions`, calling `progress` with each smaller set of failed
+    /// assumptions found on the way to the returned one.
+    ///
+    /// With `fix`, the assumptions found necessary or redundant are assigned
+    /// permanently. If `progress` panics, it is not called again and the panic is
+    /// resumed once PicoSAT returns.
+    /// # Example
+    /// ```rust
+    /// use satgalaxy::solver::{PicoSATSolver, RawStatus, SatSolver};
+    /// let mut solver = PicoSATSolver::new();
+    /// for clause in [[-10, 1], [-11, -1], [-12, 2]] {
+    ///     solver.push_clause(&clause).unwrap();
+    /// }
+    /// for lit in [10, 11, 12] {
+    ///     solver.assume(lit).unwrap();
+    /// }
+    /// assert_eq!(solver.sat(-1).unwrap(), RawStatus::Unsatisfiable);
+    /// let mut mus = solver
+    ///     .mus_assumptions_with_progress(false, |set| assert!(set.len() <= 3))
+    ///     .unwrap();
+    /// mus.sort();
+    /// assert_eq!(mus, vec![10, 11]);
+    /// ```
+    pub fn mus_assumptions_with_progress<F: FnMut(&[i32])>(
+        &mut self,
+        fix: bool,
+        progress: F,
+    ) -> Result<Vec<i32>, SolverError> {
+        let mut callback = Callback::new(progress);
+        let state = &mut callback as *mut Callback<F> as *mut raw::c_void;
+        let mus = self.mus_assumptions(state, Some(mus_progress::<F>), fix as i32);
+        callback.finish();
+        mus
+    }
+
+    /// Like `humus`, calling `progress` after each minimal correcting set with the
+    /// number of sets found so far and the size of the HUMUS so far.
+    ///
+    /// If `progress` panics, it is not called again and the panic is resumed once
+    /// PicoSAT returns.
+    pub fn humus_with_progress<F: FnMut(usize, usize)>(
+        &mut self,
+        progress: F,
+    ) -> Result<Vec<i32>, SolverError> {
+        let mut callback = Callback::new(progress);
+        let state = &mut callback as *mut Callback<F> as *mut raw::c_void;
+        let humus = self.humus(Some(humus_progress::<F>), state);
+        callback.finish();
+        humus
+    }
+
     ffi_bind! {
         /// Checks if old variable assignments changed between SAT calls
         ///
@@ -809,55 +1090,107 @@ impl PicoSATSolver {
         as corelit
     }
 
-    // ffi_bind! {
-    //     /// Writes clausal core to file
-    //     ///
-    //     /// # Arguments
-    //     /// * `core_file` - Output file for clausal core
-    //     ///
-    //     /// # Note
-    //     /// Requires trace generation enabled
-    //     picosat_s_write_clausal_core (core_file: *mut libc::FILE) -> ();
-    //     as write_clausal_core
-    // }
+    /// Writes a proof trace or the clausal core of the last UNSAT result to `path`.
+    ///
+    /// With the `parser` and `compression` features enabled, paths with a compressed
+    /// extension are compressed, see [`SmartWriter`](crate::parser::SmartWriter).
+    ///
+    /// # Note
+    /// Requires `enable_trace_generation` before adding clauses.
+    pub fn write_trace<P: AsRef<Path>>(
+        &mut self,
+        format: TraceFormat,
+        path: P,
+    ) -> Result<(), SolverError> {
+        self.check_trace()?;
+        #[cfg(all(feature = "parser", feature = "compression"))]
+        if crate::parser::SmartWriter::compresses(&path) {
+            let io_error = |err: std::io::Error| SolverError::Io(err.to_string());
+            let mut writer = crate::parser::SmartWriter::create(&path).map_err(io_error)?;
+            self.write_trace_to(format, &mut writer)?;
+            return writer.finish().map_err(io_error);
+        }
+        let path = CString::new(path.as_ref().as_os_str().as_encoded_bytes())
+            .map_err(|_| SolverError::InvalidArgument("trace path contains a NUL byte"))?;
+        let file = unsafe { fopen(path.as_ptr(), c"w".as_ptr()) };
+        if file.is_null() {
+            return Err(SolverError::Io(std::io::Error::last_os_error().to_string()));
+        }
+        self.write_trace_file(format, file)
+    }
 
-    // ffi_bind! {
-    //     /// Writes compact proof trace to file
-    //     ///
-    //     /// # Arguments
-    //     /// * `trace_file` - Output file for proof trace
-    //     ///
-    //     /// # Note
-    //     /// Requires trace generation enabled
-    //     picosat_s_write_compact_trace (trace_file: *mut libc::FILE) -> ();
-    //     as write_compact_trace
-    // }
+    /// Writes a proof trace or the clausal core of the last UNSAT result to `writer`.
+    ///
+    /// The trace is staged in a temporary file, since PicoSAT only writes to a `FILE*`.
+    pub fn write_trace_to<W: Write>(
+        &mut self,
+        format: TraceFormat,
+        mut writer: W,
+    ) -> Result<(), SolverError> {
+        self.check_trace()?;
+        let file = unsafe { tmpfile() };
+        if file.is_null() {
+            return Err(SolverError::Io(std::io::Error::last_os_error().to_string()));
+        }
+        self.trace_into(format, file);
+        let mut buf = [0u8; 8192];
+        let mut result = Ok(());
+        unsafe {
+            rewind(file);
+            loop {
+                let n = fread(buf.as_mut_ptr() as *mut raw::c_void, 1, buf.len(), file);
+                if n == 0 {
+                    break;
+                }
+                if let Err(err) = writer.write_all(&buf[..n]) {
+                    result = Err(SolverError::Io(err.to_string()));
+                    break;
+                }
+            }
+            fclose(file);
+        }
+        result?;
+        writer
+            .flush()
+            .map_err(|err| SolverError::Io(err.to_string()))?;
+        self.error()
+    }
 
-    // ffi_bind! {
-    //     /// Writes extended proof trace to file
-    //     ///
-    //     /// # Arguments
-    //     /// * `trace_file` - Output file for proof trace
-    //     ///
-    //     /// # Note
-    //     /// Requires trace generation enabled
-    //     picosat_s_write_extended_trace (trace_file: *mut libc::FILE) -> ();
-    //     as write_extended_trace
-    // }
+    fn check_trace(&mut self) -> Result<(), SolverError> {
+        if self.traced_clauses.is_none() {
+            return Err(SolverError::InvalidState("trace generation is not enabled"));
+        }
+        if self.res()? != RawStatus::Unsatisfiable {
+            return Err(SolverError::InvalidState(
+                "the last SAT call did not return UNSAT",
+            ));
+        }
+        Ok(())
+    }
 
-    // ffi_bind! {
-    //     /// Writes RUP trace to file
-    //     ///
-    //     /// # Arguments
-    //     /// * `trace_file` - Output file for RUP trace
-    //     ///
-    //     /// # Note
-    //     /// - Requires trace generation enabled
-    //     /// - Includes only learned core clauses
-    //     picosat_s_write_rup_trace (trace_file: *mut raw::) -> ();
+    fn trace_into(&mut self, format: TraceFormat, file: *mut binding::FILE) {
+        let solver = self.inner.as_ptr();
+        unsafe {
+            match format {
+                TraceFormat::ClausalCore => binding::picosat_s_write_clausal_core(solver, file),
+                TraceFormat::Compact => binding::picosat_s_write_compact_trace(solver, file),
+                TraceFormat::Extended => binding::picosat_s_write_extended_trace(solver, file),
+                TraceFormat::Rup => binding::picosat_s_write_rup_trace(solver, file),
+            }
+        }
+    }
 
-    //     as write_rup_trace
-    // }
+    fn write_trace_file(
+        &mut self,
+        format: TraceFormat,
+        file: *mut binding::FILE,
+    ) -> Result<(), SolverError> {
+        self.trace_into(format, file);
+        if unsafe { fclose(file) } != 0 {
+            return Err(SolverError::Io(std::io::Error::last_os_error().to_string()));
+        }
+        self.error()
+    }
 
     ffi_bind! {
         /// Checks if literal was used in resolution
@@ -882,17 +1215,207 @@ impl PicoSATSolver {
         picosat_s_leave ()->(); as leave
     }
 
+    /// Indices of the original clauses in the clausal core.
+    ///
+    /// # Note
+    /// Requires trace generation enabled and an UNSAT result from the last SAT call.
+    pub fn clausal_core(&mut self) -> Result<Vec<usize>, SolverError> {
+        let mut core = vec![];
+        for i in 0..self.added_original_clauses()? {
+            if self.coreclause(i)? {
+                core.push(i as usize);
+            }
+        }
+        Ok(core)
+    }
+
+    /// Builds a `Problem` containing only the clauses of the clausal core.
+    ///
+    /// Useful for cutting a large UNSAT instance down to a small reproducer.
+    ///
+    /// # Note
+    /// Call `enable_trace_generation` before adding clauses with `SatSolver::push_clause`,
+    /// then extract after an UNSAT result.
+    #[cfg(feature = "parser")]
+    pub fn extract_core_problem(&mut self) -> Result<crate::parser::Problem, SolverError> {
+        if self.res()? != RawStatus::Unsatisfiable {
+            return Err(SolverError::InvalidState(
+                "the last SAT call did not return UNSAT",
+            ));
+        }
+        let core = self.clausal_core()?;
+        let added = self.added_original_clauses()? as usize;
+        let traced = match &self.traced_clauses {
+            Some(traced) if traced.len() == added => traced,
+            _ => {
+                return Err(SolverError::InvalidState(
+                    "clauses were not recorded with trace generation enabled",
+                ))
+            }
+        };
+        let mut problem = crate::parser::Problem::new();
+        for i in core {
+            let clause = &traced[i];
+            let max = clause.iter().map(|lit| lit.unsigned_abs() as usize).max();
+            problem.num_vars = problem.num_vars.max(max.unwrap_or(0));
+            problem.clauses.push(clause.clone());
+        }
+        problem.num_clauses = problem.clauses.len();
+        Ok(problem)
+    }
+
+    /// Writes the clauses added with `MusSolver::push_clause` in DIMACS CNF format.
+    ///
+    /// Clause `i` of the output is the clause that MUS indices refer to as `i`.
+    #[cfg(feature = "parser")]
+    pub fn write_dimacs<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
+        crate::parser::write_dimacs(writer, self.vars as usize, &self.clauses, &[])
+    }
+
+    /// Enumerates minimal unsatisfiable subsets of the clauses added with
+    /// `MusSolver::push_clause`, as sorted lists of clause indices.
+    ///
+    /// The enumeration follows MARCO: a map solver proposes subsets of the clauses not
+    /// explored yet, largest first. An unsatisfiable subset is shrunk to a MUS with
+    /// `mus_assumptions`; a satisfiable one is grown to a maximal satisfiable subset,
+    /// whose complement is a minimal correcting subset. Either is then blocked in the
+    /// map. Enumeration stops once `limit` MUSes are found, so a small limit stays cheap
+    /// even when there are exponentially many. An empty result means the clauses are
+    /// satisfiable.
+    ///
+    /// # Note
+    /// The selector-guarded clauses and blocking clauses live in solvers created for the
+    /// call, so the formula of this solver is not modified and every call enumerates
+    /// from scratch.
+    pub fn all_muses(&mut self, limit: Option<usize>) -> Result<Vec<Vec<usize>>, SolverError> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let n = self.clauses.len();
+        // Clause `i` is guarded by the selector `base + i`.
+        let base = self.vars + 1;
+        let mut check = PicoSATSolver::new();
+        for (i, clause) in self.clauses.iter().enumerate() {
+            check.add_inner(-(base + i as i32))?;
+            check.add_inner_clause(clause)?;
+        }
+        let solve = |check: &mut PicoSATSolver, subset: &[usize]| {
+            for &i in subset {
+                check.assume(base + i as i32)?;
+            }
+            check.sat(-1)
+        };
+        // Variable `i + 1` of `map` selects clause `i`; its models are the unexplored
+        // subsets, which start out as large as possible.
+        let mut map = PicoSATSolver::new();
+        map.adjust(n as i32)?;
+        map.set_global_default_phase(1)?;
+        let mut muses = vec![];
+        while muses.len() < limit && map.sat(-1)? == RawStatus::Satisfiable {
+            let mut seed = vec![];
+            for i in 0..n {
+                if map.deref(i as i32 + 1)? != Some(false) {
+                    seed.push(i);
+                }
+            }
+            if solve(&mut check, &seed)? == RawStatus::Unsatisfiable {
+                let mut mus: Vec<usize> = check
+                    .mus_assumptions(std::ptr::null_mut(), None, 0)?
+                    .into_iter()
+                    .map(|lit| (lit - base) as usize)
+                    .collect();
+                mus.sort_unstable();
+                // No superset of a MUS is explored again.
+                let block: Vec<i32> = mus.iter().map(|&i| -(i as i32 + 1)).collect();
+                map.add_inner_clause(&block)?;
+                muses.push(mus);
+                continue;
+            }
+            let mut selected = vec![false; n];
+            for &i in &seed {
+                selected[i] = true;
+            }
+            for (i, selected) in selected.iter_mut().enumerate() {
+                if !*selected {
+                    seed.push(i);
+                    *selected = solve(&mut check, &seed)? == RawStatus::Satisfiable;
+                    if !*selected {
+                        seed.pop();
+                    }
+                }
+            }
+            // No subset of a maximal satisfiable subset is explored again.
+            let mcs: Vec<i32> = (0..n)
+                .filter(|&i| !selected[i])
+                .map(|i| i as i32 + 1)
+                .collect();
+            if mcs.is_empty() {
+                break;
+            }
+            map.add_inner_clause(&mcs)?;
+        }
+        muses.sort();
+        Ok(muses)
+    }
+
+    /// Replace the PicoSAT instance with an empty one.
+    fn renew_inner(&mut self) {
+        unsafe {
+            binding::picosat_s_reset(self.inner.as_ptr());
+            self.inner = NonNull::new(binding::picosat_s_init()).unwrap();
+        }
+        self.traced_clauses = None;
+    }
+
+    /// Run `sat` under `assumptions` until it answers or `deadline` passes.
+    ///
+    /// With a deadline, `sat` runs in slices of decisions with the clock checked in
+    /// between, assuming `assumptions` again for every slice since PicoSAT drops them
+    /// after each call. Returns `Unknown` once the deadline has passed.
+    fn sat_until(
+        &mut self,
+        assumptions: &[i32],
+        deadline: Option<Instant>,
+    ) -> Result<RawStatus, SolverError> {
+        // Decisions per `sat` call between two clock checks.
+        const SLICE: i32 = 10_000;
+        loop {
+            for &lit in assumptions {
+                self.assume(lit)?;
+            }
+            let Some(deadline) = deadline else {
+                return self.sat(-1);
+            };
+            let status = self.sat(SLICE)?;
+            if status != RawStatus::Unknown || Instant::now() >= deadline {
+                return Ok(status);
+            }
+        }
+    }
+
+    /// Shrink `redundant_indices` with trace-based core extraction.
+    ///
+    /// Returns `Unknown` if `deadline` passes before any round proved unsatisfiability.
     fn core_extraction(
         &mut self,
-        max_rounds: i32,
-        min_rounds: i32,
-        max_non_red_rounds: i32,
+        config: &MusConfig,
+        deadline: Option<Instant>,
         redundant_indices: &mut HashSet<usize>,
     ) -> Result<RawStatus, SolverError> {
+        let MusConfig {
+            max_rounds,
+            min_rounds,
+            max_non_red_rounds,
+            ..
+        } = *config;
         let mut non_red_count = 0;
         let mut n = self.clauses.len();
 
         for round in 1..=max_rounds {
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                if round == 1 {
+                    return Ok(RawStatus::Unknown);
+                }
+                break;
+            }
             self.set_seed(round as u32)?;
 
             // 添加子句
@@ -904,10 +1427,20 @@ impl PicoSATSolver {
                 }
             }
 
-            let res: RawStatus = self.sat(-1)?;
+            let res = self.sat_until(&[], deadline)?;
             if res == RawStatus::Satisfiable {
                 return Ok(res);
             }
+            if res == RawStatus::Unknown {
+                // The deadline passed during the round; its clauses are dropped and the
+                // previous round's result stands.
+                self.renew_inner();
+                return Ok(if round == 1 {
+                    RawStatus::Unknown
+                } else {
+                    RawStatus::Unsatisfiable
+                });
+            }
             for i in 0..self.clauses.len() {
                 if redundant_indices.contains(&i) {
                     continue;
@@ -918,10 +1451,7 @@ impl PicoSATSolver {
             }
             let old_n = n;
             n = self.clauses.len() - redundant_indices.len();
-            unsafe {
-                binding::picosat_s_reset(self.inner.as_ptr());
-                self.inner = NonNull::new(binding::picosat_s_init()).unwrap();
-            }
+            self.renew_inner();
             if round >= min_rounds {
                 let red = old_n - n;
                 if red < 10 && (100 * red + 99) / old_n < 2 {
@@ -947,11 +1477,16 @@ impl PicoSATSolver {
 
 impl SatSolver for PicoSATSolver {
     fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
-        self.add_inner_clause(clause)
+        check_clause(clause, Self::MAX_VAR)?;
+        self.add_inner_clause(clause)?;
+        if let Some(traced) = self.traced_clauses.as_mut() {
+            traced.push(clause.to_vec());
+        }
+        Ok(())
     }
 
     fn solve_sat(&mut self) -> Result<RawStatus, SolverError> {
-        self.sat(-1)
+        self.sat_within_budget(&[])
     }
 
     fn model(&mut self) -> Result<Vec<i32>, SolverError> {
@@ -965,10 +1500,118 @@ impl SatSolver for PicoSATSolver {
         }
         Ok(model)
     }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        self.sat_within_budget(assumptions)
+    }
+
+    fn statistics(&mut self) -> Result<SolverStats, SolverError> {
+        Ok(SolverStats {
+            decisions: Some(self.decisions()?),
+            propagations: Some(self.propagations()?),
+            clauses: Some(self.added_original_clauses()? as u64),
+            memory_bytes: Some(self.max_bytes_allocated()? as u64),
+            time: Some(Duration::from_secs_f64(self.seconds()?.max(0.0))),
+            ..SolverStats::default()
+        })
+    }
+
+    fn solve_with_timeout(&mut self, timeout: Duration) -> Result<RawStatus, SolverError> {
+        self.sat_until(&[], Some(Instant::now() + timeout))
+    }
+
+    fn set_phase(&mut self, lit: i32) -> Result<(), SolverError> {
+        check_clause(&[lit], Self::MAX_VAR)?;
+        self.set_default_phase_lit(lit, 1)
+    }
+
+    fn unset_phase(&mut self, lit: i32) -> Result<(), SolverError> {
+        check_clause(&[lit], Self::MAX_VAR)?;
+        self.set_default_phase_lit(lit, 0)
+    }
+
+    fn set_budget(&mut self, budget: Budget) -> Result<(), SolverError> {
+        if budget.conflicts.is_some() {
+            return Err(SolverError::UnsupportedOperation("conflict budgets"));
+        }
+        self.budget = budget;
+        Ok(())
+    }
+
+    fn exhausted_budget(&mut self) -> Result<Option<BudgetLimit>, SolverError> {
+        Ok(self.exhausted)
+    }
+
+    fn model_bitset(&mut self) -> Result<ModelBitset, SolverError> {
+        let vars = self.variables()?;
+        ModelBitset::from_fn(vars as usize, |var| Ok(self.deref(var)? == Some(true)))
+    }
+
+    fn is_ok(&mut self) -> Result<bool, SolverError> {
+        Ok(!self.is_inconsistent()?)
+    }
+
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        self.variables()
+    }
+
+    fn reset(&mut self) -> Result<(), SolverError> {
+        let traced = self.traced_clauses.is_some();
+        unsafe {
+            binding::picosat_s_reset(self.inner.as_ptr());
+            self.inner = NonNull::new(binding::picosat_s_init()).unwrap();
+        }
+        self.clauses.clear();
+        self.vars = 0;
+        self.traced_clauses = None;
+        self.exhausted = None;
+        self.ado_len = None;
+        self.ado_vars.clear();
+        // Trace generation must be enabled before anything else is set.
+        if traced {
+            self.enable_trace_generation()?;
+        }
+        let settings = self.settings;
+        if settings.measure_all_calls {
+            self.measure_all_calls()?;
+        }
+        if let Some(level) = settings.verbosity {
+            self.set_verbosity(level)?;
+        }
+        if let Some(plain) = settings.plain {
+            self.set_plain(plain)?;
+        }
+        if let Some(phase) = settings.global_default_phase {
+            self.set_global_default_phase(phase)?;
+        }
+        if let Some(seed) = settings.seed {
+            self.set_seed(seed)?;
+        }
+        Ok(())
+    }
+
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        if self.res()? != RawStatus::Unsatisfiable {
+            return Ok(None);
+        }
+        let conflict = self.failed_assumptions()?;
+        Ok(Some(conflict.into_iter().map(|lit| -lit).collect()))
+    }
+}
+
+impl Inprocessing for PicoSATSolver {
+    fn simplify(&mut self) -> Result<(), SolverError> {
+        PicoSATSolver::simplify(self)
+    }
+
+    fn remove_learned(&mut self, percentage: u32) -> Result<(), SolverError> {
+        PicoSATSolver::remove_learned(self, percentage.min(100))
+    }
 }
 
 impl MusSolver for PicoSATSolver {
     fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        check_clause(clause, Self::MAX_VAR)?;
         self.vars = clause
             .iter()
             .map(|lit| lit.abs())
@@ -980,56 +1623,66 @@ impl MusSolver for PicoSATSolver {
     }
 
     fn solve_mus(&mut self) -> Result<MusStatus, SolverError> {
+        let config = self.mus_config;
+        let deadline = config.timeout.map(|timeout| Instant::now() + timeout);
+        let expired = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
         let mut redundant_indices: HashSet<usize> = HashSet::new();
+        // Whether a core extraction round proved the remaining clauses unsatisfiable.
+        let mut proved = false;
         if self.enable_trace_generation()? {
-            let res = self.core_extraction(100, 3, 3, &mut redundant_indices)?;
-            if res == RawStatus::Satisfiable {
-                return Ok(MusStatus::Satisfiable);
+            let res = self.core_extraction(&config, deadline, &mut redundant_indices)?;
+            match res {
+                RawStatus::Satisfiable => return Ok(MusStatus::Satisfiable),
+                RawStatus::Unknown => return Ok(MusStatus::Unknown),
+                RawStatus::Unsatisfiable => proved = true,
             }
         }
+        let mut core: Vec<usize> = (0..self.clauses.len())
+            .filter(|i| !redundant_indices.contains(i))
+            .collect();
+        if expired() {
+            return Ok(if proved {
+                MusStatus::Unsatisfiable(core)
+            } else {
+                MusStatus::Unknown
+            });
+        }
         let idx: i32 = self.vars + 1;
-        for (i, clause) in self.clauses.iter().enumerate() {
-            if redundant_indices.contains(&i) {
-                continue;
-            }
+        for &i in &core {
             self.add_inner(-(idx + i as i32))?;
-            self.add_inner_clause(&clause)?;
+            self.add_inner_clause(&self.clauses[i])?;
         }
-
-        for i in 0..self.clauses.len() {
-            if !redundant_indices.contains(&i) {
-                self.assume(idx + i as i32)?;
-            }
+        let selectors =
+            |subset: &[usize]| -> Vec<i32> { subset.iter().map(|&i| idx + i as i32).collect() };
+        match self.sat_until(&selectors(&core), deadline)? {
+            RawStatus::Satisfiable => return Ok(MusStatus::Satisfiable),
+            RawStatus::Unknown if proved => return Ok(MusStatus::Unsatisfiable(core)),
+            RawStatus::Unknown => return Ok(MusStatus::Unknown),
+            RawStatus::Unsatisfiable => {}
         }
-        let status = self.sat(-1)?;
-        match status {
-            RawStatus::Satisfiable => Ok(MusStatus::Satisfiable),
-            RawStatus::Unsatisfiable => {
-                redundant_indices.extend(0..self.clauses.len());
-                let mut mus = unsafe {
-                    binding::picosat_s_mus_assumptions(
-                        self.inner.as_ptr(),
-                        std::ptr::null_mut(),
-                        None,
-                        1,
-                    )
-                };
-                self.error()?;
-                while !mus.is_null() {
-                    let assumption = unsafe { *mus };
-                    if assumption == 0 {
-                        break;
-                    }
-                    redundant_indices.remove(&((assumption - idx) as usize));
-                    mus = unsafe { mus.offset(1) };
+        // Deletion-based minimization. `core` stays unsatisfiable, so when the deadline
+        // passes it is returned as the current over-approximation of the MUS; its first
+        // `necessary` clauses are known to be in the MUS.
+        let mut necessary = 0;
+        let mut failed: HashSet<i32> = self.failed_assumptions()?.into_iter().collect();
+        loop {
+            // Clauses outside the failed assumptions are not needed for the conflict.
+            core.retain(|&i| failed.contains(&(idx + i as i32)));
+            if necessary == core.len() || expired() {
+                break;
+            }
+            let mut rest = core.clone();
+            rest.remove(necessary);
+            match self.sat_until(&selectors(&rest), deadline)? {
+                RawStatus::Unsatisfiable => {
+                    failed = self.failed_assumptions()?.into_iter().collect();
+                    core = rest;
                 }
-                let mus: Vec<usize> = (0..self.clauses.len())
-                    .filter(|i| !redundant_indices.contains(&i))
-                    .collect::<Vec<usize>>();
-                Ok(MusStatus::Unsatisfiable(mus))
+                RawStatus::Satisfiable => necessary += 1,
+                RawStatus::Unknown => break,
             }
-            RawStatus::Unknown => Ok(MusStatus::Unknown),
         }
+        Ok(MusStatus::Unsatisfiable(core))
     }
 }
 
@@ -1043,11 +1696,63 @@ impl Drop for PicoSATSolver {
 
 #[cfg(test)]
 mod tests {
-    use crate::solver::SatSolver;
     use crate::solver::SatStatus;
+    use crate::solver::{Decision, SatSolver};
 
     use super::*;
     #[test]
+    fn sat_clauses_are_not_copied() {
+        let mut solver = PicoSATSolver::new();
+        for var in 1..100 {
+            SatSolver::push_clause(&mut solver, &[var, -(var + 1)]).unwrap();
+        }
+        assert!(solver.clauses.is_empty());
+        assert!(solver.traced_clauses.is_none());
+        MusSolver::push_clause(&mut solver, &[1]).unwrap();
+        assert_eq!(solver.clauses, vec![vec![1]]);
+    }
+    #[test]
+    fn mus_progress_sets_shrink() {
+        let mut solver = PicoSATSolver::new();
+        for clause in [[-10, 1], [-11, -1], [-12, 2], [-13, -2]] {
+            SatSolver::push_clause(&mut solver, &clause).unwrap();
+        }
+        for lit in 10..=13 {
+            solver.assume(lit).unwrap();
+        }
+        assert_eq!(solver.sat(-1).unwrap(), RawStatus::Unsatisfiable);
+        let mut sets: Vec<Vec<i32>> = Vec::new();
+        let mut mus = solver
+            .mus_assumptions_with_progress(false, |set| sets.push(set.to_vec()))
+            .unwrap();
+        mus.sort();
+        assert!(mus == [10, 11] || mus == [12, 13]);
+        assert!(sets.windows(2).all(|pair| pair[1].len() <= pair[0].len()));
+        assert!(sets
+            .iter()
+            .all(|set| mus.iter().all(|lit| set.contains(lit))));
+    }
+    #[test]
+    fn all_different() {
+        let mut solver = PicoSATSolver::new();
+        solver.add_all_different(&[1, -2]).unwrap();
+        solver.add_all_different(&[3, 4]).unwrap();
+        for group in [&[][..], &[5], &[5, 6, 7], &[5, 0], &[5, -5], &[2, 5]] {
+            assert!(matches!(
+                solver.add_all_different(group),
+                Err(SolverError::InvalidArgument(_) | SolverError::InvalidLiteral(_))
+            ));
+        }
+        // Four bit vectors of length 2 exist, so a fifth group cannot differ from all.
+        for group in [[5, 6], [7, 8], [9, 10]] {
+            solver.add_all_different(&group).unwrap();
+        }
+        assert_eq!(
+            SatSolver::solve_sat(&mut solver).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+    }
+    #[test]
     fn unsat() {
         let mut solver = PicoSATSolver::new();
         SatSolver::push_clause(&mut solver, &vec![1]).unwrap();
@@ -1066,4 +1771,262 @@ mod tests {
             matches!(solver.solve_model().unwrap(),SatStatus::Satisfiable(x) if x.eq(&vec![2]))
         );
     }
+    #[test]
+    fn phases() {
+        let mut solver = PicoSATSolver::new();
+        SatSolver::push_clause(&mut solver, &[1, 2, 3]).unwrap();
+        solver.warm_start_from_model(&[1, -2, -3]).unwrap();
+        assert_eq!(
+            solver.solve_model().unwrap(),
+            SatStatus::Satisfiable(vec![1])
+        );
+        solver.unset_phase(1).unwrap();
+        solver.warm_start_from_model(&[-1, -2, 3]).unwrap();
+        assert_eq!(
+            solver.solve_model().unwrap(),
+            SatStatus::Satisfiable(vec![3])
+        );
+        assert!(solver.set_phase(0).is_err());
+    }
+    #[test]
+    fn budget() {
+        // Pigeonhole with 11 pigeons and 10 holes is far too hard to finish.
+        let mut solver = PicoSATSolver::new();
+        let var = |pigeon: i32, hole: i32| pigeon * 10 + hole + 1;
+        for pigeon in 0..11 {
+            let clause: Vec<i32> = (0..10).map(|hole| var(pigeon, hole)).collect();
+            SatSolver::push_clause(&mut solver, &clause).unwrap();
+        }
+        for hole in 0..10 {
+            for a in 0..11 {
+                for b in a + 1..11 {
+                    SatSolver::push_clause(&mut solver, &[-var(a, hole), -var(b, hole)]).unwrap();
+                }
+            }
+        }
+        let conflicts = Budget {
+            conflicts: Some(10),
+            ..Budget::default()
+        };
+        assert!(solver.set_budget(conflicts).is_err());
+        for (budget, limit) in [
+            (
+                Budget {
+                    decisions: Some(100),
+                    ..Budget::default()
+                },
+                BudgetLimit::Decisions,
+            ),
+            (
+                Budget {
+                    propagations: Some(1000),
+                    ..Budget::default()
+                },
+                BudgetLimit::Propagations,
+            ),
+            (
+                Budget {
+                    memory_mb: Some(0),
+                    ..Budget::default()
+                },
+                BudgetLimit::Memory,
+            ),
+        ] {
+            solver.set_budget(budget).unwrap();
+            assert_eq!(
+                SatSolver::solve_sat(&mut solver).unwrap(),
+                RawStatus::Unknown
+            );
+            assert_eq!(solver.exhausted_budget().unwrap(), Some(limit));
+        }
+    }
+    #[test]
+    fn statistics() {
+        let mut solver = PicoSATSolver::new();
+        SatSolver::push_clause(&mut solver, &[1, 2]).unwrap();
+        SatSolver::push_clause(&mut solver, &[-1, 2]).unwrap();
+        solver.solve_sat().unwrap();
+        let stats = solver.statistics().unwrap();
+        assert_eq!(stats.clauses, Some(2));
+        assert!(stats.decisions.is_some() && stats.time.is_some());
+        assert_eq!(stats.conflicts, None);
+    }
+    #[test]
+    fn last_conflict() {
+        let mut solver = PicoSATSolver::new();
+        SatSolver::push_clause(&mut solver, &[1, 2]).unwrap();
+        SatSolver::push_clause(&mut solver, &[3, 4]).unwrap();
+        solver.assume(-1).unwrap();
+        solver.assume(-2).unwrap();
+        solver.assume(3).unwrap();
+        assert_eq!(solver.sat(-1).unwrap(), RawStatus::Unsatisfiable);
+        let mut conflict = solver.last_conflict().unwrap().unwrap();
+        conflict.sort();
+        assert_eq!(conflict, vec![1, 2]);
+    }
+    #[test]
+    fn decide() {
+        let mut solver = PicoSATSolver::new();
+        SatSolver::push_clause(&mut solver, &[1, 2]).unwrap();
+        SatSolver::push_clause(&mut solver, &[3, 4]).unwrap();
+        let Decision::Unsat(mut core) = solver.decide(vec![-1, -2, 3]).unwrap() else {
+            panic!("assumptions must conflict");
+        };
+        core.sort();
+        assert_eq!(core, vec![-2, -1]);
+        assert!(matches!(solver.decide(vec![-1, 3]).unwrap(), Decision::Sat(m) if m.contains(&2)));
+    }
+    #[test]
+    fn all_muses() {
+        let mut solver = PicoSATSolver::new();
+        for clause in [[1], [-1], [2], [-2]] {
+            MusSolver::push_clause(&mut solver, &clause).unwrap();
+        }
+        assert_eq!(
+            solver.all_muses(None).unwrap(),
+            vec![vec![0, 1], vec![2, 3]]
+        );
+        // The formula is untouched, so a second call enumerates the same MUSes.
+        assert_eq!(solver.all_muses(Some(1)).unwrap().len(), 1);
+        assert_eq!(
+            solver.all_muses(None).unwrap(),
+            vec![vec![0, 1], vec![2, 3]]
+        );
+        let mut solver = PicoSATSolver::new();
+        for clause in [&[1][..], &[-1, 2], &[-2], &[-1]] {
+            MusSolver::push_clause(&mut solver, clause).unwrap();
+        }
+        assert_eq!(
+            solver.all_muses(None).unwrap(),
+            vec![vec![0, 1, 2], vec![0, 3]]
+        );
+        let mut solver = PicoSATSolver::new();
+        MusSolver::push_clause(&mut solver, &[1, 2]).unwrap();
+        assert!(solver.all_muses(None).unwrap().is_empty());
+    }
+    #[test]
+    fn remove_learned() {
+        // Pigeonhole with 6 pigeons and 5 holes forces the solver to learn clauses.
+        let (pigeons, holes) = (6, 5);
+        let var = |p: i32, h: i32| p * holes + h + 1;
+        let mut solver = PicoSATSolver::new();
+        for p in 0..pigeons {
+            let clause: Vec<i32> = (0..holes).map(|h| var(p, h)).collect();
+            SatSolver::push_clause(&mut solver, &clause).unwrap();
+        }
+        for h in 0..holes {
+            for p in 0..pigeons {
+                for q in p + 1..pigeons {
+                    SatSolver::push_clause(&mut solver, &[-var(p, h), -var(q, h)]).unwrap();
+                }
+            }
+        }
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+        // Neither PicoSAT's API nor `satgalaxy-core` exposes the number of learned
+        // clauses (`statistics` only reports the original ones), so the removal cannot
+        // be counted; check that the solver still answers correctly afterwards.
+        Inprocessing::remove_learned(&mut solver, 100).unwrap();
+        Inprocessing::simplify(&mut solver).unwrap();
+        assert_eq!(solver.sat(-1).unwrap(), RawStatus::Unsatisfiable);
+    }
+    #[test]
+    #[cfg(feature = "parser")]
+    fn write_dimacs() {
+        let mut solver = PicoSATSolver::new();
+        MusSolver::push_clause(&mut solver, &[1, -2]).unwrap();
+        MusSolver::push_clause(&mut solver, &[2]).unwrap();
+        let mut out = Vec::new();
+        solver.write_dimacs(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "p cnf 2 2\n1 -2 0\n2 0\n");
+    }
+    #[test]
+    fn write_trace() {
+        let mut solver = PicoSATSolver::new();
+        solver.enable_trace_generation().unwrap();
+        SatSolver::push_clause(&mut solver, &[1, 2]).unwrap();
+        SatSolver::push_clause(&mut solver, &[-1]).unwrap();
+        SatSolver::push_clause(&mut solver, &[-2]).unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+        let mut core = Vec::new();
+        solver
+            .write_trace_to(TraceFormat::ClausalCore, &mut core)
+            .unwrap();
+        assert!(String::from_utf8(core).unwrap().starts_with("p cnf 2 3"));
+        let path = std::env::temp_dir().join("satgalaxy_picosat_trace.rup");
+        solver.write_trace(TraceFormat::Rup, &path).unwrap();
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("%RUPD32"));
+        std::fs::remove_file(&path).unwrap();
+    }
+    #[test]
+    fn mus_timeout() {
+        let mut solver = PicoSATSolver::new();
+        solver.set_mus_config(MusConfig {
+            timeout: Some(Duration::ZERO),
+            ..MusConfig::default()
+        });
+        for clause in [[1, 2], [-1, 2], [1, -2], [-1, -2]] {
+            MusSolver::push_clause(&mut solver, &clause).unwrap();
+        }
+        let start = Instant::now();
+        assert_eq!(solver.solve_mus().unwrap(), MusStatus::Unknown);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+    #[cfg(feature = "parser")]
+    #[test]
+    fn mus_timeout_bounds_final_solve() {
+        use crate::generator::{Generator, Pigeonhole};
+        let mut solver = PicoSATSolver::new();
+        solver.set_mus_config(MusConfig {
+            timeout: Some(Duration::from_millis(200)),
+            ..MusConfig::default()
+        });
+        let problem = Pigeonhole::new(11).to_problem().unwrap();
+        for clause in &problem.clauses {
+            MusSolver::push_clause(&mut solver, clause).unwrap();
+        }
+        let start = Instant::now();
+        let status = solver.solve_mus().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        match status {
+            MusStatus::Unknown => {}
+            MusStatus::Unsatisfiable(core) => assert!(!core.is_empty()),
+            MusStatus::Satisfiable => panic!("pigeonhole is unsatisfiable"),
+        }
+    }
+    #[cfg(feature = "parser")]
+    #[test]
+    fn extract_core_problem() {
+        let mut solver = PicoSATSolver::new();
+        if !solver.enable_trace_generation().unwrap() {
+            return;
+        }
+        let clauses = [
+            vec![1, 2],
+            vec![3, 4],
+            vec![-1],
+            vec![-3, 5],
+            vec![-2],
+            vec![4, 5],
+        ];
+        for clause in &clauses {
+            SatSolver::push_clause(&mut solver, clause).unwrap();
+        }
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+        let core = solver.extract_core_problem().unwrap();
+        assert!(core.num_clauses < clauses.len());
+
+        let mut check = PicoSATSolver::new();
+        for clause in &core.clauses {
+            SatSolver::push_clause(&mut check, clause).unwrap();
+        }
+        assert_eq!(check.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+    }
+    #[test]
+    fn mus_min_literal() {
+        let mut solver = PicoSATSolver::new();
+        assert!(MusSolver::push_clause(&mut solver, &[1, i32::MIN]).is_err());
+        assert!(solver.clauses.is_empty());
+    }
 }
@@ -0,0 +1,226 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+use crate::errors::SolverError;
+
+use super::{Backend, RawStatus, SatSolver};
+
+/// Creates the solver of one portfolio worker.
+type Factory = dyn Fn() -> Result<Box<dyn SatSolver + Send>, SolverError> + Send + Sync;
+
+/// The status, model and conflict a worker found.
+type Outcome = (RawStatus, Vec<i32>, Option<Vec<i32>>);
+
+/// A parallel portfolio over several [`SatSolver`]s.
+///
+/// Every `solve_sat` starts one thread per worker, each with a fresh solver holding all
+/// clauses pushed so far, and returns the first definite answer. The other workers are
+/// then asked to stop through `SatSolver::set_terminate`; workers whose backend has no
+/// termination support run to completion in the background and their result is dropped.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{Backend, CaDiCaLSolver, PortfolioSolver, RawStatus, SatSolver};
+/// let mut portfolio = PortfolioSolver::new();
+/// portfolio.add_backend(Backend::CaDiCaL);
+/// portfolio.add_worker(|| {
+///     let mut solver = CaDiCaLSolver::new();
+///     solver.set_opt_seed(42)?;
+///     Ok(solver)
+/// });
+/// portfolio.push_clause(&[1, 2]).unwrap();
+/// portfolio.push_clause(&[-1]).unwrap();
+/// assert_eq!(portfolio.solve_sat().unwrap(), RawStatus::Satisfiable);
+/// assert_eq!(portfolio.model().unwrap(), vec![2]);
+/// ```
+#[derive(Default)]
+pub struct PortfolioSolver {
+    workers: Vec<Arc<Factory>>,
+    clauses: Arc<Vec<Vec<i32>>>,
+    model: Vec<i32>,
+    conflict: Option<Vec<i32>>,
+    winner: Option<usize>,
+}
+
+impl PortfolioSolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a worker built by `factory`, which is called once per solve on the worker's
+    /// thread; use it to pick a backend and configure seeds or options.
+    pub fn add_worker<S, F>(&mut self, factory: F) -> &mut Self
+    where
+        S: SatSolver + Send + 'static,
+        F: Fn() -> Result<S, SolverError> + Send + Sync + 'static,
+    {
+        self.workers.push(Arc::new(move || {
+            factory().map(|solver| Box::new(solver) as Box<dyn SatSolver + Send>)
+        }));
+        self
+    }
+
+    /// Add a worker running `backend` with its default configuration.
+    pub fn add_backend(&mut self, backend: Backend) -> &mut Self {
+        self.workers.push(Arc::new(move || backend.build()));
+        self
+    }
+
+    /// The number of workers.
+    pub fn len(&self) -> usize {
+        self.workers.len()
+    }
+
+    /// Whether no worker has been added.
+    pub fn is_empty(&self) -> bool {
+        self.workers.is_empty()
+    }
+
+    /// Index of the worker that answered the last solve, if any did.
+    pub fn winner(&self) -> Option<usize> {
+        self.winner
+    }
+
+    fn race(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        if self.workers.is_empty() {
+            return Err(SolverError::InvalidState("Portfolio has no workers"));
+        }
+        self.model.clear();
+        self.conflict = None;
+        self.winner = None;
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel::<(usize, Result<Outcome, SolverError>)>();
+        for (index, factory) in self.workers.iter().enumerate() {
+            let factory = Arc::clone(factory);
+            let clauses = Arc::clone(&self.clauses);
+            let assumptions = assumptions.to_vec();
+            let stop = Arc::clone(&stop);
+            let sender = sender.clone();
+            thread::spawn(move || {
+                let result = run_worker(&*factory, &clauses, &assumptions, stop);
+                // The receiver is gone once another worker has won.
+                let _ = sender.send((index, result));
+            });
+        }
+        drop(sender);
+
+        let mut first_error = None;
+        for (index, result) in receiver {
+            match result {
+                Ok((RawStatus::Unknown, _, _)) => {}
+                Ok((status, model, conflict)) => {
+                    stop.store(true, Ordering::Relaxed);
+                    self.model = model;
+                    self.conflict = conflict;
+                    self.winner = Some(index);
+                    return Ok(status);
+                }
+                Err(err) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(RawStatus::Unknown),
+        }
+    }
+}
+
+fn run_worker(
+    factory: &Factory,
+    clauses: &[Vec<i32>],
+    assumptions: &[i32],
+    stop: Arc<AtomicBool>,
+) -> Result<Outcome, SolverError> {
+    let mut solver = factory()?;
+    // Backends without termination support simply run to completion.
+    let _ = solver.set_terminate(Box::new(move || stop.load(Ordering::Relaxed)));
+    for clause in clauses {
+        solver.push_clause(clause)?;
+    }
+    let status = if assumptions.is_empty() {
+        solver.solve_sat()?
+    } else {
+        solver.solve_with_assumptions(assumptions)?
+    };
+    match status {
+        RawStatus::Satisfiable => Ok((status, solver.model()?, None)),
+        RawStatus::Unsatisfiable if !assumptions.is_empty() => {
+            Ok((status, vec![], solver.last_conflict().ok().flatten()))
+        }
+        _ => Ok((status, vec![], None)),
+    }
+}
+
+impl SatSolver for PortfolioSolver {
+    fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        Arc::make_mut(&mut self.clauses).push(clause.to_vec());
+        Ok(())
+    }
+
+    fn solve_sat(&mut self) -> Result<RawStatus, SolverError> {
+        self.race(&[])
+    }
+
+    fn model(&mut self) -> Result<Vec<i32>, SolverError> {
+        Ok(self.model.clone())
+    }
+
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        Ok(self
+            .clauses
+            .iter()
+            .flatten()
+            .map(|lit| lit.abs())
+            .max()
+            .unwrap_or(0))
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        self.race(assumptions)
+    }
+
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        Ok(self.conflict.clone())
+    }
+}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+    use crate::solver::CaDiCaLSolver;
+
+    #[test]
+    fn race() {
+        let mut portfolio = PortfolioSolver::new();
+        for seed in 0..3 {
+            portfolio.add_worker(move || {
+                let mut solver = CaDiCaLSolver::new();
+                solver.set_opt_seed(seed)?;
+                Ok(solver)
+            });
+        }
+        portfolio.push_clause(&[1, 2]).unwrap();
+        portfolio.push_clause(&[-1, 2]).unwrap();
+        assert_eq!(portfolio.solve_sat().unwrap(), RawStatus::Satisfiable);
+        assert!(portfolio.model().unwrap().contains(&2));
+        assert!(portfolio.winner().unwrap() < 3);
+
+        assert_eq!(
+            portfolio.solve_with_assumptions(&[-2, 3]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        assert_eq!(portfolio.failed_assumptions().unwrap(), vec![-2]);
+
+        portfolio.push_clause(&[-2]).unwrap();
+        assert_eq!(portfolio.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+    }
+
+    #[test]
+    fn no_workers() {
+        let mut portfolio = PortfolioSolver::new();
+        portfolio.push_clause(&[1]).unwrap();
+        assert!(portfolio.solve_sat().is_err());
+    }
+}
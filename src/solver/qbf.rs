@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+
+use crate::errors::SolverError;
+use crate::parser::{QdimacsProblem, Quantifier};
+
+use super::{Backend, RawStatus, SatSolver};
+
+/// The result of [`solve_2qbf`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QbfStatus {
+    True,
+    /// An assignment of the universal variables, one literal each in prefix order, under
+    /// which the matrix is unsatisfiable. Empty if the formula has no universal variables.
+    False {
+        counterexample: Vec<i32>,
+    },
+    Unknown,
+}
+
+/// Decide a QBF of the form `∀X ∃Y. φ` by counterexample-guided expansion.
+///
+/// A candidate solver proposes an assignment `x` of the universal variables, and a
+/// matrix solver looks for a `y` with `φ(x, y)`. If there is none, `x` refutes the
+/// formula. Otherwise `y` is a witness for `x`, and the candidate solver learns
+/// `¬φ(X, y)`, so later candidates must falsify the matrix under `y`; the formula is
+/// true once no candidate is left. Both solvers are created from `backend`.
+///
+/// Prefixes with a single existential block, or none, are plain SAT problems. Free
+/// variables are existential in front of the prefix, so they are only accepted in that
+/// case; other prefixes are `UnsupportedOperation`.
+/// # Example
+/// ```rust
+/// use satgalaxy::parser::{parse_qdimacs, QdimacsProblem};
+/// use satgalaxy::solver::{solve_2qbf, Backend, QbfStatus};
+/// let mut problem = QdimacsProblem::new();
+/// // For every x1 there is an x2 equal to it.
+/// parse_qdimacs("p cnf 2 2\na 1 0\ne 2 0\n-1 2 0\n1 -2 0\n", &mut problem).unwrap();
+/// assert_eq!(solve_2qbf(&problem, Backend::CaDiCaL).unwrap(), QbfStatus::True);
+/// ```
+pub fn solve_2qbf(problem: &QdimacsProblem, backend: Backend) -> Result<QbfStatus, SolverError> {
+    let mut prefix: Vec<(Quantifier, Vec<i32>)> = vec![];
+    for (quantifier, vars) in &problem.prefix {
+        match prefix.last_mut() {
+            Some((last, block)) if last == quantifier => block.extend(vars),
+            _ => prefix.push((*quantifier, vars.clone())),
+        }
+    }
+    // The innermost existential block is the only one the matrix solver may choose.
+    if prefix.last().is_some_and(|(q, _)| *q == Quantifier::Exists) {
+        prefix.pop();
+    }
+    let universal = match &prefix[..] {
+        [] => vec![],
+        [(Quantifier::Forall, vars)] => vars.clone(),
+        _ => {
+            return Err(SolverError::UnsupportedOperation(
+                "QBF prefixes beyond forall-exists",
+            ))
+        }
+    };
+    let bound: HashSet<i32> = problem
+        .prefix
+        .iter()
+        .flat_map(|(_, vars)| vars)
+        .copied()
+        .collect();
+    let has_free = problem
+        .clauses
+        .iter()
+        .flatten()
+        .any(|lit| !bound.contains(&lit.abs()));
+    if !universal.is_empty() && has_free {
+        return Err(SolverError::UnsupportedOperation(
+            "free variables in front of a universal block",
+        ));
+    }
+
+    let mut matrix = backend.build()?;
+    for clause in &problem.clauses {
+        matrix.push_clause(clause)?;
+    }
+    if universal.is_empty() {
+        return Ok(match matrix.solve_sat()? {
+            RawStatus::Satisfiable => QbfStatus::True,
+            RawStatus::Unsatisfiable => QbfStatus::False {
+                counterexample: vec![],
+            },
+            RawStatus::Unknown => QbfStatus::Unknown,
+        });
+    }
+    let universal_set: HashSet<i32> = universal.iter().copied().collect();
+    let mut candidates = backend.build()?;
+    let max_var = problem
+        .clauses
+        .iter()
+        .flatten()
+        .map(|lit| lit.abs())
+        .max()
+        .unwrap_or(0);
+    let mut next_var = max_var.max(problem.num_vars as i32);
+    loop {
+        match candidates.solve_sat()? {
+            RawStatus::Satisfiable => {}
+            RawStatus::Unsatisfiable => return Ok(QbfStatus::True),
+            RawStatus::Unknown => return Ok(QbfStatus::Unknown),
+        }
+        let model: HashSet<i32> = candidates.model()?.into_iter().collect();
+        let x: Vec<i32> = universal
+            .iter()
+            .map(|&var| if model.contains(&var) { var } else { -var })
+            .collect();
+        match matrix.solve_with_assumptions(&x)? {
+            RawStatus::Satisfiable => {}
+            RawStatus::Unsatisfiable => return Ok(QbfStatus::False { counterexample: x }),
+            RawStatus::Unknown => return Ok(QbfStatus::Unknown),
+        }
+        // Learn ¬φ(X, y): some clause not satisfied by the witness `y` has all of its
+        // universal literals false.
+        let witness: HashSet<i32> = matrix.model()?.into_iter().collect();
+        let satisfied_by_witness = |lit: i32| {
+            !universal_set.contains(&lit.abs()) && witness.contains(&lit.abs()) == (lit > 0)
+        };
+        let mut falsified = vec![];
+        for clause in &problem.clauses {
+            if clause.iter().any(|&lit| satisfied_by_witness(lit)) {
+                continue;
+            }
+            next_var += 1;
+            for &lit in clause
+                .iter()
+                .filter(|lit| universal_set.contains(&lit.abs()))
+            {
+                candidates.push_clause(&[-next_var, -lit])?;
+            }
+            falsified.push(next_var);
+        }
+        candidates.push_clause(&falsified)?;
+    }
+}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forall_exists() {
+        let mut problem = QdimacsProblem {
+            prefix: vec![
+                (Quantifier::Forall, vec![1, 2]),
+                (Quantifier::Exists, vec![3]),
+            ],
+            clauses: vec![vec![-1, 3], vec![1, -3], vec![-2, 3], vec![2, -3]],
+            num_vars: 3,
+            comments: vec![],
+        };
+        // y = x1 and y = x2 cannot both hold when x1 != x2.
+        let QbfStatus::False { counterexample } = solve_2qbf(&problem, Backend::CaDiCaL).unwrap()
+        else {
+            panic!("the formula is false");
+        };
+        assert!(counterexample == vec![1, -2] || counterexample == vec![-1, 2]);
+
+        // y = x1 or y = x2 always holds.
+        problem.clauses = vec![vec![-1, 3, -2], vec![1, -3, 2]];
+        assert_eq!(
+            solve_2qbf(&problem, Backend::CaDiCaL).unwrap(),
+            QbfStatus::True
+        );
+
+        problem.prefix.insert(0, (Quantifier::Exists, vec![4]));
+        assert!(solve_2qbf(&problem, Backend::CaDiCaL).is_err());
+    }
+}
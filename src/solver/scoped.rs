@@ -0,0 +1,240 @@
+use std::collections::HashSet;
+
+use crate::errors::SolverError;
+
+use super::{ModelBitset, RawStatus, SatSolver, SolverStats};
+
+/// Push/pop solving: clauses added after `push` are dropped again by the matching `pop`.
+pub trait IncrementalSolver: SatSolver {
+    /// Open a new scope.
+    fn push(&mut self) -> Result<(), SolverError>;
+    /// Close the innermost scope, dropping every clause added since its `push`.
+    ///
+    /// Returns an error if no scope is open.
+    fn pop(&mut self) -> Result<(), SolverError>;
+    /// The number of open scopes.
+    fn depth(&self) -> usize;
+}
+
+/// Adds [`IncrementalSolver`] to any [`SatSolver`] with selector variables.
+///
+/// Each scope owns a fresh selector `s`: clauses added inside it are pushed as
+/// `clause ∨ ¬s`, every solve assumes the selectors of the open scopes, and `pop` adds
+/// the unit `¬s` so the backend can discard the scope's clauses. Selectors are allocated
+/// above `max_var`, hidden from models and cores, and rejected in later clauses. This
+/// works for every backend, including PicoSAT, whose native contexts would otherwise
+/// leak their context variables into models and MUS bookkeeping.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CaDiCaLSolver, IncrementalSolver, RawStatus, SatSolver, Scoped};
+/// let mut solver = Scoped::new(CaDiCaLSolver::new());
+/// solver.push_clause(&[1, 2]).unwrap();
+/// solver.push().unwrap();
+/// solver.push_clause(&[-1]).unwrap();
+/// solver.push_clause(&[-2]).unwrap();
+/// assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+/// solver.pop().unwrap();
+/// assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Scoped<S> {
+    inner: S,
+    /// Selectors of the open scopes, innermost last.
+    scopes: Vec<i32>,
+    /// Every selector ever allocated.
+    selectors: HashSet<i32>,
+    max_var: i32,
+}
+
+impl<S: SatSolver> Scoped<S> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            scopes: vec![],
+            selectors: HashSet::new(),
+            max_var: 0,
+        }
+    }
+
+    /// The wrapped solver.
+    pub fn inner(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Unwrap the solver; clauses of scopes that are still open stay guarded.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn is_selector(&self, lit: i32) -> bool {
+        self.selectors.contains(&lit.abs())
+    }
+
+    fn visible(&self, lits: Vec<i32>) -> Vec<i32> {
+        lits.into_iter()
+            .filter(|&lit| !self.is_selector(lit))
+            .collect()
+    }
+}
+
+impl<S: SatSolver> IncrementalSolver for Scoped<S> {
+    fn push(&mut self) -> Result<(), SolverError> {
+        let inner_max = self.inner.max_var().unwrap_or(0);
+        self.max_var = self.max_var.max(inner_max) + 1;
+        self.selectors.insert(self.max_var);
+        self.scopes.push(self.max_var);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<(), SolverError> {
+        let selector = self
+            .scopes
+            .pop()
+            .ok_or(SolverError::InvalidState("No scope to pop"))?;
+        self.inner.push_clause(&[-selector])
+    }
+
+    fn depth(&self) -> usize {
+        self.scopes.len()
+    }
+}
+
+impl<S: SatSolver> SatSolver for Scoped<S> {
+    fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        if clause.iter().any(|&lit| self.is_selector(lit)) {
+            return Err(SolverError::InvalidArgument(
+                "variable is used as a scope selector",
+            ));
+        }
+        let max_lit = clause
+            .iter()
+            .map(|lit| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        self.max_var = self.max_var.max(max_lit as i32);
+        match self.scopes.last() {
+            Some(&selector) => {
+                let mut guarded = clause.to_vec();
+                guarded.push(-selector);
+                self.inner.push_clause(&guarded)
+            }
+            None => self.inner.push_clause(clause),
+        }
+    }
+
+    fn solve_sat(&mut self) -> Result<RawStatus, SolverError> {
+        if self.scopes.is_empty() {
+            return self.inner.solve_sat();
+        }
+        let scopes = self.scopes.clone();
+        self.inner.solve_with_assumptions(&scopes)
+    }
+
+    fn model(&mut self) -> Result<Vec<i32>, SolverError> {
+        let model = self.inner.model()?;
+        Ok(self.visible(model))
+    }
+
+    fn is_ok(&mut self) -> Result<bool, SolverError> {
+        self.inner.is_ok()
+    }
+
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        Ok(self.max_var)
+    }
+
+    fn model_bitset(&mut self) -> Result<ModelBitset, SolverError> {
+        let bits = self.inner.model_bitset()?;
+        ModelBitset::from_fn(self.max_var as usize, |var| Ok(bits.bit(var)))
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        if assumptions.iter().any(|&lit| self.is_selector(lit)) {
+            return Err(SolverError::InvalidArgument(
+                "variable is used as a scope selector",
+            ));
+        }
+        let mut all = self.scopes.clone();
+        all.extend_from_slice(assumptions);
+        self.inner.solve_with_assumptions(&all)
+    }
+
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        let conflict = self.inner.last_conflict()?;
+        Ok(conflict.map(|conflict| self.visible(conflict)))
+    }
+
+    fn failed_assumptions(&mut self) -> Result<Vec<i32>, SolverError> {
+        let failed = self.inner.failed_assumptions()?;
+        Ok(self.visible(failed))
+    }
+
+    fn unsat_core(&mut self) -> Result<Vec<i32>, SolverError> {
+        let core = self.inner.unsat_core()?;
+        Ok(self.visible(core))
+    }
+
+    fn set_terminate(
+        &mut self,
+        callback: Box<dyn FnMut() -> bool + Send>,
+    ) -> Result<(), SolverError> {
+        self.inner.set_terminate(callback)
+    }
+
+    fn statistics(&mut self) -> Result<SolverStats, SolverError> {
+        self.inner.statistics()
+    }
+}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+    use crate::solver::CaDiCaLSolver;
+
+    #[test]
+    fn push_pop() {
+        let mut solver = Scoped::new(CaDiCaLSolver::new());
+        solver.push_clause(&[1, 2]).unwrap();
+        assert!(solver.pop().is_err());
+        solver.push().unwrap();
+        solver.push_clause(&[-1]).unwrap();
+        solver.push().unwrap();
+        solver.push_clause(&[-2]).unwrap();
+        assert_eq!(solver.depth(), 2);
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+        solver.pop().unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        assert_eq!(solver.model().unwrap(), vec![2]);
+        solver.pop().unwrap();
+        assert_eq!(
+            solver.solve_with_assumptions(&[-1, -2]).unwrap(),
+            RawStatus::Unsatisfiable
+        );
+        let mut failed = solver.failed_assumptions().unwrap();
+        failed.sort();
+        assert_eq!(failed, vec![-2, -1]);
+    }
+
+    #[test]
+    fn count_models() {
+        let mut solver = Scoped::new(CaDiCaLSolver::new());
+        solver.push_clause(&[1, 3]).unwrap();
+        solver.push().unwrap();
+        solver.push_clause(&[2]).unwrap();
+        let count = solver.count_models(10).unwrap();
+        assert_eq!(count.exact, Some(3));
+        solver.pop().unwrap();
+        // The blocking clauses of the scope are dropped with it; variable 2 is free.
+        let count = solver.count_models(10).unwrap();
+        assert_eq!(count.exact, Some(6));
+    }
+
+    #[test]
+    fn rejects_selectors() {
+        let mut solver = Scoped::new(CaDiCaLSolver::new());
+        solver.push_clause(&[1]).unwrap();
+        solver.push().unwrap();
+        assert!(solver.push_clause(&[2]).is_err());
+        assert!(solver.solve_with_assumptions(&[-2]).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::errors::SolverError;
+
+/// A solver shared between threads behind a mutex.
+///
+/// The backend wrappers are `Send` but not `Sync`: a solver may move to another thread,
+/// but the C and C++ solvers must not be used from two threads at once. A
+/// `SharedSolver` is `Send + Sync` for any `Send` solver, and its clones are handles to
+/// the same solver; `lock` gives one thread at a time exclusive access.
+///
+/// A solve holds the lock until it returns, so stop it through a handle that needs no
+/// lock, such as `CaDiCaLSolver::termination_handle`, rather than `set_terminate`.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CaDiCaLSolver, RawStatus, SatSolver, SharedSolver};
+/// let solver = SharedSolver::new(CaDiCaLSolver::new());
+/// let handles: Vec<_> = (1..=4)
+///     .map(|var| {
+///         let solver = solver.clone();
+///         std::thread::spawn(move || solver.lock().unwrap().push_clause(&[var]).unwrap())
+///     })
+///     .collect();
+/// for handle in handles {
+///     handle.join().unwrap();
+/// }
+/// let mut solver = solver.lock().unwrap();
+/// assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+/// assert_eq!(solver.model().unwrap(), vec![1, 2, 3, 4]);
+/// ```
+#[derive(Debug, Default)]
+pub struct SharedSolver<S> {
+    inner: Arc<Mutex<S>>,
+}
+
+impl<S> Clone for SharedSolver<S> {
+    fn clone(&self) -> Self {
+        SharedSolver {
+            inner: Arc::clone(&self.inner),
+        }
+    }
+}
+
+impl<S: Send> SharedSolver<S> {
+    pub fn new(solver: S) -> Self {
+        SharedSolver {
+            inner: Arc::new(Mutex::new(solver)),
+        }
+    }
+
+    /// Wait for exclusive access to the solver.
+    ///
+    /// Fails with `SolverError::InvalidState` if a thread panicked while holding the
+    /// lock, since the solver may have been left in the middle of an operation.
+    pub fn lock(&self) -> Result<MutexGuard<'_, S>, SolverError> {
+        self.inner
+            .lock()
+            .map_err(|_| SolverError::InvalidState("a thread panicked while using the solver"))
+    }
+
+    /// The solver, if this is the last handle to it.
+    pub fn into_inner(self) -> Result<S, Self> {
+        match Arc::try_unwrap(self.inner) {
+            Ok(mutex) => Ok(mutex.into_inner().unwrap_or_else(|err| err.into_inner())),
+            Err(inner) => Err(SharedSolver { inner }),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+    use crate::solver::{CaDiCaLSolver, RawStatus, SatSolver};
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn shared_between_threads() {
+        assert_send_sync::<SharedSolver<CaDiCaLSolver>>();
+        let solver = SharedSolver::new(CaDiCaLSolver::new());
+        std::thread::scope(|scope| {
+            for var in 1..=8 {
+                let solver = solver.clone();
+                scope.spawn(move || solver.lock().unwrap().push_clause(&[-var]).unwrap());
+            }
+        });
+        let solver = solver.into_inner().unwrap();
+        let poisoned = SharedSolver::new(solver);
+        let handle = poisoned.clone();
+        std::thread::spawn(move || {
+            let _guard = handle.lock().unwrap();
+            panic!("poison the lock");
+        })
+        .join()
+        .unwrap_err();
+        assert!(matches!(poisoned.lock(), Err(SolverError::InvalidState(_))));
+        let mut solver = poisoned.into_inner().unwrap();
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Satisfiable);
+        assert_eq!(solver.max_var().unwrap(), 8);
+    }
+}
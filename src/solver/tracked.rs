@@ -0,0 +1,292 @@
+use std::collections::HashMap;
+
+use crate::errors::SolverError;
+
+use super::{shrink_core, ModelBitset, MusStatus, RawStatus, SatSolver, SolverStats};
+
+/// A clause added with [`TrackedSolver::add_clause`], and its label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedClause<L> {
+    pub clause: Vec<i32>,
+    pub label: L,
+    /// The selector variable guarding the clause in the wrapped solver.
+    selector: i32,
+}
+
+/// Gives clauses stable IDs and labels, and reports cores in terms of them.
+///
+/// A clause added with `add_clause` is pushed as `clause ∨ ¬s` for a fresh selector
+/// `s`, and every solve assumes the selectors of all tracked clauses. An UNSAT answer
+/// can then be explained by `core` (the IDs of the tracked clauses involved) or `mus`
+/// (a minimal such set), and both have `*_labels` variants returning the user labels.
+/// Clauses pushed through `SatSolver::push_clause` are hard: they are never part of
+/// a core. Selectors are allocated above `max_var`, hidden from models and cores, and
+/// rejected in later clauses.
+/// # Example
+/// ```rust
+/// use satgalaxy::solver::{CaDiCaLSolver, RawStatus, SatSolver, TrackedSolver};
+/// let mut solver: TrackedSolver<_> = TrackedSolver::new(CaDiCaLSolver::new());
+/// solver.push_clause(&[-1, -2]).unwrap();
+/// solver.add_clause(&[1], "wants 1").unwrap();
+/// solver.add_clause(&[3], "wants 3").unwrap();
+/// solver.add_clause(&[2], "wants 2").unwrap();
+/// assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+/// assert_eq!(solver.mus_labels().unwrap(), vec!["wants 1", "wants 2"]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TrackedSolver<S, L = String> {
+    inner: S,
+    /// Tracked clauses, indexed by ID.
+    clauses: Vec<TrackedClause<L>>,
+    /// The ID of the clause each selector guards.
+    by_selector: HashMap<i32, usize>,
+    max_var: i32,
+}
+
+impl<S: SatSolver, L> TrackedSolver<S, L> {
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            clauses: vec![],
+            by_selector: HashMap::new(),
+            max_var: 0,
+        }
+    }
+
+    /// The wrapped solver.
+    pub fn inner(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Unwrap the solver; tracked clauses stay guarded by their selectors.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Add `clause` under `label`, returning its ID.
+    ///
+    /// IDs are assigned consecutively from 0 and never change.
+    pub fn add_clause(
+        &mut self,
+        clause: &[i32],
+        label: impl Into<L>,
+    ) -> Result<usize, SolverError> {
+        self.check(clause)?;
+        let max_lit = clause
+            .iter()
+            .map(|lit| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        let inner_max = self.inner.max_var().unwrap_or(0);
+        self.max_var = self.max_var.max(inner_max).max(max_lit as i32) + 1;
+        let selector = self.max_var;
+        let mut guarded = clause.to_vec();
+        guarded.push(-selector);
+        self.inner.push_clause(&guarded)?;
+        let id = self.clauses.len();
+        self.by_selector.insert(selector, id);
+        self.clauses.push(TrackedClause {
+            clause: clause.to_vec(),
+            label: label.into(),
+            selector,
+        });
+        Ok(id)
+    }
+
+    /// The tracked clause with ID `id`.
+    pub fn get(&self, id: usize) -> Option<&TrackedClause<L>> {
+        self.clauses.get(id)
+    }
+
+    /// The label of the tracked clause with ID `id`.
+    pub fn label(&self, id: usize) -> Option<&L> {
+        self.clauses.get(id).map(|tracked| &tracked.label)
+    }
+
+    /// The tracked clauses, indexed by ID.
+    pub fn clauses(&self) -> &[TrackedClause<L>] {
+        &self.clauses
+    }
+
+    /// The IDs of the tracked clauses behind the last UNSAT answer, in ascending order.
+    ///
+    /// Derived from the failed assumptions, so it is not necessarily minimal, see `mus`.
+    pub fn core(&mut self) -> Result<Vec<usize>, SolverError> {
+        let failed = self.inner.failed_assumptions()?;
+        Ok(self.ids(&failed))
+    }
+
+    /// The labels of `core`.
+    pub fn core_labels(&mut self) -> Result<Vec<&L>, SolverError> {
+        let core = self.core()?;
+        Ok(self.labels(&core))
+    }
+
+    /// A minimal set of tracked clauses that is unsatisfiable together with the hard
+    /// clauses, by ID.
+    ///
+    /// Solves again, starting from the failed assumptions and removing selectors one at
+    /// a time.
+    pub fn mus(&mut self) -> Result<MusStatus, SolverError> {
+        let selectors = self.selectors();
+        match self.inner.solve_with_assumptions(&selectors)? {
+            RawStatus::Satisfiable => return Ok(MusStatus::Satisfiable),
+            RawStatus::Unknown => return Ok(MusStatus::Unknown),
+            RawStatus::Unsatisfiable => {}
+        }
+        let failed = self.inner.failed_assumptions()?;
+        let core = if failed.is_empty() { selectors } else { failed };
+        let mus = shrink_core(&mut self.inner, core)?;
+        Ok(MusStatus::Unsatisfiable(self.ids(&mus)))
+    }
+
+    /// The labels of `mus`; empty if the tracked clauses are satisfiable.
+    pub fn mus_labels(&mut self) -> Result<Vec<&L>, SolverError> {
+        match self.mus()? {
+            MusStatus::Unsatisfiable(mus) => Ok(self.labels(&mus)),
+            MusStatus::Satisfiable => Ok(vec![]),
+            MusStatus::Unknown => Err(SolverError::Terminated),
+        }
+    }
+
+    fn check(&self, lits: &[i32]) -> Result<(), SolverError> {
+        if lits
+            .iter()
+            .any(|lit| self.by_selector.contains_key(&lit.abs()))
+        {
+            return Err(SolverError::InvalidArgument(
+                "variable is used as a clause selector",
+            ));
+        }
+        Ok(())
+    }
+
+    fn selectors(&self) -> Vec<i32> {
+        self.clauses
+            .iter()
+            .map(|tracked| tracked.selector)
+            .collect()
+    }
+
+    /// The IDs of the tracked clauses whose selectors occur in `lits`, in ascending order.
+    fn ids(&self, lits: &[i32]) -> Vec<usize> {
+        let mut ids: Vec<usize> = lits
+            .iter()
+            .filter_map(|lit| self.by_selector.get(&lit.abs()).copied())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    fn labels(&self, ids: &[usize]) -> Vec<&L> {
+        ids.iter().map(|&id| &self.clauses[id].label).collect()
+    }
+
+    fn visible(&self, lits: Vec<i32>) -> Vec<i32> {
+        lits.into_iter()
+            .filter(|lit| !self.by_selector.contains_key(&lit.abs()))
+            .collect()
+    }
+}
+
+impl<S: SatSolver, L> SatSolver for TrackedSolver<S, L> {
+    /// Add a hard clause, which is not tracked.
+    fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+        self.check(clause)?;
+        let max_lit = clause
+            .iter()
+            .map(|lit| lit.unsigned_abs())
+            .max()
+            .unwrap_or(0);
+        self.max_var = self.max_var.max(max_lit as i32);
+        self.inner.push_clause(clause)
+    }
+
+    fn solve_sat(&mut self) -> Result<RawStatus, SolverError> {
+        let selectors = self.selectors();
+        self.inner.solve_with_assumptions(&selectors)
+    }
+
+    fn model(&mut self) -> Result<Vec<i32>, SolverError> {
+        let model = self.inner.model()?;
+        Ok(self.visible(model))
+    }
+
+    fn is_ok(&mut self) -> Result<bool, SolverError> {
+        self.inner.is_ok()
+    }
+
+    fn max_var(&mut self) -> Result<i32, SolverError> {
+        Ok(self.max_var)
+    }
+
+    fn model_bitset(&mut self) -> Result<ModelBitset, SolverError> {
+        let bits = self.inner.model_bitset()?;
+        ModelBitset::from_fn(self.max_var as usize, |var| Ok(bits.bit(var)))
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<RawStatus, SolverError> {
+        self.check(assumptions)?;
+        let mut all = self.selectors();
+        all.extend_from_slice(assumptions);
+        self.inner.solve_with_assumptions(&all)
+    }
+
+    fn last_conflict(&mut self) -> Result<Option<Vec<i32>>, SolverError> {
+        let conflict = self.inner.last_conflict()?;
+        Ok(conflict.map(|conflict| self.visible(conflict)))
+    }
+
+    fn failed_assumptions(&mut self) -> Result<Vec<i32>, SolverError> {
+        let failed = self.inner.failed_assumptions()?;
+        Ok(self.visible(failed))
+    }
+
+    fn set_terminate(
+        &mut self,
+        callback: Box<dyn FnMut() -> bool + Send>,
+    ) -> Result<(), SolverError> {
+        self.inner.set_terminate(callback)
+    }
+
+    fn statistics(&mut self) -> Result<SolverStats, SolverError> {
+        self.inner.statistics()
+    }
+}
+
+#[cfg(all(test, feature = "cadical"))]
+mod tests {
+    use super::*;
+    use crate::solver::CaDiCaLSolver;
+
+    #[test]
+    fn core_and_mus() {
+        let mut solver: TrackedSolver<_, &str> = TrackedSolver::new(CaDiCaLSolver::new());
+        solver.push_clause(&[-1, -2]).unwrap();
+        assert_eq!(solver.add_clause(&[1, 3], "a").unwrap(), 0);
+        assert_eq!(solver.add_clause(&[-3], "b").unwrap(), 1);
+        assert_eq!(solver.add_clause(&[1, 2], "c").unwrap(), 2);
+        assert_eq!(solver.add_clause(&[2], "d").unwrap(), 3);
+        // Selectors are 4 to 7.
+        assert!(solver.push_clause(&[5]).is_err());
+        assert_eq!(solver.label(1), Some(&"b"));
+
+        assert_eq!(solver.solve_sat().unwrap(), RawStatus::Unsatisfiable);
+        let core = solver.core().unwrap();
+        assert!([0, 1, 3].iter().all(|id| core.contains(id)));
+        assert_eq!(
+            solver.mus().unwrap(),
+            MusStatus::Unsatisfiable(vec![0, 1, 3])
+        );
+        assert_eq!(solver.mus_labels().unwrap(), vec![&"a", &"b", &"d"]);
+
+        let mut relaxed: TrackedSolver<_, &str> = TrackedSolver::new(CaDiCaLSolver::new());
+        relaxed.add_clause(&[1], "a").unwrap();
+        assert_eq!(
+            relaxed.solve_model().unwrap(),
+            crate::solver::SatStatus::Satisfiable(vec![1])
+        );
+        assert_eq!(relaxed.mus().unwrap(), MusStatus::Satisfiable);
+    }
+}
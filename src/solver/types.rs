@@ -0,0 +1,183 @@
+use std::fmt;
+use std::num::NonZeroU32;
+use std::ops::Not;
+
+use crate::errors::SolverError;
+
+/// A propositional variable, numbered from 1 as in DIMACS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "u32", into = "u32"))]
+pub struct Var(NonZeroU32);
+
+impl Var {
+    /// The largest index a variable can have, so that both of its literals fit an `i32`.
+    pub const MAX_INDEX: u32 = i32::MAX as u32;
+
+    /// Create the variable with `index`, or `None` if it is 0 or above `MAX_INDEX`.
+    pub fn new(index: u32) -> Option<Self> {
+        if index > Self::MAX_INDEX {
+            return None;
+        }
+        NonZeroU32::new(index).map(Var)
+    }
+
+    /// The 1-based index of the variable.
+    pub fn index(self) -> u32 {
+        self.0.get()
+    }
+
+    /// The positive literal of this variable.
+    pub fn positive(self) -> Lit {
+        Lit(self.0.get() as i32)
+    }
+
+    /// The negative literal of this variable.
+    pub fn negative(self) -> Lit {
+        Lit(-(self.0.get() as i32))
+    }
+}
+
+/// A literal: a variable or its negation, encoded as a nonzero DIMACS integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "i32", into = "i32"))]
+pub struct Lit(i32);
+
+impl Lit {
+    /// Create a literal from its DIMACS encoding, or `None` if it is 0 or `i32::MIN`.
+    pub fn new(lit: i32) -> Option<Self> {
+        (lit != 0 && lit != i32::MIN).then_some(Lit(lit))
+    }
+
+    /// The variable of this literal.
+    pub fn var(self) -> Var {
+        Var(NonZeroU32::new(self.0.unsigned_abs()).unwrap())
+    }
+
+    /// Whether this is the positive literal of its variable.
+    pub fn is_positive(self) -> bool {
+        self.0 > 0
+    }
+
+    /// The DIMACS encoding of this literal.
+    pub fn to_i32(self) -> i32 {
+        self.0
+    }
+}
+
+impl Not for Lit {
+    type Output = Lit;
+    fn not(self) -> Lit {
+        Lit(-self.0)
+    }
+}
+
+impl From<Var> for Lit {
+    fn from(var: Var) -> Self {
+        var.positive()
+    }
+}
+
+impl From<Lit> for i32 {
+    fn from(lit: Lit) -> Self {
+        lit.0
+    }
+}
+
+impl From<Var> for u32 {
+    fn from(var: Var) -> Self {
+        var.index()
+    }
+}
+
+impl TryFrom<i32> for Lit {
+    type Error = SolverError;
+    fn try_from(lit: i32) -> Result<Self, Self::Error> {
+        Lit::new(lit).ok_or(SolverError::InvalidLiteral(lit))
+    }
+}
+
+impl TryFrom<u32> for Var {
+    type Error = SolverError;
+    fn try_from(index: u32) -> Result<Self, Self::Error> {
+        Var::new(index).ok_or(SolverError::InvalidArgument(
+            "variable index must be in 1..=i32::MAX",
+        ))
+    }
+}
+
+impl TryFrom<i32> for Var {
+    type Error = SolverError;
+    fn try_from(index: i32) -> Result<Self, Self::Error> {
+        u32::try_from(index)
+            .ok()
+            .and_then(Var::new)
+            .ok_or(SolverError::InvalidArgument(
+                "variable index must be in 1..=i32::MAX",
+            ))
+    }
+}
+
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl fmt::Display for Lit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::{RawStatus, SatSolver};
+
+    #[derive(Default)]
+    struct Recorder(Vec<Vec<i32>>);
+    impl SatSolver for Recorder {
+        fn push_clause(&mut self, clause: &[i32]) -> Result<(), SolverError> {
+            self.0.push(clause.to_vec());
+            Ok(())
+        }
+        fn solve_sat(&mut self) -> Result<RawStatus, SolverError> {
+            Ok(RawStatus::Unknown)
+        }
+        fn model(&mut self) -> Result<Vec<i32>, SolverError> {
+            Ok(vec![])
+        }
+    }
+
+    #[test]
+    fn push_lits() {
+        let (x, y) = (Var::new(1).unwrap(), Var::new(2).unwrap());
+        let mut solver = Recorder::default();
+        solver.push_lits([x.positive(), !Lit::from(y)]).unwrap();
+        solver.push_lits([y]).unwrap();
+        solver.push_clause(&[-1]).unwrap();
+        assert_eq!(solver.0, vec![vec![1, -2], vec![2], vec![-1]]);
+    }
+
+    #[test]
+    fn checked_construction() {
+        assert!(Lit::new(0).is_none());
+        assert!(Lit::new(i32::MIN).is_none());
+        assert_eq!(Lit::try_from(0), Err(SolverError::InvalidLiteral(0)));
+        assert!(Var::new(0).is_none());
+        assert!(Var::new(Var::MAX_INDEX + 1).is_none());
+        assert!(Var::try_from(-3).is_err());
+
+        let var = Var::try_from(3).unwrap();
+        assert_eq!(i32::from(var.negative()), -3);
+        assert_eq!(!Lit::from(var), var.negative());
+        assert_eq!(Lit::new(-3).unwrap().var(), var);
+        assert!(!var.negative().is_positive());
+        assert_eq!(
+            Var::new(Var::MAX_INDEX).unwrap().negative().to_i32(),
+            -i32::MAX
+        );
+    }
+}
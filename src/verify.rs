@@ -0,0 +1,62 @@
+//! Checking solver answers against the formula they were computed for.
+//!
+//! The backends are reached through `unsafe` FFI, so `verify_model` is a cheap safety
+//! net for anything that relies on a model being correct.
+use std::collections::HashSet;
+
+use thiserror::Error;
+
+/// The first clause a model fails to satisfy.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("Clause {index} ({clause:?}) is not satisfied by the model")]
+pub struct UnsatisfiedClause {
+    /// The position of the clause in the formula.
+    pub index: usize,
+    pub clause: Vec<i32>,
+}
+
+/// Check that `model` satisfies every clause of `clauses`.
+///
+/// `model` lists the true literals, as returned by `SatSolver::model`: a variable that
+/// does not appear positively is false, so negative literals may be given or left out.
+/// # Example
+/// ```rust
+/// use satgalaxy::verify::verify_model;
+/// let clauses = vec![vec![1, 2], vec![-1]];
+/// assert!(verify_model(&clauses, &[2]).is_ok());
+/// assert_eq!(verify_model(&clauses, &[1]).unwrap_err().index, 1);
+/// ```
+pub fn verify_model(clauses: &[Vec<i32>], model: &[i32]) -> Result<(), UnsatisfiedClause> {
+    let positive: HashSet<i32> = model.iter().copied().filter(|&lit| lit > 0).collect();
+    let holds = |lit: i32| positive.contains(&lit.abs()) == (lit > 0);
+    match clauses
+        .iter()
+        .position(|clause| !clause.iter().any(|&lit| holds(lit)))
+    {
+        Some(index) => Err(UnsatisfiedClause {
+            index,
+            clause: clauses[index].clone(),
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify() {
+        let clauses = vec![vec![1, -2], vec![2, 3], vec![-1, -3]];
+        assert!(verify_model(&clauses, &[1, 2]).is_ok());
+        assert!(verify_model(&clauses, &[1, 2, -3]).is_ok());
+        assert_eq!(
+            verify_model(&clauses, &[1, 3]),
+            Err(UnsatisfiedClause {
+                index: 2,
+                clause: vec![-1, -3]
+            })
+        );
+        assert_eq!(verify_model(&[vec![]], &[]).unwrap_err().index, 0);
+    }
+}
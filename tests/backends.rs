@@ -0,0 +1,21 @@
+//! Builds every enabled backend against the `SatSolver` trait as a separate crate, so
+//! that each backend feature is checked on its own. Run the suite once per feature,
+//! e.g. `cargo test --no-default-features --features minisat --test backends`.
+
+/// The test program of each backend, and whether its feature is enabled.
+const BACKENDS: [(&str, bool); 4] = [
+    ("tests/compile/cadical.rs", cfg!(feature = "cadical")),
+    ("tests/compile/glucose.rs", cfg!(feature = "glucose")),
+    ("tests/compile/minisat.rs", cfg!(feature = "minisat")),
+    ("tests/compile/picosat.rs", cfg!(feature = "picosat")),
+];
+
+#[test]
+fn backends_implement_sat_solver() {
+    let cases = trybuild::TestCases::new();
+    for (path, enabled) in BACKENDS {
+        if enabled {
+            cases.pass(path);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! `MinisatSolver` used through the `SatSolver` trait from outside the crate.
+use satgalaxy::solver::{MinisatSolver, RawStatus, SatSolver};
+
+fn solve<S: SatSolver + Send + 'static>(mut solver: S) -> RawStatus {
+    solver.push_clause(&[1, 2]).unwrap();
+    solver.push_clause(&[-1]).unwrap();
+    solver.solve_sat().unwrap()
+}
+
+fn main() {
+    assert_eq!(solve(MinisatSolver::new()), RawStatus::Satisfiable);
+    let boxed: Box<dyn SatSolver + Send> = Box::new(MinisatSolver::new());
+    assert_eq!(solve(boxed), RawStatus::Satisfiable);
+}